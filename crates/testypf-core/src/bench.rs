@@ -0,0 +1,230 @@
+use super::*;
+use std::time::Instant;
+
+/// How many times each combination is rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BenchmarkConfig {
+    /// Untimed renders before measuring (warms typf, Python and font caches)
+    pub warmup: usize,
+
+    /// Timed renders per combination
+    pub iterations: usize,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 2,
+            iterations: 10,
+        }
+    }
+}
+
+/// One (font, backend, settings) combination to benchmark; the backend comes from `settings`
+#[derive(Debug, Clone)]
+pub struct BenchmarkCase {
+    pub font_name: String,
+    pub font_path: PathBuf,
+    pub settings: RenderSettings,
+}
+
+/// Timing statistics for one benchmark case
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BenchmarkStats {
+    pub font: String,
+    pub font_path: PathBuf,
+    pub backend: String,
+    pub font_size: f32,
+    pub warmup: usize,
+    /// Number of successful timed renders
+    pub iterations: usize,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub p95_ms: f64,
+    pub mean_ms: f64,
+    /// Renders per second, from the mean
+    pub renders_per_sec: f64,
+    /// Output megapixels per second, from the mean
+    pub megapixels_per_sec: f64,
+    /// Peak resident set size of the process after this case, if the platform reports it
+    pub peak_rss_bytes: Option<u64>,
+    /// First render error, if the case failed
+    pub error: Option<String>,
+}
+
+/// Render every case `warmup + iterations` times and collect timing statistics.
+///
+/// The renderer's backend is switched per case and restored afterwards. `progress` is called
+/// with (finished cases, total cases).
+pub fn run_benchmark(
+    renderer: &mut dyn TextRenderer,
+    cases: &[BenchmarkCase],
+    config: BenchmarkConfig,
+    mut progress: impl FnMut(usize, usize),
+) -> Vec<BenchmarkStats> {
+    let previous = renderer.backend();
+    let mut results = Vec::with_capacity(cases.len());
+
+    for (index, case) in cases.iter().enumerate() {
+        let mut samples = Vec::with_capacity(config.iterations);
+        let mut pixels = 0u64;
+        let mut error = renderer.set_backend(case.settings.backend.clone()).err();
+
+        if error.is_none() {
+            for run in 0..config.warmup + config.iterations {
+                let started = Instant::now();
+                match renderer.render_text(&case.font_path, &case.settings) {
+                    Ok(result) => {
+                        let elapsed = started.elapsed().as_secs_f64() * 1000.0;
+                        if run >= config.warmup {
+                            samples.push(elapsed);
+                            pixels = result.width as u64 * result.height as u64;
+                        }
+                    }
+                    Err(e) => {
+                        error = Some(e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        results.push(summarize(
+            case,
+            config,
+            &samples,
+            pixels,
+            error.map(|e| e.to_string()),
+        ));
+        progress(index + 1, cases.len());
+    }
+    let _ = renderer.set_backend(previous);
+
+    results
+}
+
+fn summarize(
+    case: &BenchmarkCase,
+    config: BenchmarkConfig,
+    samples: &[f64],
+    pixels: u64,
+    error: Option<String>,
+) -> BenchmarkStats {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let mean = if sorted.is_empty() {
+        0.0
+    } else {
+        sorted.iter().sum::<f64>() / sorted.len() as f64
+    };
+    let per_sec = |amount: f64| {
+        if mean > 0.0 {
+            amount * 1000.0 / mean
+        } else {
+            0.0
+        }
+    };
+
+    BenchmarkStats {
+        font: case.font_name.clone(),
+        font_path: case.font_path.clone(),
+        backend: case.settings.backend.to_string(),
+        font_size: case.settings.font_size,
+        warmup: config.warmup,
+        iterations: sorted.len(),
+        min_ms: sorted.first().copied().unwrap_or(0.0),
+        median_ms: percentile(&sorted, 50.0),
+        p95_ms: percentile(&sorted, 95.0),
+        mean_ms: mean,
+        renders_per_sec: per_sec(1.0),
+        megapixels_per_sec: per_sec(pixels as f64 / 1_000_000.0),
+        peak_rss_bytes: peak_rss_bytes(),
+        error,
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice (0.0 when empty)
+pub fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (pct / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Peak resident set size of this process in bytes, where the platform exposes it
+pub fn peak_rss_bytes() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+        let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+        Some(kb * 1024)
+    }
+
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        let mut usage = std::mem::MaybeUninit::<libc::rusage>::zeroed();
+        // SAFETY: getrusage only writes into the provided struct
+        let rc = unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) };
+        if rc != 0 {
+            return None;
+        }
+        // SAFETY: getrusage succeeded, so the struct is initialized
+        let max_rss = unsafe { usage.assume_init() }.ru_maxrss;
+        // macOS reports bytes, the BSDs kilobytes
+        if cfg!(target_os = "macos") {
+            Some(max_rss as u64)
+        } else {
+            Some(max_rss as u64 * 1024)
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+/// Benchmark results as pretty-printed JSON
+pub fn to_json(results: &[BenchmarkStats]) -> TestypfResult<String> {
+    serde_json::to_string_pretty(results)
+        .map_err(|e| TestypfError::RenderFailed(format!("Failed to serialize benchmark: {e}")))
+}
+
+/// Benchmark results as CSV with a header row
+pub fn to_csv(results: &[BenchmarkStats]) -> String {
+    let mut csv = String::from(
+        "font,font_path,backend,font_size,warmup,iterations,min_ms,median_ms,p95_ms,mean_ms,renders_per_sec,megapixels_per_sec,peak_rss_bytes,error\n",
+    );
+    for r in results {
+        let fields = [
+            csv_field(&r.font),
+            csv_field(&r.font_path.display().to_string()),
+            csv_field(&r.backend),
+            r.font_size.to_string(),
+            r.warmup.to_string(),
+            r.iterations.to_string(),
+            format!("{:.3}", r.min_ms),
+            format!("{:.3}", r.median_ms),
+            format!("{:.3}", r.p95_ms),
+            format!("{:.3}", r.mean_ms),
+            format!("{:.2}", r.renders_per_sec),
+            format!("{:.3}", r.megapixels_per_sec),
+            r.peak_rss_bytes.map(|b| b.to_string()).unwrap_or_default(),
+            csv_field(r.error.as_deref().unwrap_or("")),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,356 @@
+use super::*;
+use std::collections::BTreeSet;
+
+/// Printable ASCII and the Latin-1 Supplement
+const LATIN_1: &[(u32, u32)] = &[(0x0020, 0x007E), (0x00A0, 0x00FF)];
+
+/// Characters Windows-1252 adds in 0x80–0x9F
+const WIN_1252: &[(u32, u32)] = &[
+    (0x0152, 0x0153),
+    (0x0160, 0x0161),
+    (0x0178, 0x0178),
+    (0x017D, 0x017E),
+    (0x0192, 0x0192),
+    (0x02C6, 0x02C6),
+    (0x02DC, 0x02DC),
+    (0x2013, 0x2014),
+    (0x2018, 0x201A),
+    (0x201C, 0x201E),
+    (0x2020, 0x2022),
+    (0x2026, 0x2026),
+    (0x2030, 0x2030),
+    (0x2039, 0x203A),
+    (0x20AC, 0x20AC),
+    (0x2122, 0x2122),
+];
+
+/// Mac Roman and Adobe Standard characters beyond Windows-1252
+const MAC_ROMAN: &[(u32, u32)] = &[
+    (0x0131, 0x0131),
+    (0x0141, 0x0142),
+    (0x02C7, 0x02C7),
+    (0x02D8, 0x02DB),
+    (0x02DD, 0x02DD),
+    (0x03C0, 0x03C0),
+    (0x2044, 0x2044),
+    (0x2126, 0x2126),
+    (0x2202, 0x2202),
+    (0x2206, 0x2206),
+    (0x220F, 0x220F),
+    (0x2211, 0x2212),
+    (0x221A, 0x221A),
+    (0x221E, 0x221E),
+    (0x222B, 0x222B),
+    (0x2248, 0x2248),
+    (0x2260, 0x2260),
+    (0x2264, 0x2265),
+    (0x25CA, 0x25CA),
+    (0xFB01, 0xFB02),
+];
+
+/// Central European, Baltic, Turkish and Romanian letters
+const ADOBE_LATIN_2: &[(u32, u32)] = &[
+    (0x0100, 0x0107),
+    (0x010C, 0x0113),
+    (0x0116, 0x011B),
+    (0x011E, 0x011F),
+    (0x0122, 0x0123),
+    (0x012A, 0x012B),
+    (0x012E, 0x0130),
+    (0x0136, 0x0137),
+    (0x0139, 0x013E),
+    (0x0143, 0x0148),
+    (0x014C, 0x014D),
+    (0x0150, 0x0151),
+    (0x0154, 0x015B),
+    (0x015E, 0x0165),
+    (0x016A, 0x016B),
+    (0x016E, 0x0173),
+    (0x0179, 0x017C),
+    (0x0218, 0x021B),
+];
+
+/// The rest of Latin Extended-A plus Welsh, Azerbaijani, Sami and Skolt letters
+const ADOBE_LATIN_3: &[(u32, u32)] = &[
+    (0x0100, 0x017F),
+    (0x018F, 0x018F),
+    (0x01B7, 0x01B7),
+    (0x01E6, 0x01E9),
+    (0x01EE, 0x01EF),
+    (0x01FA, 0x01FF),
+    (0x0259, 0x0259),
+    (0x0292, 0x0292),
+    (0x02BB, 0x02BC),
+    (0x1E80, 0x1E85),
+    (0x1E9E, 0x1E9E),
+    (0x1EF2, 0x1EF3),
+];
+
+/// African letters, transliteration dot-below letters and the combining marks to build more
+const ADOBE_LATIN_4: &[(u32, u32)] = &[
+    (0x0181, 0x0181),
+    (0x0186, 0x0186),
+    (0x0189, 0x018A),
+    (0x018E, 0x018E),
+    (0x0190, 0x0190),
+    (0x0194, 0x0194),
+    (0x0198, 0x0199),
+    (0x019D, 0x019D),
+    (0x01B3, 0x01B4),
+    (0x01DD, 0x01DD),
+    (0x01EA, 0x01EB),
+    (0x0232, 0x0233),
+    (0x0253, 0x0254),
+    (0x0256, 0x0257),
+    (0x025B, 0x025B),
+    (0x0263, 0x0263),
+    (0x0272, 0x0272),
+    (0x0300, 0x0304),
+    (0x0306, 0x0308),
+    (0x030A, 0x030C),
+    (0x0323, 0x0323),
+    (0x0326, 0x0328),
+    (0x1E0C, 0x1E0D),
+    (0x1E24, 0x1E25),
+    (0x1E36, 0x1E37),
+    (0x1E42, 0x1E43),
+    (0x1E46, 0x1E47),
+    (0x1E5A, 0x1E5B),
+    (0x1E62, 0x1E63),
+    (0x1E6C, 0x1E6D),
+    (0x1E92, 0x1E93),
+    (0x1EB8, 0x1EB9),
+    (0x1ECC, 0x1ECD),
+];
+
+/// Vietnamese and Pinyin
+const ADOBE_LATIN_5: &[(u32, u32)] = &[
+    (0x01A0, 0x01A1),
+    (0x01AF, 0x01B0),
+    (0x01CD, 0x01DC),
+    (0x0309, 0x0309),
+    (0x031B, 0x031B),
+    (0x1EA0, 0x1EF9),
+    (0x20AB, 0x20AB),
+];
+
+/// Spacing accents, dotless letters and combining marks Google Fonts adds to Windows-1252
+const GF_LATIN_CORE: &[(u32, u32)] = &[
+    (0x0131, 0x0131),
+    (0x0237, 0x0237),
+    (0x02C7, 0x02C7),
+    (0x02C9, 0x02C9),
+    (0x02D8, 0x02DB),
+    (0x02DD, 0x02DD),
+    (0x0300, 0x0304),
+    (0x0306, 0x0308),
+    (0x030A, 0x030C),
+    (0x0312, 0x0312),
+    (0x0326, 0x0328),
+    (0x2044, 0x2044),
+    (0x2212, 0x2212),
+];
+
+/// Windows Glyph List 4 beyond Latin-1
+const WGL4: &[(u32, u32)] = &[
+    (0x0100, 0x017F),
+    (0x0192, 0x0192),
+    (0x01FA, 0x01FF),
+    (0x02C6, 0x02C7),
+    (0x02C9, 0x02C9),
+    (0x02D8, 0x02DD),
+    (0x0384, 0x038A),
+    (0x038C, 0x038C),
+    (0x038E, 0x03A1),
+    (0x03A3, 0x03CE),
+    (0x0401, 0x040C),
+    (0x040E, 0x044F),
+    (0x0451, 0x045C),
+    (0x045E, 0x045F),
+    (0x0490, 0x0491),
+    (0x1E80, 0x1E85),
+    (0x1EF2, 0x1EF3),
+    (0x2013, 0x2015),
+    (0x2017, 0x201E),
+    (0x2020, 0x2022),
+    (0x2026, 0x2026),
+    (0x2030, 0x2030),
+    (0x2032, 0x2033),
+    (0x2039, 0x203A),
+    (0x203C, 0x203C),
+    (0x203E, 0x203E),
+    (0x2044, 0x2044),
+    (0x207F, 0x207F),
+    (0x20A3, 0x20A4),
+    (0x20A7, 0x20A7),
+    (0x20AC, 0x20AC),
+    (0x2105, 0x2105),
+    (0x2113, 0x2113),
+    (0x2116, 0x2116),
+    (0x2122, 0x2122),
+    (0x2126, 0x2126),
+    (0x212E, 0x212E),
+    (0x215B, 0x215E),
+    (0x2190, 0x2195),
+    (0x21A8, 0x21A8),
+    (0x2202, 0x2202),
+    (0x2206, 0x2206),
+    (0x220F, 0x220F),
+    (0x2211, 0x2212),
+    (0x2215, 0x2215),
+    (0x2219, 0x221A),
+    (0x221E, 0x221F),
+    (0x2229, 0x2229),
+    (0x222B, 0x222B),
+    (0x2248, 0x2248),
+    (0x2260, 0x2261),
+    (0x2264, 0x2265),
+    (0x2302, 0x2302),
+    (0x2310, 0x2310),
+    (0x2320, 0x2321),
+    (0x2500, 0x2500),
+    (0x2502, 0x2502),
+    (0x250C, 0x250C),
+    (0x2510, 0x2510),
+    (0x2514, 0x2514),
+    (0x2518, 0x2518),
+    (0x251C, 0x251C),
+    (0x2524, 0x2524),
+    (0x252C, 0x252C),
+    (0x2534, 0x2534),
+    (0x253C, 0x253C),
+    (0x2550, 0x256C),
+    (0x2580, 0x2580),
+    (0x2584, 0x2584),
+    (0x2588, 0x2588),
+    (0x258C, 0x258C),
+    (0x2590, 0x2593),
+    (0x25A0, 0x25A1),
+    (0x25AA, 0x25AC),
+    (0x25B2, 0x25B2),
+    (0x25BA, 0x25BA),
+    (0x25BC, 0x25BC),
+    (0x25C4, 0x25C4),
+    (0x25CA, 0x25CB),
+    (0x25CF, 0x25CF),
+    (0x25D8, 0x25D9),
+    (0x25E6, 0x25E6),
+    (0x263A, 0x263C),
+    (0x2640, 0x2640),
+    (0x2642, 0x2642),
+    (0x2660, 0x2660),
+    (0x2663, 0x2663),
+    (0x2665, 0x2666),
+    (0x266A, 0x266B),
+    (0xFB01, 0xFB02),
+];
+
+/// A named character set built from codepoint ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Charset {
+    pub name: &'static str,
+    parts: &'static [&'static [(u32, u32)]],
+}
+
+impl Charset {
+    pub fn codepoints(&self) -> BTreeSet<u32> {
+        self.parts
+            .iter()
+            .flat_map(|ranges| ranges.iter())
+            .flat_map(|&(first, last)| first..=last)
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Charset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Built-in charsets. Adobe Latin 1–5 are cumulative approximations of the published
+/// glyph sets by codepoint; ligatures and alternates without codepoints are left out.
+pub const CHARSETS: &[Charset] = &[
+    Charset {
+        name: "Latin-1",
+        parts: &[LATIN_1],
+    },
+    Charset {
+        name: "Adobe Latin 1",
+        parts: &[LATIN_1, WIN_1252, MAC_ROMAN],
+    },
+    Charset {
+        name: "Adobe Latin 2",
+        parts: &[LATIN_1, WIN_1252, MAC_ROMAN, ADOBE_LATIN_2],
+    },
+    Charset {
+        name: "Adobe Latin 3",
+        parts: &[LATIN_1, WIN_1252, MAC_ROMAN, ADOBE_LATIN_2, ADOBE_LATIN_3],
+    },
+    Charset {
+        name: "Adobe Latin 4",
+        parts: &[
+            LATIN_1,
+            WIN_1252,
+            MAC_ROMAN,
+            ADOBE_LATIN_2,
+            ADOBE_LATIN_3,
+            ADOBE_LATIN_4,
+        ],
+    },
+    Charset {
+        name: "Adobe Latin 5",
+        parts: &[
+            LATIN_1,
+            WIN_1252,
+            MAC_ROMAN,
+            ADOBE_LATIN_2,
+            ADOBE_LATIN_3,
+            ADOBE_LATIN_4,
+            ADOBE_LATIN_5,
+        ],
+    },
+    Charset {
+        name: "GF Latin Core",
+        parts: &[LATIN_1, WIN_1252, GF_LATIN_CORE],
+    },
+    Charset {
+        name: "WGL4",
+        parts: &[LATIN_1, WGL4],
+    },
+];
+
+/// Parse a custom charset: literal characters plus `U+XXXX` codepoints and
+/// `U+XXXX-YYYY` (or `U+XXXX..YYYY`) ranges, separated by spaces or commas.
+pub fn parse_charset(text: &str) -> TestypfResult<BTreeSet<u32>> {
+    let mut codepoints = BTreeSet::new();
+    for token in text.split(|c: char| c.is_whitespace() || c == ',') {
+        let Some(hex) = token
+            .strip_prefix("U+")
+            .or_else(|| token.strip_prefix("u+"))
+        else {
+            codepoints.extend(token.chars().map(u32::from));
+            continue;
+        };
+        let (first, last) = match hex.split_once("..").or_else(|| hex.split_once('-')) {
+            Some((first, last)) => (first, last.trim_start_matches("U+")),
+            None => (hex, hex),
+        };
+        let parse = |value: &str| {
+            u32::from_str_radix(value, 16)
+                .ok()
+                .filter(|&cp| cp <= 0x10FFFF)
+                .ok_or_else(|| {
+                    TestypfError::RenderFailed(format!("Invalid charset codepoint: {token}"))
+                })
+        };
+        let (first, last) = (parse(first)?, parse(last)?);
+        if first > last {
+            return Err(TestypfError::RenderFailed(format!(
+                "Charset range runs backwards: {token}"
+            )));
+        }
+        codepoints.extend(first..=last);
+    }
+    Ok(codepoints)
+}
@@ -0,0 +1,410 @@
+use super::*;
+
+/// Width of a caption glyph cell (5px glyph + 1px spacing) before scaling
+const GLYPH_ADVANCE: u32 = 6;
+/// Height of a caption line (7px glyph + 2px leading) before scaling
+const LINE_HEIGHT: u32 = 9;
+/// Gap between a cell's image and its caption before scaling
+const CAPTION_GAP: u32 = 4;
+
+/// Layout and styling for a contact sheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContactSheetOptions {
+    /// Number of cells per row (at least 1)
+    pub columns: usize,
+
+    /// Space between cells, in pixels
+    pub gutter: u32,
+
+    /// Space around the whole grid, in pixels
+    pub margin: u32,
+
+    /// Minimum cell width, so captions stay readable next to narrow renders
+    pub min_cell_width: u32,
+
+    /// Sheet background (RGBA)
+    pub background: (u8, u8, u8, u8),
+
+    /// Caption text color (RGBA)
+    pub caption_color: (u8, u8, u8, u8),
+
+    /// Integer scale of the built-in 5x7 caption font
+    pub caption_scale: u32,
+
+    /// Draw captions under each cell
+    pub show_captions: bool,
+
+    /// Labels drawn above each column (e.g. backend names); empty for none
+    pub column_headers: Vec<String>,
+}
+
+impl Default for ContactSheetOptions {
+    fn default() -> Self {
+        Self {
+            columns: 2,
+            gutter: 16,
+            margin: 16,
+            min_cell_width: 240,
+            background: (255, 255, 255, 255),
+            caption_color: (64, 64, 64, 255),
+            caption_scale: 1,
+            show_captions: true,
+            column_headers: Vec::new(),
+        }
+    }
+}
+
+/// One tile of a contact sheet
+#[derive(Debug, Clone)]
+pub struct ContactSheetCell<'a> {
+    pub image: &'a RenderResult,
+    pub caption: String,
+}
+
+/// Tile render results (rasterized to RGBA8 first) into one grid with captions and column headers
+pub fn compose(
+    cells: &[ContactSheetCell<'_>],
+    options: &ContactSheetOptions,
+) -> TestypfResult<RenderResult> {
+    if cells.is_empty() {
+        return Err(TestypfError::RenderFailed(
+            "Contact sheet needs at least one render".to_string(),
+        ));
+    }
+
+    let columns = options.columns.clamp(1, cells.len());
+    let scale = options.caption_scale.max(1);
+
+    let mut rasters = Vec::with_capacity(cells.len());
+    for cell in cells {
+        let raster = if cell.image.is_vector() {
+            crate::vector::rasterize(cell.image, 1.0)?
+        } else {
+            cell.image.to_rgba8()?
+        };
+        rasters.push(raster);
+    }
+
+    let cell_width = rasters
+        .iter()
+        .map(|r| r.width)
+        .max()
+        .unwrap_or(0)
+        .max(options.min_cell_width)
+        .max(1);
+    let max_chars = (cell_width / (GLYPH_ADVANCE * scale)).max(1) as usize;
+
+    let captions: Vec<Vec<String>> = cells
+        .iter()
+        .map(|cell| {
+            if options.show_captions {
+                wrap_caption(&cell.caption, max_chars)
+            } else {
+                Vec::new()
+            }
+        })
+        .collect();
+
+    let cell_height = |i: usize| {
+        let lines = captions[i].len() as u32;
+        let caption = if lines == 0 {
+            0
+        } else {
+            CAPTION_GAP * scale + lines * LINE_HEIGHT * scale
+        };
+        rasters[i].height + caption
+    };
+
+    let headers: Vec<Vec<String>> = options
+        .column_headers
+        .iter()
+        .take(columns)
+        .map(|header| wrap_caption(header, max_chars))
+        .collect();
+    let header_lines = headers.iter().map(Vec::len).max().unwrap_or(0) as u32;
+    let header_height = if header_lines == 0 {
+        0
+    } else {
+        header_lines * LINE_HEIGHT * scale + options.gutter
+    };
+
+    let rows = cells.len().div_ceil(columns);
+    let row_heights: Vec<u32> = (0..rows)
+        .map(|row| {
+            (row * columns..((row + 1) * columns).min(cells.len()))
+                .map(cell_height)
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let width =
+        options.margin * 2 + cell_width * columns as u32 + options.gutter * (columns as u32 - 1);
+    let height = options.margin * 2
+        + header_height
+        + row_heights.iter().sum::<u32>()
+        + options.gutter * (rows as u32 - 1);
+
+    let mut canvas = Canvas::new(width, height, options.background);
+    for (col, lines) in headers.iter().enumerate() {
+        let x = options.margin + col as u32 * (cell_width + options.gutter);
+        let mut line_y = options.margin;
+        for line in lines {
+            canvas.draw_text(x, line_y, line, scale, options.caption_color);
+            line_y += LINE_HEIGHT * scale;
+        }
+    }
+
+    let mut y = options.margin + header_height;
+    for (row, row_height) in row_heights.iter().enumerate() {
+        for col in 0..columns {
+            let index = row * columns + col;
+            let Some(raster) = rasters.get(index) else {
+                break;
+            };
+            let x = options.margin + col as u32 * (cell_width + options.gutter);
+            canvas.draw_image(x, y, raster);
+
+            let mut line_y = y + raster.height + CAPTION_GAP * scale;
+            for line in &captions[index] {
+                canvas.draw_text(x, line_y, line, scale, options.caption_color);
+                line_y += LINE_HEIGHT * scale;
+            }
+        }
+        y += row_height + options.gutter;
+    }
+
+    Ok(RenderResult {
+        width,
+        height,
+        data: canvas.data.into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
+    })
+}
+
+/// Word-wrap a caption to `max_chars` per line, preferring breaks at " | " separators
+fn wrap_caption(caption: &str, max_chars: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let len = |s: &str| s.chars().count();
+
+    for segment in caption.split(" | ").filter(|s| !s.is_empty()) {
+        if let Some(last) = lines.last_mut() {
+            if len(last) + 3 + len(segment) <= max_chars {
+                last.push_str(" | ");
+                last.push_str(segment);
+                continue;
+            }
+        }
+
+        let mut current = String::new();
+        for word in segment.split(' ') {
+            if current.is_empty() {
+                current.push_str(word);
+            } else if len(&current) + 1 + len(word) <= max_chars {
+                current.push(' ');
+                current.push_str(word);
+            } else {
+                lines.push(std::mem::take(&mut current));
+                current.push_str(word);
+            }
+
+            while len(&current) > max_chars {
+                let tail: String = current.chars().skip(max_chars).collect();
+                current = current.chars().take(max_chars).collect();
+                lines.push(std::mem::replace(&mut current, tail));
+            }
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+    }
+
+    lines
+}
+
+/// Straight-alpha RGBA8 drawing surface
+struct Canvas {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, background: (u8, u8, u8, u8)) -> Self {
+        let (r, g, b, a) = background;
+        Self {
+            width,
+            height,
+            data: [r, g, b, a].repeat((width as usize) * (height as usize)),
+        }
+    }
+
+    fn blend(&mut self, x: u32, y: u32, src: [u8; 4]) {
+        if x >= self.width || y >= self.height || src[3] == 0 {
+            return;
+        }
+        let offset = ((y as usize) * (self.width as usize) + x as usize) * 4;
+        let dst = &mut self.data[offset..offset + 4];
+
+        let sa = src[3] as f32 / 255.0;
+        let da = dst[3] as f32 / 255.0;
+        let out_a = sa + da * (1.0 - sa);
+        if out_a <= 0.0 {
+            return;
+        }
+        for c in 0..3 {
+            let value = (src[c] as f32 * sa + dst[c] as f32 * da * (1.0 - sa)) / out_a;
+            dst[c] = value.round().clamp(0.0, 255.0) as u8;
+        }
+        dst[3] = (out_a * 255.0).round() as u8;
+    }
+
+    fn draw_image(&mut self, x: u32, y: u32, image: &RenderResult) {
+        for (row, line) in image
+            .data
+            .chunks_exact(image.width as usize * 4)
+            .enumerate()
+        {
+            for (col, px) in line.chunks_exact(4).enumerate() {
+                self.blend(x + col as u32, y + row as u32, [px[0], px[1], px[2], px[3]]);
+            }
+        }
+    }
+
+    fn draw_text(&mut self, x: u32, y: u32, text: &str, scale: u32, color: (u8, u8, u8, u8)) {
+        let (r, g, b, a) = color;
+        for (i, ch) in text.chars().enumerate() {
+            let glyph = glyph_rows(ch);
+            let gx = x + i as u32 * GLYPH_ADVANCE * scale;
+            for (row, bits) in glyph.iter().enumerate() {
+                for col in 0..5u32 {
+                    if bits & (0x10 >> col) == 0 {
+                        continue;
+                    }
+                    for dy in 0..scale {
+                        for dx in 0..scale {
+                            self.blend(
+                                gx + col * scale + dx,
+                                y + row as u32 * scale + dy,
+                                [r, g, b, a],
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Rows of the built-in 5x7 caption font; anything outside printable ASCII renders as '?'
+fn glyph_rows(ch: char) -> &'static [u8; 7] {
+    let code = ch as u32;
+    let index = if (0x20..0x7F).contains(&code) {
+        code - 0x20
+    } else {
+        '?' as u32 - 0x20
+    };
+    &FONT_5X7[index as usize]
+}
+
+/// 5x7 bitmap font for printable ASCII (0x20..=0x7E); bit 4 is the leftmost column
+#[rustfmt::skip]
+const FONT_5X7: [[u8; 7]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04], // '!'
+    [0x0A, 0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A], // '#'
+    [0x04, 0x0F, 0x14, 0x0E, 0x05, 0x1E, 0x04], // '$'
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03], // '%'
+    [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D], // '&'
+    [0x04, 0x04, 0x04, 0x00, 0x00, 0x00, 0x00], // "'"
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02], // '('
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08], // ')'
+    [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00], // '*'
+    [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08], // ','
+    [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C], // '.'
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00], // '/'
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E], // '0'
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E], // '1'
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F], // '2'
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E], // '3'
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02], // '4'
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E], // '5'
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E], // '6'
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08], // '7'
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E], // '8'
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C], // '9'
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00], // ':'
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08], // ';'
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02], // '<'
+    [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00], // '='
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08], // '>'
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04], // '?'
+    [0x0E, 0x11, 0x01, 0x0D, 0x15, 0x15, 0x0E], // '@'
+    [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // 'A'
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E], // 'B'
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E], // 'C'
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C], // 'D'
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F], // 'E'
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10], // 'F'
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F], // 'G'
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11], // 'H'
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // 'I'
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C], // 'J'
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11], // 'K'
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F], // 'L'
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11], // 'M'
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11], // 'N'
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // 'O'
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10], // 'P'
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D], // 'Q'
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11], // 'R'
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E], // 'S'
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // 'T'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E], // 'U'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04], // 'V'
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A], // 'W'
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11], // 'X'
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04], // 'Y'
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F], // 'Z'
+    [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E], // '['
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00], // '\\'
+    [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E], // ']'
+    [0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F], // '_'
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F], // 'a'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E], // 'b'
+    [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E], // 'c'
+    [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F], // 'd'
+    [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E], // 'e'
+    [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08], // 'f'
+    [0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E], // 'g'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11], // 'h'
+    [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E], // 'i'
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0C], // 'j'
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12], // 'k'
+    [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E], // 'l'
+    [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11], // 'm'
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11], // 'n'
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E], // 'o'
+    [0x00, 0x00, 0x1E, 0x11, 0x1E, 0x10, 0x10], // 'p'
+    [0x00, 0x00, 0x0D, 0x13, 0x0F, 0x01, 0x01], // 'q'
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10], // 'r'
+    [0x00, 0x00, 0x0E, 0x10, 0x0E, 0x01, 0x1E], // 's'
+    [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06], // 't'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D], // 'u'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04], // 'v'
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A], // 'w'
+    [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11], // 'x'
+    [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E], // 'y'
+    [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F], // 'z'
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02], // '{'
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04], // '|'
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08], // '}'
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00], // '~'
+];
@@ -0,0 +1,319 @@
+use super::*;
+
+/// Side length of the square windows used for the SSIM score
+const SSIM_WINDOW: u32 = 8;
+/// SSIM stabilizers for 8-bit luma: (0.01 * 255)^2 and (0.03 * 255)^2
+const SSIM_C1: f64 = 6.5025;
+const SSIM_C2: f64 = 58.5225;
+
+/// How the diff bitmap visualizes changes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum DiffStyle {
+    /// Changed pixels colored red (small delta) to yellow (large delta) over a faded copy of the first render
+    #[default]
+    Heatmap,
+    /// Ink only in the first render in red, ink only in the second in green, shared ink in gray
+    Overlay,
+}
+
+impl DiffStyle {
+    pub const ALL: [DiffStyle; 2] = [DiffStyle::Heatmap, DiffStyle::Overlay];
+}
+
+impl std::fmt::Display for DiffStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiffStyle::Heatmap => write!(f, "Heatmap"),
+            DiffStyle::Overlay => write!(f, "Red/green overlay"),
+        }
+    }
+}
+
+/// How much two renders may differ and still count as matching
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DiffTolerance {
+    /// Per-channel difference that is ignored (0 = exact)
+    pub channel: u8,
+
+    /// Pixels allowed to exceed `channel` before the renders count as different
+    pub max_changed_pixels: usize,
+}
+
+/// Rectangle enclosing every changed pixel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DiffBounds {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Numbers describing how two renders differ
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DiffReport {
+    /// Compared area: the larger of the two renders in each direction
+    pub width: u32,
+    pub height: u32,
+
+    /// True when the renders have different dimensions (missing pixels count as transparent)
+    pub size_mismatch: bool,
+
+    /// Per-channel tolerance `changed_pixels` was counted with
+    #[serde(default)]
+    pub channel_tolerance: u8,
+
+    /// Pixels whose largest channel difference exceeds the tolerance
+    pub changed_pixels: usize,
+
+    /// Largest difference seen in any RGBA channel
+    pub max_channel_delta: u8,
+
+    /// Peak signal-to-noise ratio over all RGBA channels; `None` when the renders are identical
+    pub psnr_db: Option<f64>,
+
+    /// Mean structural similarity of the luma (composited over white), 1.0 for identical renders
+    pub ssim: f64,
+
+    /// Bounding box of the changed pixels, `None` when nothing changed
+    pub bounds: Option<DiffBounds>,
+}
+
+impl DiffReport {
+    pub fn total_pixels(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    /// Whether the renders match within the given tolerance.
+    ///
+    /// False when `tolerance.channel` differs from the tolerance the report was compared with,
+    /// since `changed_pixels` cannot answer for another per-channel tolerance.
+    pub fn within(&self, tolerance: &DiffTolerance) -> bool {
+        tolerance.channel == self.channel_tolerance
+            && self.changed_pixels <= tolerance.max_changed_pixels
+    }
+
+    /// One-line description, e.g. for status bars and assertion messages
+    pub fn summary(&self) -> String {
+        let psnr = self
+            .psnr_db
+            .map(|db| format!("{db:.1} dB"))
+            .unwrap_or_else(|| "identical".to_string());
+        let bounds = self
+            .bounds
+            .map(|b| format!(", bounds {}x{} at ({}, {})", b.width, b.height, b.x, b.y))
+            .unwrap_or_default();
+        format!(
+            "{} of {} pixels changed, max delta {}, PSNR {}, SSIM {:.4}{}{}",
+            self.changed_pixels,
+            self.total_pixels(),
+            self.max_channel_delta,
+            psnr,
+            self.ssim,
+            bounds,
+            if self.size_mismatch {
+                ", sizes differ"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// Diff statistics plus a straight-alpha RGBA8 visualization
+#[derive(Debug, Clone)]
+pub struct PixelDiff {
+    pub report: DiffReport,
+    pub image: RenderResult,
+}
+
+/// Compare two renders pixel by pixel.
+///
+/// Both renders are converted to straight RGBA8 (vector output is rasterized) and aligned at the top-left corner.
+/// A pixel counts as changed when any channel differs by more than `tolerance`.
+pub fn compare(
+    first: &RenderResult,
+    second: &RenderResult,
+    tolerance: u8,
+    style: DiffStyle,
+) -> TestypfResult<PixelDiff> {
+    let a = to_straight_rgba(first)?;
+    let b = to_straight_rgba(second)?;
+
+    let width = a.width.max(b.width);
+    let height = a.height.max(b.height);
+    let pixel = |image: &RenderResult, x: u32, y: u32| -> [u8; 4] {
+        if x >= image.width || y >= image.height {
+            return [0; 4];
+        }
+        let offset = ((y * image.width + x) * 4) as usize;
+        image.data[offset..offset + 4].try_into().unwrap_or([0; 4])
+    };
+
+    let mut changed_pixels = 0;
+    let mut max_channel_delta = 0u8;
+    let mut squared_error = 0f64;
+    let mut min = (u32::MAX, u32::MAX);
+    let mut max = (0u32, 0u32);
+    let mut luma_a = Vec::with_capacity((width * height) as usize);
+    let mut luma_b = Vec::with_capacity((width * height) as usize);
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+
+    for y in 0..height {
+        for x in 0..width {
+            let pa = pixel(&a, x, y);
+            let pb = pixel(&b, x, y);
+
+            let mut delta = 0u8;
+            for channel in 0..4 {
+                let d = pa[channel].abs_diff(pb[channel]);
+                delta = delta.max(d);
+                squared_error += f64::from(d) * f64::from(d);
+            }
+            max_channel_delta = max_channel_delta.max(delta);
+
+            let la = luma_over_white(pa);
+            let lb = luma_over_white(pb);
+            luma_a.push(la);
+            luma_b.push(lb);
+
+            let changed = delta > tolerance;
+            if changed {
+                changed_pixels += 1;
+                min = (min.0.min(x), min.1.min(y));
+                max = (max.0.max(x), max.1.max(y));
+            }
+
+            data.extend_from_slice(&match style {
+                DiffStyle::Heatmap => heatmap_pixel(la, changed, delta),
+                DiffStyle::Overlay => overlay_pixel(la, lb, changed),
+            });
+        }
+    }
+
+    let samples = f64::from(width) * f64::from(height) * 4.0;
+    let psnr_db = (squared_error > 0.0).then(|| {
+        let mse = squared_error / samples;
+        10.0 * (255.0 * 255.0 / mse).log10()
+    });
+    let bounds = (changed_pixels > 0).then(|| DiffBounds {
+        x: min.0,
+        y: min.1,
+        width: max.0 - min.0 + 1,
+        height: max.1 - min.1 + 1,
+    });
+
+    Ok(PixelDiff {
+        report: DiffReport {
+            width,
+            height,
+            size_mismatch: a.width != b.width || a.height != b.height,
+            channel_tolerance: tolerance,
+            changed_pixels,
+            max_channel_delta,
+            psnr_db,
+            ssim: ssim(&luma_a, &luma_b, width, height),
+            bounds,
+        },
+        image: RenderResult {
+            width,
+            height,
+            data: data.into(),
+            format: PixelFormat::Rgba8,
+            glyphs: Vec::new(),
+            baseline: None,
+        },
+    })
+}
+
+/// Compare two renders and fail with `TestypfError::RenderMismatch` unless they match within `tolerance`.
+pub fn assert_renders_match(
+    expected: &RenderResult,
+    actual: &RenderResult,
+    tolerance: &DiffTolerance,
+) -> TestypfResult<DiffReport> {
+    let report = compare(expected, actual, tolerance.channel, DiffStyle::Heatmap)?.report;
+    if report.within(tolerance) {
+        Ok(report)
+    } else {
+        Err(TestypfError::RenderMismatch(report.summary()))
+    }
+}
+
+fn to_straight_rgba(result: &RenderResult) -> TestypfResult<RenderResult> {
+    if result.is_vector() {
+        crate::vector::rasterize(result, 1.0)
+    } else {
+        result.to_rgba8()
+    }
+}
+
+/// Rec. 601 luma of a straight-alpha pixel composited over white
+fn luma_over_white(p: [u8; 4]) -> f64 {
+    let alpha = f64::from(p[3]) / 255.0;
+    let luma = 0.299 * f64::from(p[0]) + 0.587 * f64::from(p[1]) + 0.114 * f64::from(p[2]);
+    luma * alpha + 255.0 * (1.0 - alpha)
+}
+
+fn heatmap_pixel(luma: f64, changed: bool, delta: u8) -> [u8; 4] {
+    if changed {
+        // Red for barely-changed pixels, ramping to yellow at full delta
+        [255, delta, 0, 255]
+    } else {
+        let faded = (255.0 - (255.0 - luma) / 4.0).round() as u8;
+        [faded, faded, faded, 255]
+    }
+}
+
+fn overlay_pixel(luma_a: f64, luma_b: f64, changed: bool) -> [u8; 4] {
+    let ink_a = 255.0 - luma_a;
+    let ink_b = 255.0 - luma_b;
+    if changed && ink_a > ink_b {
+        [220, 40, 40, 255]
+    } else if changed {
+        [40, 170, 60, 255]
+    } else {
+        let shared = (255.0 - ink_a.max(ink_b) / 2.0).round() as u8;
+        [shared, shared, shared, 255]
+    }
+}
+
+/// Mean SSIM over non-overlapping windows (partial windows at the edges included)
+fn ssim(a: &[f64], b: &[f64], width: u32, height: u32) -> f64 {
+    let mut total = 0.0;
+    let mut windows = 0usize;
+
+    for wy in (0..height).step_by(SSIM_WINDOW as usize) {
+        for wx in (0..width).step_by(SSIM_WINDOW as usize) {
+            let mut n = 0.0;
+            let (mut sum_a, mut sum_b) = (0.0, 0.0);
+            let (mut sum_aa, mut sum_bb, mut sum_ab) = (0.0, 0.0, 0.0);
+            for y in wy..(wy + SSIM_WINDOW).min(height) {
+                for x in wx..(wx + SSIM_WINDOW).min(width) {
+                    let i = (y * width + x) as usize;
+                    let (va, vb) = (a[i], b[i]);
+                    n += 1.0;
+                    sum_a += va;
+                    sum_b += vb;
+                    sum_aa += va * va;
+                    sum_bb += vb * vb;
+                    sum_ab += va * vb;
+                }
+            }
+
+            let (mean_a, mean_b) = (sum_a / n, sum_b / n);
+            let var_a = (sum_aa / n - mean_a * mean_a).max(0.0);
+            let var_b = (sum_bb / n - mean_b * mean_b).max(0.0);
+            let covariance = sum_ab / n - mean_a * mean_b;
+
+            total += ((2.0 * mean_a * mean_b + SSIM_C1) * (2.0 * covariance + SSIM_C2))
+                / ((mean_a * mean_a + mean_b * mean_b + SSIM_C1) * (var_a + var_b + SSIM_C2));
+            windows += 1;
+        }
+    }
+
+    if windows == 0 {
+        1.0
+    } else {
+        total / windows as f64
+    }
+}
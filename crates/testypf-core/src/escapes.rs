@@ -0,0 +1,287 @@
+//! Escape syntax for hard-to-type characters in sample text
+//!
+//! - `\u{200D}` takes one to six hex digits, `\u0301` exactly four
+//! - `/glyphname` references a glyph by its `post` table name, or by the `uniXXXX` / `uXXXXX`
+//!   forms, and is replaced by the codepoint the cmap maps to it. A name runs over
+//!   `A-Z a-z 0-9 . _ -`, so `/A/V` is two references; it only starts at the beginning of the
+//!   text, after whitespace or right after another escape, which keeps `1/2` and `and/or` literal
+//! - `\\` and `\/` produce a literal backslash and slash
+//!
+//! Escapes that cannot be decoded stay in the text as typed and are reported as issues.
+
+use super::*;
+use read_fonts::tables::cmap::CmapIterLimits;
+use read_fonts::types::GlyphId16;
+use read_fonts::{FontRef, TableProvider};
+use std::collections::HashMap;
+use std::path::Path;
+use unicode_script::{Script, UnicodeScript};
+
+/// Glyph names of one font, with the codepoint each glyph is reachable from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GlyphNames {
+    /// Lowest codepoint the cmap maps to the glyph, `None` for unencoded glyphs
+    by_name: HashMap<String, Option<char>>,
+}
+
+impl GlyphNames {
+    /// Pair `post` names (formats 1 and 2) with the cmap's reverse mapping
+    pub fn from_font(font: &FontRef) -> Self {
+        let mut by_glyph: HashMap<u32, char> = HashMap::new();
+        let glyph_count = font
+            .maxp()
+            .map(|maxp| u32::from(maxp.num_glyphs()))
+            .unwrap_or(u32::MAX);
+        if let Some((_, _, subtable)) = font.cmap().ok().and_then(|cmap| cmap.best_subtable()) {
+            let limits = CmapIterLimits {
+                max_char: 0x10FFFF,
+                glyph_count,
+            };
+            for (codepoint, glyph) in subtable.iter_with_limits(limits) {
+                if let Some(ch) = char::from_u32(codepoint) {
+                    let entry = by_glyph.entry(glyph.to_u32()).or_insert(ch);
+                    *entry = (*entry).min(ch);
+                }
+            }
+        }
+
+        let mut by_name = HashMap::new();
+        if let Ok(post) = font.post() {
+            for glyph in 0..glyph_count.min(u32::from(u16::MAX) + 1) {
+                let Some(name) = post.glyph_name(GlyphId16::new(glyph as u16)) else {
+                    continue;
+                };
+                by_name
+                    .entry(name.to_string())
+                    .or_insert_with(|| by_glyph.get(&glyph).copied());
+            }
+        }
+        Self { by_name }
+    }
+
+    /// Parse one face of a font file and collect its glyph names
+    pub fn read(data: &[u8], face_index: u32) -> TestypfResult<Self> {
+        let font = FontRef::from_index(data, face_index)
+            .map_err(|e| TestypfError::InvalidFont(format!("Failed to parse font: {}", e)))?;
+        Ok(Self::from_font(&font))
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_name.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+
+    /// Character to shape for a glyph name: `post` names first, then `uniXXXX` / `uXXXXX`
+    pub fn resolve(&self, name: &str) -> Result<char, String> {
+        match self.by_name.get(name) {
+            Some(Some(ch)) => Ok(*ch),
+            Some(None) => Err(format!(
+                "/{name} has no Unicode mapping, so it can only be reached through features"
+            )),
+            None if self.by_name.is_empty() => codepoint_name(name).ok_or_else(|| {
+                format!("/{name}: the font has no post glyph names, only uniXXXX names resolve")
+            }),
+            None => codepoint_name(name).ok_or_else(|| format!("/{name}: no glyph with that name")),
+        }
+    }
+}
+
+/// Sample text after escape decoding
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecodedText {
+    /// The string handed to the shaper
+    pub text: String,
+    /// Escapes left as typed, with the reason
+    pub issues: Vec<String>,
+}
+
+/// Whether `text` contains anything the decoder would act on.
+pub fn has_escapes(text: &str) -> bool {
+    let mut previous: Option<char> = None;
+    for (index, ch) in text.char_indices() {
+        match ch {
+            '\\' => return true,
+            '/' if previous.map_or(true, char::is_whitespace)
+                && text[index + 1..].starts_with(is_name_char) =>
+            {
+                return true
+            }
+            _ => {}
+        }
+        previous = Some(ch);
+    }
+    false
+}
+
+/// `text` with every backslash and slash escaped, so [`decode`] returns it unchanged.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '/') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Decode escapes; glyph names resolve through `names`, or only as `uniXXXX` / `uXXXXX` without it.
+pub fn decode(text: &str, names: Option<&GlyphNames>) -> DecodedText {
+    let mut decoded = DecodedText::default();
+    let mut rest = text;
+    // Glyph references may start at the beginning, after whitespace or after another escape
+    let mut at_boundary = true;
+    while let Some(ch) = rest.chars().next() {
+        if ch == '\\' {
+            let (consumed, result) = decode_backslash(rest);
+            match result {
+                Ok(ch) => decoded.text.push(ch),
+                Err(issue) => {
+                    decoded.text.push_str(&rest[..consumed]);
+                    if let Some(issue) = issue {
+                        decoded.issues.push(issue);
+                    }
+                }
+            }
+            rest = &rest[consumed..];
+            at_boundary = true;
+            continue;
+        }
+        if ch == '/' && at_boundary {
+            let name_len = rest[1..]
+                .find(|c: char| !is_name_char(c))
+                .unwrap_or(rest.len() - 1);
+            if name_len > 0 {
+                let name = &rest[1..1 + name_len];
+                let resolved = match names {
+                    Some(names) => names.resolve(name),
+                    None => codepoint_name(name)
+                        .ok_or_else(|| format!("/{name}: no font to look the name up in")),
+                };
+                match resolved {
+                    Ok(ch) => decoded.text.push(ch),
+                    Err(issue) => {
+                        decoded.text.push_str(&rest[..1 + name_len]);
+                        decoded.issues.push(issue);
+                    }
+                }
+                rest = &rest[1 + name_len..];
+                continue;
+            }
+        }
+        decoded.text.push(ch);
+        at_boundary = ch.is_whitespace();
+        rest = &rest[ch.len_utf8()..];
+    }
+    decoded
+}
+
+/// Decode `text` for the font at `font_path`, reading glyph names only when the text has references.
+pub fn decode_for_font(text: &str, font_path: &Path) -> DecodedText {
+    if !has_escapes(text) {
+        return DecodedText {
+            text: text.to_string(),
+            issues: Vec::new(),
+        };
+    }
+    let names = if text.contains('/') {
+        std::fs::read(font_path)
+            .ok()
+            .and_then(|data| GlyphNames::read(&data, 0).ok())
+    } else {
+        None
+    };
+    decode(text, names.as_ref())
+}
+
+/// `text` with invisible characters, combining marks and private use codepoints spelled out as `⟨U+XXXX⟩`
+pub fn visible_form(text: &str) -> String {
+    let mut visible = String::new();
+    for ch in text.chars() {
+        let cp = u32::from(ch);
+        let spelled = crate::coverage::is_invisible(ch)
+            || ch.script() == Script::Inherited
+            || matches!(cp, 0xE000..=0xF8FF | 0xF0000..=0x10FFFF);
+        if spelled {
+            visible.push_str(&format!("⟨U+{cp:04X}⟩"));
+        } else {
+            visible.push(ch);
+        }
+    }
+    visible
+}
+
+fn is_name_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-')
+}
+
+/// Codepoint spelled by an AGL-style `uniXXXX` or `uXXXX`–`uXXXXXX` name
+fn codepoint_name(name: &str) -> Option<char> {
+    let hex = match name.strip_prefix("uni") {
+        Some(hex) if hex.len() == 4 => hex,
+        _ => name
+            .strip_prefix('u')
+            .filter(|hex| (4..=6).contains(&hex.len()))?,
+    };
+    if !hex
+        .chars()
+        .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_lowercase())
+    {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+/// Decode the backslash escape at the start of `text`.
+///
+/// Returns the bytes consumed and the character, or the issue to report (`None` for a
+/// lone backslash, which is simply kept).
+fn decode_backslash(text: &str) -> (usize, Result<char, Option<String>>) {
+    let after = &text[1..];
+    match after.chars().next() {
+        Some(ch @ ('\\' | '/')) => (2, Ok(ch)),
+        Some('u') if after[1..].starts_with('{') => {
+            let Some(close) = after.find('}') else {
+                return (
+                    2,
+                    Err(Some("\\u{ is missing its closing brace".to_string())),
+                );
+            };
+            let consumed = 1 + close + 1;
+            let hex = &after[2..close];
+            (consumed, parse_codepoint(hex, 1..=6, &text[..consumed]))
+        }
+        Some('u') => {
+            let hex: String = after[1..].chars().take(4).collect();
+            if hex.len() == 4 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                (6, parse_codepoint(&hex, 4..=4, &text[..6]))
+            } else {
+                (
+                    2,
+                    Err(Some(
+                        "\\u needs four hex digits or braces, e.g. \\u0301 or \\u{1F600}"
+                            .to_string(),
+                    )),
+                )
+            }
+        }
+        _ => (1, Err(None)),
+    }
+}
+
+fn parse_codepoint(
+    hex: &str,
+    digits: std::ops::RangeInclusive<usize>,
+    escape: &str,
+) -> Result<char, Option<String>> {
+    if !digits.contains(&hex.len()) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Some(format!("{escape} is not a hex codepoint")));
+    }
+    u32::from_str_radix(hex, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or_else(|| Some(format!("{escape} is not a valid Unicode scalar value")))
+}
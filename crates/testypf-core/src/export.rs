@@ -0,0 +1,531 @@
+use super::*;
+use sha2::{Digest, Sha256};
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+/// iTXt keyword holding the full JSON provenance record
+pub const PROVENANCE_KEY: &str = "testypf:provenance";
+
+/// Everything needed to reproduce an exported render
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct RenderProvenance {
+    /// testypf version that produced the render
+    pub testypf_version: String,
+
+    /// Font file the render was made with
+    pub font_path: PathBuf,
+
+    /// SHA-256 of the font file at export time (hex), if it could be read
+    pub font_sha256: Option<String>,
+
+    /// Shaper used by typf (e.g. "harfbuzz")
+    pub shaper: String,
+
+    /// Full render settings (backend, size, colors, variations, ...)
+    pub settings: RenderSettings,
+}
+
+impl RenderProvenance {
+    /// Capture provenance for a render of `font_path` with `settings`
+    pub fn capture(font_path: &Path, settings: &RenderSettings, shaper: &str) -> Self {
+        Self {
+            testypf_version: env!("CARGO_PKG_VERSION").to_string(),
+            font_path: font_path.to_path_buf(),
+            font_sha256: font_sha256(font_path).ok(),
+            shaper: shaper.to_string(),
+            settings: settings.clone(),
+        }
+    }
+
+    /// Whether the font on disk still matches the recorded hash (`None` if unknown)
+    pub fn font_matches(&self) -> Option<bool> {
+        let expected = self.font_sha256.as_ref()?;
+        let actual = font_sha256(&self.font_path).ok()?;
+        Some(&actual == expected)
+    }
+
+    /// Human-readable key/value pairs written as individual PNG text chunks
+    pub fn text_entries(&self) -> Vec<(String, String)> {
+        let settings = &self.settings;
+        let (r, g, b, a) = settings.foreground_color;
+        let mut variations: Vec<_> = settings.variation_coords.iter().collect();
+        variations.sort_by(|x, y| x.0.cmp(y.0));
+
+        vec![
+            (
+                "Software".to_string(),
+                format!("testypf {}", self.testypf_version),
+            ),
+            (
+                "testypf:font".to_string(),
+                self.font_path.display().to_string(),
+            ),
+            (
+                "testypf:font-sha256".to_string(),
+                self.font_sha256.clone().unwrap_or_default(),
+            ),
+            ("testypf:backend".to_string(), settings.backend.to_string()),
+            ("testypf:shaper".to_string(), self.shaper.clone()),
+            ("testypf:size".to_string(), settings.font_size.to_string()),
+            (
+                "testypf:scale".to_string(),
+                settings.scale_factor.to_string(),
+            ),
+            (
+                "testypf:raster".to_string(),
+                format!(
+                    "antialias={},hinting={},gamma={}",
+                    settings.raster.antialias.typf_name(),
+                    settings.raster.hinting.typf_name(),
+                    settings.raster.gamma
+                ),
+            ),
+            (
+                "testypf:foreground".to_string(),
+                format!("#{r:02X}{g:02X}{b:02X}{a:02X}"),
+            ),
+            (
+                "testypf:background".to_string(),
+                settings
+                    .background_color
+                    .map(|(r, g, b, a)| format!("#{r:02X}{g:02X}{b:02X}{a:02X}"))
+                    .unwrap_or_else(|| "transparent".to_string()),
+            ),
+            (
+                "testypf:variations".to_string(),
+                variations
+                    .into_iter()
+                    .map(|(tag, value)| format!("{tag}={value}"))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            ("testypf:text".to_string(), settings.sample_text.clone()),
+        ]
+    }
+}
+
+/// Hex SHA-256 digest of a font file
+pub fn font_sha256(path: &Path) -> TestypfResult<String> {
+    let data = std::fs::read(path)?;
+    let digest = Sha256::digest(&data);
+    Ok(digest.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Write straight-alpha RGBA8 pixels as a PNG, embedding provenance when given
+pub fn write_png(
+    path: &Path,
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    provenance: Option<&RenderProvenance>,
+) -> TestypfResult<()> {
+    if width == 0 || height == 0 {
+        return Err(TestypfError::ExportFailed(
+            "Image has zero dimensions".to_string(),
+        ));
+    }
+
+    if rgba.len() != (width as usize) * (height as usize) * 4 {
+        return Err(TestypfError::ExportFailed(
+            "Pixel data length is invalid".to_string(),
+        ));
+    }
+
+    let file = std::fs::File::create(path)?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    if let Some(provenance) = provenance {
+        let json = serde_json::to_string(provenance).map_err(|e| {
+            TestypfError::ExportFailed(format!("Failed to serialize provenance: {e}"))
+        })?;
+        for (key, value) in provenance.text_entries() {
+            // tEXt is Latin-1 only; anything else goes into iTXt
+            let result = if value.chars().all(|c| (c as u32) < 0x100) {
+                encoder.add_text_chunk(key, value)
+            } else {
+                encoder.add_itxt_chunk(key, value)
+            };
+            result.map_err(|e| TestypfError::ExportFailed(format!("PNG text chunk: {e}")))?;
+        }
+        encoder
+            .add_itxt_chunk(PROVENANCE_KEY.to_string(), json)
+            .map_err(|e| TestypfError::ExportFailed(format!("PNG text chunk: {e}")))?;
+    }
+
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| TestypfError::ExportFailed(format!("Failed to write PNG header: {e}")))?;
+    writer
+        .write_image_data(rgba)
+        .map_err(|e| TestypfError::ExportFailed(format!("Failed to write PNG data: {e}")))?;
+    writer
+        .finish()
+        .map_err(|e| TestypfError::ExportFailed(format!("Failed to finish PNG: {e}")))
+}
+
+/// Path of the JSON sidecar that accompanies an exported image
+pub fn sidecar_path(image_path: &Path) -> PathBuf {
+    image_path.with_extension("json")
+}
+
+/// Write provenance as a pretty-printed JSON sidecar next to `image_path`
+pub fn write_sidecar(image_path: &Path, provenance: &RenderProvenance) -> TestypfResult<PathBuf> {
+    let path = sidecar_path(image_path);
+    let json = serde_json::to_string_pretty(provenance)
+        .map_err(|e| TestypfError::ExportFailed(format!("Failed to serialize provenance: {e}")))?;
+    std::fs::write(&path, json)?;
+    Ok(path)
+}
+
+/// Read provenance from an exported PNG, falling back to its JSON sidecar
+pub fn read_provenance(image_path: &Path) -> TestypfResult<RenderProvenance> {
+    if let Some(json) = read_png_text(image_path, PROVENANCE_KEY)? {
+        return serde_json::from_str(&json).map_err(|e| {
+            TestypfError::ExportFailed(format!("Invalid provenance in {:?}: {e}", image_path))
+        });
+    }
+
+    let sidecar = sidecar_path(image_path);
+    if sidecar.exists() {
+        let json = std::fs::read_to_string(&sidecar)?;
+        return serde_json::from_str(&json).map_err(|e| {
+            TestypfError::ExportFailed(format!("Invalid provenance sidecar {:?}: {e}", sidecar))
+        });
+    }
+
+    Err(TestypfError::ExportFailed(format!(
+        "{:?} carries no testypf render settings",
+        image_path
+    )))
+}
+
+/// Look up a text chunk (tEXt, zTXt or iTXt) by keyword
+pub fn read_png_text(path: &Path, keyword: &str) -> TestypfResult<Option<String>> {
+    let file = std::fs::File::open(path)?;
+    let mut reader = png::Decoder::new(BufReader::new(file))
+        .read_info()
+        .map_err(|e| TestypfError::ExportFailed(format!("Failed to read PNG {:?}: {e}", path)))?;
+    // Text chunks may follow the image data, so read through to the end
+    reader
+        .finish()
+        .map_err(|e| TestypfError::ExportFailed(format!("Failed to read PNG {:?}: {e}", path)))?;
+
+    let info = reader.info();
+    if let Some(chunk) = info.utf8_text.iter().find(|c| c.keyword == keyword) {
+        return chunk
+            .get_text()
+            .map(Some)
+            .map_err(|e| TestypfError::ExportFailed(format!("Invalid iTXt chunk: {e}")));
+    }
+    if let Some(chunk) = info
+        .uncompressed_latin1_text
+        .iter()
+        .find(|c| c.keyword == keyword)
+    {
+        return Ok(Some(chunk.text.clone()));
+    }
+    if let Some(chunk) = info
+        .compressed_latin1_text
+        .iter()
+        .find(|c| c.keyword == keyword)
+    {
+        return chunk
+            .get_text()
+            .map(Some)
+            .map_err(|e| TestypfError::ExportFailed(format!("Invalid zTXt chunk: {e}")));
+    }
+
+    Ok(None)
+}
+
+/// Image container used when exporting renders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum ExportFormat {
+    #[default]
+    Png,
+    WebP,
+    Tiff,
+    Jpeg,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::Png,
+        ExportFormat::WebP,
+        ExportFormat::Tiff,
+        ExportFormat::Jpeg,
+    ];
+
+    /// File extension (without dot)
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Png => "png",
+            ExportFormat::WebP => "webp",
+            ExportFormat::Tiff => "tiff",
+            ExportFormat::Jpeg => "jpg",
+        }
+    }
+
+    /// Format named by a file extension, case-insensitively (`tif` and `jpeg` included)
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "png" => Some(ExportFormat::Png),
+            "webp" => Some(ExportFormat::WebP),
+            "tif" | "tiff" => Some(ExportFormat::Tiff),
+            "jpg" | "jpeg" => Some(ExportFormat::Jpeg),
+            _ => None,
+        }
+    }
+
+    /// Whether the format keeps an alpha channel
+    pub fn supports_alpha(self) -> bool {
+        !matches!(self, ExportFormat::Jpeg)
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Png => write!(f, "PNG"),
+            ExportFormat::WebP => write!(f, "WebP (lossless)"),
+            ExportFormat::Tiff => write!(f, "TIFF"),
+            ExportFormat::Jpeg => write!(f, "JPEG"),
+        }
+    }
+}
+
+/// Background used for formats without alpha when no flatten color is set
+pub const DEFAULT_FLATTEN_COLOR: (u8, u8, u8) = (255, 255, 255);
+
+/// Composite straight-alpha RGBA8 over an opaque color
+pub fn flatten_rgba(rgba: &[u8], background: (u8, u8, u8)) -> Vec<u8> {
+    let (br, bg, bb) = background;
+    rgba.chunks_exact(4)
+        .flat_map(|px| {
+            let alpha = px[3] as u32;
+            let blend = |fg: u8, bg: u8| {
+                ((fg as u32 * alpha + bg as u32 * (255 - alpha) + 127) / 255) as u8
+            };
+            [blend(px[0], br), blend(px[1], bg), blend(px[2], bb), 255]
+        })
+        .collect()
+}
+
+/// Write straight-alpha RGBA8 pixels in the chosen format.
+///
+/// `flatten` composites onto an opaque color first; JPEG always flattens (white by default).
+/// Provenance chunks are only embedded in PNG output; use a sidecar for other formats.
+pub fn write_image(
+    path: &Path,
+    format: ExportFormat,
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    flatten: Option<(u8, u8, u8)>,
+    provenance: Option<&RenderProvenance>,
+) -> TestypfResult<()> {
+    let flatten = match (flatten, format.supports_alpha()) {
+        (None, false) => Some(DEFAULT_FLATTEN_COLOR),
+        (flatten, _) => flatten,
+    };
+    let flattened;
+    let pixels = match flatten {
+        Some(background) => {
+            flattened = flatten_rgba(rgba, background);
+            flattened.as_slice()
+        }
+        None => rgba,
+    };
+
+    if format == ExportFormat::Png {
+        return write_png(path, width, height, pixels, provenance);
+    }
+
+    let image = image::RgbaImage::from_raw(width, height, pixels.to_vec())
+        .ok_or_else(|| TestypfError::ExportFailed("Pixel data length is invalid".to_string()))?;
+    let result = match format {
+        ExportFormat::Jpeg => image::DynamicImage::ImageRgba8(image)
+            .to_rgb8()
+            .save_with_format(path, image::ImageFormat::Jpeg),
+        ExportFormat::WebP => image.save_with_format(path, image::ImageFormat::WebP),
+        ExportFormat::Tiff => image.save_with_format(path, image::ImageFormat::Tiff),
+        ExportFormat::Png => unreachable!("PNG handled above"),
+    };
+
+    result.map_err(|e| TestypfError::ExportFailed(format!("Failed to write {format}: {e}")))
+}
+
+/// Default export file name template (matches the historical `NN_<name>` naming)
+pub const DEFAULT_NAME_TEMPLATE: &str = "{index}_{name}";
+
+/// Placeholders understood by [`render_name_template`]
+pub const NAME_PLACEHOLDERS: &[&str] = &[
+    "name", "family", "style", "ps", "size", "backend", "axes", "index", "date",
+];
+
+/// Values substituted into an export file name template
+#[derive(Debug, Clone)]
+pub struct NameFields<'a> {
+    pub font: &'a TestypfFontInfo,
+    pub settings: &'a RenderSettings,
+    /// 1-based position of the preview in the export batch
+    pub index: usize,
+    /// Export date as YYYY-MM-DD
+    pub date: String,
+}
+
+impl<'a> NameFields<'a> {
+    /// Fields for a preview exported today
+    pub fn new(font: &'a TestypfFontInfo, settings: &'a RenderSettings, index: usize) -> Self {
+        Self {
+            font,
+            settings,
+            index,
+            date: today_utc(),
+        }
+    }
+
+    fn value(&self, placeholder: &str) -> Option<String> {
+        let value = match placeholder {
+            "name" => return Some(sanitized_file_stem(&self.font.full_name)),
+            "family" => self.font.family_name.clone(),
+            "style" => self.font.style.clone(),
+            "ps" => self.font.postscript_name.clone(),
+            "size" => format!("{}", self.settings.font_size),
+            "backend" => self.settings.backend.to_string(),
+            "axes" => {
+                let mut coords: Vec<_> = self.settings.variation_coords.iter().collect();
+                coords.sort_by(|a, b| a.0.cmp(b.0));
+                if coords.is_empty() {
+                    "default".to_string()
+                } else {
+                    coords
+                        .into_iter()
+                        .map(|(tag, value)| format!("{tag}{value}"))
+                        .collect::<Vec<_>>()
+                        .join("_")
+                }
+            }
+            "index" => format!("{:02}", self.index),
+            "date" => self.date.clone(),
+            _ => return None,
+        };
+        Some(sanitize_component(&value))
+    }
+}
+
+/// Expand `{placeholder}` fields in an export name template into a file stem
+pub fn render_name_template(template: &str, fields: &NameFields<'_>) -> TestypfResult<String> {
+    let stem = sanitize_component(&expand_template(template, |p| fields.value(p))?);
+    if stem.is_empty() {
+        return Err(TestypfError::ExportFailed(format!(
+            "Template '{template}' produced an empty file name"
+        )));
+    }
+    Ok(stem)
+}
+
+/// Check that a template only uses known placeholders and is well formed
+pub fn validate_name_template(template: &str) -> TestypfResult<()> {
+    if template.trim().is_empty() {
+        return Err(TestypfError::ExportFailed(
+            "Export name template is empty".to_string(),
+        ));
+    }
+    expand_template(template, |p| {
+        NAME_PLACEHOLDERS.contains(&p).then(String::new)
+    })
+    .map(|_| ())
+}
+
+fn expand_template(
+    template: &str,
+    mut value: impl FnMut(&str) -> Option<String>,
+) -> TestypfResult<String> {
+    let mut out = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let end = after.find('}').ok_or_else(|| {
+            TestypfError::ExportFailed(format!("Unclosed placeholder in template '{template}'"))
+        })?;
+        let placeholder = &after[..end];
+        let expanded = value(placeholder).ok_or_else(|| {
+            TestypfError::ExportFailed(format!(
+                "Unknown placeholder {{{placeholder}}}; use one of {}",
+                NAME_PLACEHOLDERS
+                    .iter()
+                    .map(|p| format!("{{{p}}}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
+        out.push_str(&expanded);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Make a filesystem-safe, lowercase filename stem from user-facing font names
+pub fn sanitized_file_stem(name: &str) -> String {
+    let mut stem: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    while stem.contains("__") {
+        stem = stem.replace("__", "_");
+    }
+
+    stem.trim_matches('_')
+        .chars()
+        .take(40)
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Replace characters that are unsafe in file names, keeping case, `-` and `.`
+pub fn sanitize_component(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        let c = if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_') {
+            c
+        } else {
+            '_'
+        };
+        if !(c == '_' && out.ends_with('_')) {
+            out.push(c);
+        }
+    }
+    out.trim_matches(|c| c == '_' || c == '.').to_string()
+}
+
+/// Current UTC date as YYYY-MM-DD
+fn today_utc() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Convert days since 1970-01-01 into a (year, month, day) civil date
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's days-to-civil algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
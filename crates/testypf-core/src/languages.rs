@@ -0,0 +1,168 @@
+use crate::coverage::FontCoverage;
+
+/// Share of a language's exemplars a font must cover to count as partial support
+pub const PARTIAL_SUPPORT: f64 = 0.75;
+
+/// A language and its main exemplar characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Language {
+    /// BCP 47 code
+    pub code: &'static str,
+    /// English name
+    pub name: &'static str,
+    /// Space-separated lowercase exemplars; a multi-character entry needs all of its codepoints
+    exemplars: &'static str,
+}
+
+impl Language {
+    /// Exemplars plus their uppercase forms, deduplicated, in table order
+    pub fn exemplars(&self) -> Vec<String> {
+        let mut items: Vec<String> = Vec::new();
+        for exemplar in self.exemplars.split_whitespace() {
+            let upper = self.uppercase(exemplar);
+            for form in std::iter::once(exemplar.to_string()).chain(upper) {
+                if !items.contains(&form) {
+                    items.push(form);
+                }
+            }
+        }
+        items
+    }
+
+    /// Uppercase exemplar as CLDR lists it; `to_uppercase` alone is locale-blind
+    fn uppercase(&self, exemplar: &str) -> Option<String> {
+        let turkic = matches!(self.code, "tr" | "az");
+        match exemplar {
+            "i" if turkic => Some("İ".to_string()),
+            // No precomposed capitals: uppercasing yields Ϊ́ / Ϋ́ as three codepoints
+            "ΐ" | "ΰ" => None,
+            _ => Some(exemplar.to_uppercase()),
+        }
+    }
+}
+
+const fn lang(code: &'static str, name: &'static str, exemplars: &'static str) -> Language {
+    Language {
+        code,
+        name,
+        exemplars,
+    }
+}
+
+/// Main exemplar sets derived from CLDR, limited to alphabets and abugidas small enough to list
+pub const LANGUAGES: &[Language] = &[
+    lang("af", "Afrikaans", "a á â b c d e é è ê ë f g h i î ï j k l m n o ô ö p q r s t u û v w x y z"),
+    lang("sq", "Albanian", "a b c ç d e ë f g h i j k l m n o p q r s t u v x y z"),
+    lang("ar", "Arabic", "ء آ أ ؤ إ ئ ا ب ة ت ث ج ح خ د ذ ر ز س ش ص ض ط ظ ع غ ف ق ك ل م ن ه و ى ي \u{64B} \u{64C} \u{64D} \u{64E} \u{64F} \u{650} \u{651} \u{652}"),
+    lang("hy", "Armenian", "ա բ գ դ ե զ է ը թ ժ ի լ խ ծ կ հ ձ ղ ճ մ յ ն շ ո չ պ ջ ռ ս վ տ ր ց ւ փ ք օ ֆ և"),
+    lang("az", "Azerbaijani", "a b c ç d e ə f g ğ h x ı i j k q l m n o ö p r s ş t u ü v y z"),
+    lang("eu", "Basque", "a b c ç d e f g h i j k l m n ñ o p q r s t u v w x y z"),
+    lang("be", "Belarusian", "а б в г д е ё ж з і й к л м н о п р с т у ў ф х ц ч ш ы ь э ю я"),
+    lang("bg", "Bulgarian", "а б в г д е ж з и й к л м н о п р с т у ф х ц ч ш щ ъ ь ю я"),
+    lang("ca", "Catalan", "a à b c ç d e é è f g h i í ï j k l ŀ m n o ó ò p q r s t u ú ü v w x y z"),
+    lang("hr", "Croatian", "a b c č ć d đ e f g h i j k l m n o p r s š t u v z ž"),
+    lang("cs", "Czech", "a á b c č d ď e é ě f g h i í j k l m n ň o ó p q r ř s š t ť u ú ů v w x y ý z ž"),
+    lang("da", "Danish", "a b c d e f g h i j k l m n o p q r s t u v w x y z æ ø å"),
+    lang("nl", "Dutch", "a á ä b c d e é ë f g h i í ï j k l m n o ó ö p q r s t u ú ü v w x y z"),
+    lang("en", "English", "a b c d e f g h i j k l m n o p q r s t u v w x y z"),
+    lang("eo", "Esperanto", "a b c ĉ d e f g ĝ h ĥ i j ĵ k l m n o p r s ŝ t u ŭ v z"),
+    lang("et", "Estonian", "a b c d e f g h i j k l m n o p q r s š z ž t u v w õ ä ö ü x y"),
+    lang("fil", "Filipino", "a b c d e f g h i j k l m n ñ o p q r s t u v w x y z"),
+    lang("fi", "Finnish", "a b c d e f g h i j k l m n o p q r s š t u v w x y z ž å ä ö"),
+    lang("fr", "French", "a à â æ b c ç d e é è ê ë f g h i î ï j k l m n o ô œ p q r s t u ù û ü v w x y ÿ z"),
+    lang("gl", "Galician", "a á b c d e é f g h i í j k l m n ñ o ó p q r s t u ú ü v w x y z"),
+    lang("ka", "Georgian", "ა ბ გ დ ე ვ ზ თ ი კ ლ მ ნ ო პ ჟ რ ს ტ უ ფ ქ ღ ყ შ ჩ ც ძ წ ჭ ხ ჯ ჰ"),
+    lang("de", "German", "a ä b c d e f g h i j k l m n o ö p q r s ß t u ü v w x y z"),
+    lang("el", "Greek", "α ά β γ δ ε έ ζ η ή θ ι ί ϊ ΐ κ λ μ ν ξ ο ό π ρ σ ς τ υ ύ ϋ ΰ φ χ ψ ω ώ"),
+    lang("ha", "Hausa", "a b ɓ c d ɗ e f g h i j k ƙ l m n o r s t u w y ƴ z"),
+    lang("haw", "Hawaiian", "a ā e ē i ī o ō u ū h k l m n p w ʻ"),
+    lang("he", "Hebrew", "א ב ג ד ה ו ז ח ט י כ ך ל מ ם נ ן ס ע פ ף צ ץ ק ר ש ת"),
+    lang("hi", "Hindi", "ँ ं ः अ आ इ ई उ ऊ ऋ ए ऐ ऑ ओ औ क ख ग घ ङ च छ ज झ ञ ट ठ ड ढ ण त थ द ध न प फ ब भ म य र ल व श ष स ह ़ ा ि ी ु ू ृ ॅ े ै ॉ ो ौ ्"),
+    lang("hu", "Hungarian", "a á b c d e é f g h i í j k l m n o ó ö ő p q r s t u ú ü ű v w x y z"),
+    lang("is", "Icelandic", "a á b d ð e é f g h i í j k l m n o ó p r s t u ú v x y ý þ æ ö"),
+    lang("id", "Indonesian", "a b c d e f g h i j k l m n o p q r s t u v w x y z"),
+    lang("ga", "Irish", "a á b c d e é f g h i í l m n o ó p r s t u ú"),
+    lang("it", "Italian", "a à b c d e é è f g h i ì j k l m n o ó ò p q r s t u ù v w x y z"),
+    lang("kk", "Kazakh", "а ә б в г ғ д е ё ж з и й к қ л м н ң о ө п р с т у ұ ү ф х һ ц ч ш щ ъ ы і ь э ю я"),
+    lang("lv", "Latvian", "a ā b c č d e ē f g ģ h i ī j k ķ l ļ m n ņ o p r s š t u ū v z ž"),
+    lang("lt", "Lithuanian", "a ą b c č d e ę ė f g h i į y j k l m n o p r s š t u ų ū v z ž"),
+    lang("mk", "Macedonian", "а б в г д ѓ е ж з ѕ и ј к л љ м н њ о п р с т ќ у ф х ц ч џ ш"),
+    lang("ms", "Malay", "a b c d e f g h i j k l m n o p q r s t u v w x y z"),
+    lang("mt", "Maltese", "a à b ċ d e è f ġ g h ħ i ì j k l m n o ò p q r s t u ù v w x ż z"),
+    lang("mi", "Māori", "a ā e ē h i ī k m n o ō p r t u ū w"),
+    lang("mn", "Mongolian", "а б в г д е ё ж з и й к л м н о ө п р с т у ү ф х ц ч ш щ ъ ы ь э ю я"),
+    lang("nb", "Norwegian Bokmål", "a à b c d e é f g h i j k l m n o ó ò ô p q r s t u v w x y z æ ø å"),
+    lang("fa", "Persian", "آ ا ء أ ؤ ئ ب پ ت ث ج چ ح خ د ذ ر ز ژ س ش ص ض ط ظ ع غ ف ق ک گ ل م ن و ه ة ی \u{64B} \u{64C} \u{64D} \u{651} \u{654}"),
+    lang("pl", "Polish", "a ą b c ć d e ę f g h i j k l ł m n ń o ó p r s ś t u w y z ź ż"),
+    lang("pt", "Portuguese", "a á à â ã b c ç d e é ê f g h i í j k l m n o ó ô õ p q r s t u ú v w x y z"),
+    lang("ro", "Romanian", "a ă â b c d e f g h i î j k l m n o p q r s ș t ț u v w x y z"),
+    lang("ru", "Russian", "а б в г д е ё ж з и й к л м н о п р с т у ф х ц ч ш щ ъ ы ь э ю я"),
+    lang("sr", "Serbian", "а б в г д ђ е ж з и ј к л љ м н њ о п р с т ћ у ф х ц ч џ ш"),
+    lang("sk", "Slovak", "a á ä b c č d ď e é f g h i í j k l ĺ ľ m n ň o ó ô p q r ŕ s š t ť u ú v w x y ý z ž"),
+    lang("sl", "Slovenian", "a b c č d e f g h i j k l m n o p r s š t u v z ž"),
+    lang("es", "Spanish", "a á b c d e é f g h i í j k l m n ñ o ó p q r s t u ú ü v w x y z"),
+    lang("sw", "Swahili", "a b c d e f g h i j k l m n o p r s t u v w y z"),
+    lang("sv", "Swedish", "a à b c d e é f g h i j k l m n o p q r s t u v w x y z å ä ö"),
+    lang("th", "Thai", "ก ข ฃ ค ฅ ฆ ง จ ฉ ช ซ ฌ ญ ฎ ฏ ฐ ฑ ฒ ณ ด ต ถ ท ธ น บ ป ผ ฝ พ ฟ ภ ม ย ร ฤ ล ฦ ว ศ ษ ส ห ฬ อ ฮ ฯ ะ \u{E31} า \u{E33} \u{E34} \u{E35} \u{E36} \u{E37} \u{E38} \u{E39} \u{E3A} เ แ โ ใ ไ ๅ ๆ \u{E47} \u{E48} \u{E49} \u{E4A} \u{E4B} \u{E4C} \u{E4D}"),
+    lang("tr", "Turkish", "a b c ç d e f g ğ h ı i j k l m n o ö p r s ş t u ü v y z"),
+    lang("uk", "Ukrainian", "а б в г ґ д е є ж з и і ї й к л м н о п р с т у ф х ц ч ш щ ь ю я"),
+    lang("uz", "Uzbek", "a b d e f g h i j k l m n o p q r s t u v x y z ʻ"),
+    lang("vi", "Vietnamese", "a à ả ã á ạ ă ằ ẳ ẵ ắ ặ â ầ ẩ ẫ ấ ậ b c d đ e è ẻ ẽ é ẹ ê ề ể ễ ế ệ g h i ì ỉ ĩ í ị k l m n o ò ỏ õ ó ọ ô ồ ổ ỗ ố ộ ơ ờ ở ỡ ớ ợ p q r s t u ù ủ ũ ú ụ ư ừ ử ữ ứ ự v x y ỳ ỷ ỹ ý ỵ"),
+    lang("cy", "Welsh", "a á à â ä b c d e é è ê ë f g h i í ì î ï j l m n o ó ò ô ö p r s t u ú ù û ü w ẁ ẃ ŵ ẅ y ý ỳ ŷ ÿ"),
+    lang("yo", "Yoruba", "a á à b d e é è ẹ ẹ\u{301} ẹ\u{300} f g h i í ì j k l m n o ó ò ọ ọ\u{301} ọ\u{300} p r s ṣ t u ú ù w y"),
+    lang("zu", "Zulu", "a b c d e f g h i j k l m n o p q r s t u v w x y z"),
+];
+
+/// How much of one language's exemplar set a font covers
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageSupport {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub covered: usize,
+    pub total: usize,
+    /// Exemplars the font lacks, in table order
+    pub missing: Vec<String>,
+}
+
+impl LanguageSupport {
+    pub fn is_full(&self) -> bool {
+        self.covered == self.total
+    }
+
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.covered as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+/// Check one language against a font's coverage
+pub fn language_support(coverage: &FontCoverage, language: &Language) -> LanguageSupport {
+    let exemplars = language.exemplars();
+    let total = exemplars.len();
+    let missing: Vec<String> = exemplars
+        .into_iter()
+        .filter(|exemplar| !exemplar.chars().all(|ch| coverage.contains(ch)))
+        .collect();
+    LanguageSupport {
+        code: language.code,
+        name: language.name,
+        covered: total - missing.len(),
+        total,
+        missing,
+    }
+}
+
+/// Languages a font supports fully or at least partially (see [`PARTIAL_SUPPORT`]), full ones first, then by name
+pub fn supported_languages(coverage: &FontCoverage) -> Vec<LanguageSupport> {
+    let mut supported: Vec<LanguageSupport> = LANGUAGES
+        .iter()
+        .map(|language| language_support(coverage, language))
+        .filter(|support| support.percent() >= PARTIAL_SUPPORT * 100.0)
+        .collect();
+    // Stable, so each group keeps the table's alphabetical order
+    supported.sort_by_key(|support| !support.is_full());
+    supported
+}
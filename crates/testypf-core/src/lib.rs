@@ -4,13 +4,14 @@
 //! including integration with typf for rendering, fontlift for font management,
 //! and typg for font discovery.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 pub use fontlift_core::{FontScope, FontliftFontSource};
 
 // Re-export discovery types for GUI use
 pub use discovery::{DiscoveryManager, FontDiscoveryResult, SearchCriteria};
+pub use pixels::PixelBuffer;
 
 /// Core errors for testypf
 #[derive(Error, Debug)]
@@ -29,6 +30,12 @@ pub enum TestypfError {
 
     #[error("Font discovery failed: {0}")]
     DiscoveryFailed(String),
+
+    #[error("Export failed: {0}")]
+    ExportFailed(String),
+
+    #[error("Renders differ: {0}")]
+    RenderMismatch(String),
 }
 
 /// Result type for testypf operations
@@ -53,6 +60,68 @@ pub struct TestypfVariationAxis {
     pub max_value: f32,
 }
 
+/// Vertical metrics from the head, hhea and OS/2 tables, in font units
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct TestypfVerticalMetrics {
+    pub units_per_em: u16,
+
+    /// hhea ascender, descender (negative below the baseline) and line gap
+    pub hhea_ascender: i16,
+    pub hhea_descender: i16,
+    pub hhea_line_gap: i16,
+
+    /// OS/2 sTypoAscender, sTypoDescender and sTypoLineGap
+    pub typo_ascender: i16,
+    pub typo_descender: i16,
+    pub typo_line_gap: i16,
+
+    /// OS/2 usWinAscent and usWinDescent (both positive)
+    pub win_ascent: u16,
+    pub win_descent: u16,
+
+    /// OS/2 sxHeight and sCapHeight (version 2 and later)
+    pub x_height: Option<i16>,
+    pub cap_height: Option<i16>,
+}
+
+impl TestypfVerticalMetrics {
+    /// Read metrics from a parsed font; `None` without a usable head table.
+    ///
+    /// Missing hhea or OS/2 tables leave their fields at zero.
+    pub fn from_font(font: &read_fonts::FontRef) -> Option<Self> {
+        use read_fonts::TableProvider;
+
+        let units_per_em = font.head().ok()?.units_per_em();
+        if units_per_em == 0 {
+            return None;
+        }
+        let mut metrics = Self {
+            units_per_em,
+            ..Self::default()
+        };
+        if let Ok(hhea) = font.hhea() {
+            metrics.hhea_ascender = hhea.ascender().to_i16();
+            metrics.hhea_descender = hhea.descender().to_i16();
+            metrics.hhea_line_gap = hhea.line_gap().to_i16();
+        }
+        if let Ok(os2) = font.os2() {
+            metrics.typo_ascender = os2.s_typo_ascender();
+            metrics.typo_descender = os2.s_typo_descender();
+            metrics.typo_line_gap = os2.s_typo_line_gap();
+            metrics.win_ascent = os2.us_win_ascent();
+            metrics.win_descent = os2.us_win_descent();
+            metrics.x_height = os2.sx_height();
+            metrics.cap_height = os2.s_cap_height();
+        }
+        Some(metrics)
+    }
+
+    /// Convert a value in font units to pixels at `pixel_size` pixels per em
+    pub fn to_pixels(&self, units: f32, pixel_size: f32) -> f32 {
+        units * pixel_size / f32::from(self.units_per_em.max(1))
+    }
+}
+
 /// Font face information for GUI display
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TestypfFontInfo {
@@ -76,6 +145,10 @@ pub struct TestypfFontInfo {
 
     /// Variable font axes (empty if not a variable font)
     pub variation_axes: Vec<TestypfVariationAxis>,
+
+    /// Vertical metrics, `None` when the font has no readable head table
+    #[serde(default)]
+    pub vertical_metrics: Option<TestypfVerticalMetrics>,
 }
 
 impl TestypfFontInfo {
@@ -86,6 +159,27 @@ impl TestypfFontInfo {
     pub fn with_scope(&self, scope: FontScope) -> FontliftFontSource {
         self.source.clone().with_scope(Some(scope))
     }
+
+    /// Read the font file and decode its table directory and common tables
+    pub fn font_tables(&self) -> TestypfResult<crate::tables::FontTables> {
+        let data = std::fs::read(self.path())
+            .map_err(|e| TestypfError::InvalidFont(format!("Failed to read font file: {}", e)))?;
+        crate::tables::read_font_tables(&data, 0)
+    }
+
+    /// Read the font file and collect the codepoints its cmap maps to glyphs
+    pub fn coverage(&self) -> TestypfResult<crate::coverage::FontCoverage> {
+        let data = std::fs::read(self.path())
+            .map_err(|e| TestypfError::InvalidFont(format!("Failed to read font file: {}", e)))?;
+        crate::coverage::FontCoverage::read(&data, 0)
+    }
+
+    /// Read the font file and build control strings and kern pairs for spacing review
+    pub fn spacing_proof(&self) -> TestypfResult<crate::proofs::SpacingProof> {
+        let data = std::fs::read(self.path())
+            .map_err(|e| TestypfError::InvalidFont(format!("Failed to read font file: {}", e)))?;
+        crate::proofs::SpacingProof::read(&data, 0)
+    }
 }
 
 /// Render settings for text rendering
@@ -94,7 +188,7 @@ pub struct RenderSettings {
     /// Sample text to render
     pub sample_text: String,
 
-    /// Font size in points
+    /// Font size in points (logical pixels)
     pub font_size: f32,
 
     /// Foreground color (RGBA)
@@ -112,6 +206,230 @@ pub struct RenderSettings {
     /// Variable font axis coordinates (tag -> value)
     #[serde(default)]
     pub variation_coords: std::collections::HashMap<String, f32>,
+
+    /// Device pixels per logical pixel; font size and padding are multiplied by it
+    #[serde(default = "default_scale_factor")]
+    pub scale_factor: f32,
+
+    /// Antialiasing, hinting and gamma options for the rasterizer
+    #[serde(default)]
+    pub raster: RasterOptions,
+
+    /// Ask typf to report per-glyph positions and ink bounds with the pixels
+    #[serde(default)]
+    pub glyph_positions: bool,
+
+    /// Unicode normalization applied to the sample text after escape decoding
+    #[serde(default)]
+    pub normalization: crate::normalization::Normalization,
+}
+
+fn default_scale_factor() -> f32 {
+    1.0
+}
+
+impl RenderSettings {
+    /// Font size in device pixels, as passed to typf
+    pub fn device_font_size(&self) -> f32 {
+        self.font_size * self.effective_scale()
+    }
+
+    /// Padding in device pixels, as passed to typf
+    pub fn device_padding(&self) -> u32 {
+        (self.padding as f32 * self.effective_scale()).round() as u32
+    }
+
+    /// Copy of these settings rendering at a different scale factor
+    pub fn at_scale(&self, scale_factor: f32) -> Self {
+        Self {
+            scale_factor,
+            ..self.clone()
+        }
+    }
+
+    /// Text handed to the shaper for `font_path`: escapes decoded, then normalized
+    pub fn text_for_font(&self, font_path: &Path) -> String {
+        let decoded = crate::escapes::decode_for_font(&self.sample_text, font_path);
+        self.normalization.apply(&decoded.text)
+    }
+
+    /// Scale factor, treating non-finite or non-positive values as 1.0
+    pub fn effective_scale(&self) -> f32 {
+        if self.scale_factor.is_finite() && self.scale_factor > 0.0 {
+            self.scale_factor
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Glyph coverage mode used by the rasterizer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum AntialiasMode {
+    /// Aliased, 1-bit coverage
+    None,
+    /// Grayscale coverage
+    #[default]
+    Grayscale,
+    /// Subpixel (LCD) coverage
+    Lcd,
+}
+
+impl AntialiasMode {
+    pub const ALL: [AntialiasMode; 3] = [
+        AntialiasMode::None,
+        AntialiasMode::Grayscale,
+        AntialiasMode::Lcd,
+    ];
+
+    /// Value passed to typf's `antialias` keyword
+    pub fn typf_name(self) -> &'static str {
+        match self {
+            AntialiasMode::None => "none",
+            AntialiasMode::Grayscale => "grayscale",
+            AntialiasMode::Lcd => "lcd",
+        }
+    }
+}
+
+impl std::fmt::Display for AntialiasMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AntialiasMode::None => write!(f, "Off (aliased)"),
+            AntialiasMode::Grayscale => write!(f, "Grayscale"),
+            AntialiasMode::Lcd => write!(f, "Subpixel LCD"),
+        }
+    }
+}
+
+/// Outline hinting strength used by the rasterizer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum HintingMode {
+    #[default]
+    None,
+    Slight,
+    Full,
+}
+
+impl HintingMode {
+    pub const ALL: [HintingMode; 3] = [HintingMode::None, HintingMode::Slight, HintingMode::Full];
+
+    /// Value passed to typf's `hinting` keyword
+    pub fn typf_name(self) -> &'static str {
+        match self {
+            HintingMode::None => "none",
+            HintingMode::Slight => "slight",
+            HintingMode::Full => "full",
+        }
+    }
+}
+
+impl std::fmt::Display for HintingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HintingMode::None => write!(f, "No hinting"),
+            HintingMode::Slight => write!(f, "Slight hinting"),
+            HintingMode::Full => write!(f, "Full hinting"),
+        }
+    }
+}
+
+/// Rasterization options; only non-default values are forwarded to typf
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RasterOptions {
+    /// Coverage mode (off, grayscale, subpixel LCD)
+    #[serde(default)]
+    pub antialias: AntialiasMode,
+
+    /// Hinting strength
+    #[serde(default)]
+    pub hinting: HintingMode,
+
+    /// Gamma applied to glyph coverage (1.0 = linear, backend default)
+    #[serde(default = "default_gamma")]
+    pub gamma: f32,
+}
+
+fn default_gamma() -> f32 {
+    1.0
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        Self {
+            antialias: AntialiasMode::default(),
+            hinting: HintingMode::default(),
+            gamma: default_gamma(),
+        }
+    }
+}
+
+impl RasterOptions {
+    /// Copy with every option the backend cannot honor reset to its default
+    pub fn restricted_to(&self, caps: RasterCapabilities) -> Self {
+        let defaults = Self::default();
+        Self {
+            antialias: match self.antialias {
+                AntialiasMode::None if !caps.antialias_off => defaults.antialias,
+                AntialiasMode::Lcd if !caps.lcd => defaults.antialias,
+                mode => mode,
+            },
+            hinting: if caps.hinting {
+                self.hinting
+            } else {
+                defaults.hinting
+            },
+            gamma: if caps.gamma {
+                self.gamma
+            } else {
+                defaults.gamma
+            },
+        }
+    }
+
+    /// Names of requested (non-default) options the backend cannot honor
+    pub fn unsupported_by(&self, caps: RasterCapabilities) -> Vec<&'static str> {
+        let supported = self.restricted_to(caps);
+        let mut names = Vec::new();
+        if supported.antialias != self.antialias {
+            names.push(match self.antialias {
+                AntialiasMode::Lcd => "subpixel LCD",
+                _ => "antialiasing off",
+            });
+        }
+        if supported.hinting != self.hinting {
+            names.push("hinting");
+        }
+        if supported.gamma != self.gamma {
+            names.push("gamma");
+        }
+        names
+    }
+}
+
+/// Rasterization options a backend can honor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RasterCapabilities {
+    pub antialias_off: bool,
+    pub lcd: bool,
+    pub hinting: bool,
+    pub gamma: bool,
+}
+
+impl RasterCapabilities {
+    /// Names of options this backend ignores
+    pub fn missing(&self) -> Vec<&'static str> {
+        [
+            (self.antialias_off, "antialiasing off"),
+            (self.lcd, "subpixel LCD"),
+            (self.hinting, "hinting"),
+            (self.gamma, "gamma"),
+        ]
+        .into_iter()
+        .filter(|(supported, _)| !supported)
+        .map(|(_, name)| name)
+        .collect()
+    }
 }
 
 /// Available rendering backends
@@ -119,6 +437,7 @@ pub struct RenderSettings {
 pub enum RendererBackend {
     Orge,
     Json,
+    Svg,
     #[cfg(target_os = "macos")]
     CoreGraphics,
     #[cfg(feature = "render-skia")]
@@ -132,6 +451,7 @@ impl std::fmt::Display for RendererBackend {
         match self {
             RendererBackend::Orge => write!(f, "Opixa"),
             RendererBackend::Json => write!(f, "Json"),
+            RendererBackend::Svg => write!(f, "Svg"),
             #[cfg(target_os = "macos")]
             RendererBackend::CoreGraphics => write!(f, "CoreGraphics"),
             #[cfg(feature = "render-skia")]
@@ -142,6 +462,44 @@ impl std::fmt::Display for RendererBackend {
     }
 }
 
+impl RendererBackend {
+    /// Rasterization options this backend honors; the rest are not sent to typf
+    pub fn raster_capabilities(&self) -> RasterCapabilities {
+        match self {
+            RendererBackend::Orge => RasterCapabilities {
+                antialias_off: true,
+                gamma: true,
+                ..RasterCapabilities::default()
+            },
+            // Metadata and vector output have no rasterizer to configure
+            RendererBackend::Json | RendererBackend::Svg => RasterCapabilities::default(),
+            #[cfg(target_os = "macos")]
+            RendererBackend::CoreGraphics => RasterCapabilities {
+                antialias_off: true,
+                lcd: true,
+                ..RasterCapabilities::default()
+            },
+            #[cfg(feature = "render-skia")]
+            RendererBackend::Skia => RasterCapabilities {
+                antialias_off: true,
+                lcd: true,
+                hinting: true,
+                gamma: true,
+            },
+            #[cfg(feature = "render-zeno")]
+            RendererBackend::Zeno => RasterCapabilities {
+                antialias_off: true,
+                ..RasterCapabilities::default()
+            },
+        }
+    }
+
+    /// Whether the backend produces a bitmap rather than shaping metadata or vector markup
+    pub fn is_raster(&self) -> bool {
+        !matches!(self, RendererBackend::Json | RendererBackend::Svg)
+    }
+}
+
 impl Default for RenderSettings {
     fn default() -> Self {
         Self {
@@ -152,6 +510,119 @@ impl Default for RenderSettings {
             backend: RendererBackend::Orge,
             padding: 10,
             variation_coords: std::collections::HashMap::new(),
+            scale_factor: default_scale_factor(),
+            raster: RasterOptions::default(),
+            glyph_positions: false,
+            normalization: crate::normalization::Normalization::None,
+        }
+    }
+}
+
+/// Layout of the bytes in a RenderResult
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 8-bit luminance, opaque
+    Gray8,
+    /// 8-bit luminance + straight alpha
+    GrayAlpha8,
+    /// RGBA, straight alpha
+    Rgba8,
+    /// BGRA, straight alpha
+    Bgra8,
+    /// RGBA, premultiplied alpha
+    Rgba8Premultiplied,
+    /// BGRA, premultiplied alpha
+    Bgra8Premultiplied,
+    /// Subpixel (LCD) rendering, 3 bytes per pixel in R,G,B subpixel order, opaque
+    LcdRgb8,
+    /// Subpixel (LCD) rendering, 3 bytes per pixel in B,G,R subpixel order, opaque
+    LcdBgr8,
+    /// UTF-8 SVG markup
+    Svg,
+    /// UTF-8 JSON (shaping/metadata output)
+    Json,
+}
+
+impl PixelFormat {
+    /// Bytes per pixel for bitmap formats, `None` for text payloads
+    pub fn bytes_per_pixel(self) -> Option<usize> {
+        match self {
+            PixelFormat::Gray8 => Some(1),
+            PixelFormat::GrayAlpha8 => Some(2),
+            PixelFormat::LcdRgb8 | PixelFormat::LcdBgr8 => Some(3),
+            PixelFormat::Rgba8
+            | PixelFormat::Bgra8
+            | PixelFormat::Rgba8Premultiplied
+            | PixelFormat::Bgra8Premultiplied => Some(4),
+            PixelFormat::Svg | PixelFormat::Json => None,
+        }
+    }
+
+    /// Interpret a typf format name, inferring from the buffer size when the name is unknown
+    pub fn from_typf(name: &str, width: u32, height: u32, data_len: usize) -> Self {
+        let key: String = name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let premultiplied = key.contains("premul") || key.contains("pma");
+
+        match key.as_str() {
+            "svg" => return PixelFormat::Svg,
+            "json" => return PixelFormat::Json,
+            _ => {}
+        }
+        if key.contains("lcd") || key.contains("subpixel") {
+            return if key.contains("bgr") {
+                PixelFormat::LcdBgr8
+            } else {
+                PixelFormat::LcdRgb8
+            };
+        }
+        if key.contains("bgra") {
+            return if premultiplied {
+                PixelFormat::Bgra8Premultiplied
+            } else {
+                PixelFormat::Bgra8
+            };
+        }
+        if key.contains("rgba") {
+            return if premultiplied {
+                PixelFormat::Rgba8Premultiplied
+            } else {
+                PixelFormat::Rgba8
+            };
+        }
+        if key.starts_with("graya") || key.starts_with("greya") || key.starts_with("la8") {
+            return PixelFormat::GrayAlpha8;
+        }
+        if key.starts_with("gray") || key.starts_with("grey") || key == "l8" || key == "luma8" {
+            return PixelFormat::Gray8;
+        }
+
+        let pixels = (width as usize) * (height as usize);
+        match data_len.checked_div(pixels) {
+            Some(1) if data_len == pixels => PixelFormat::Gray8,
+            Some(2) if data_len == pixels * 2 => PixelFormat::GrayAlpha8,
+            Some(3) if data_len == pixels * 3 => PixelFormat::LcdRgb8,
+            _ => PixelFormat::Rgba8,
+        }
+    }
+}
+
+impl std::fmt::Display for PixelFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PixelFormat::Gray8 => write!(f, "Gray8"),
+            PixelFormat::GrayAlpha8 => write!(f, "GrayAlpha8"),
+            PixelFormat::Rgba8 => write!(f, "Rgba8"),
+            PixelFormat::Bgra8 => write!(f, "Bgra8"),
+            PixelFormat::Rgba8Premultiplied => write!(f, "Rgba8 (premultiplied)"),
+            PixelFormat::Bgra8Premultiplied => write!(f, "Bgra8 (premultiplied)"),
+            PixelFormat::LcdRgb8 => write!(f, "LCD RGB"),
+            PixelFormat::LcdBgr8 => write!(f, "LCD BGR"),
+            PixelFormat::Svg => write!(f, "svg"),
+            PixelFormat::Json => write!(f, "json"),
         }
     }
 }
@@ -165,11 +636,38 @@ pub struct RenderResult {
     /// Image height in pixels
     pub height: u32,
 
-    /// RGBA pixel data
-    pub data: Vec<u8>,
+    /// Pixel data (or SVG/JSON text) laid out as described by `format`; cheap to clone
+    pub data: PixelBuffer,
+
+    /// Layout of `data`
+    pub format: PixelFormat,
+
+    /// Per-glyph positions and ink bounds in device pixels, when typf reports them
+    pub glyphs: Vec<crate::overlay::GlyphPlacement>,
+
+    /// Baseline of the first line in device pixels from the top, when known
+    pub baseline: Option<f32>,
+}
+
+impl RenderResult {
+    /// Whether this result carries vector (SVG) output instead of a bitmap
+    pub fn is_vector(&self) -> bool {
+        self.format == PixelFormat::Svg
+    }
+
+    /// Convert bitmap output of any supported layout into straight-alpha RGBA8
+    pub fn to_rgba8(&self) -> TestypfResult<RenderResult> {
+        crate::pixels::to_rgba8(self)
+    }
+
+    /// SVG markup for vector results, `None` for bitmaps
+    pub fn svg_markup(&self) -> Option<&str> {
+        if !self.is_vector() {
+            return None;
+        }
 
-    /// Format description
-    pub format: String,
+        std::str::from_utf8(&self.data).ok()
+    }
 }
 
 /// Font manager interface
@@ -213,6 +711,44 @@ pub trait TextRenderer: Send + Sync {
 
     /// Set backend
     fn set_backend(&mut self, backend: RendererBackend) -> TestypfResult<()>;
+
+    /// Backend renders currently go through
+    fn backend(&self) -> RendererBackend;
+
+    /// Name of the shaper used for rendering
+    fn shaper(&self) -> &str;
+
+    /// Shape text with the JSON backend and return typed glyph runs.
+    ///
+    /// Switches to `RendererBackend::Json` for the call and back to the previous backend
+    /// afterwards, whether or not shaping succeeded.
+    /// Glyph names missing from typf output are filled in from the font's `post` table.
+    fn shape_text(
+        &mut self,
+        font_path: &PathBuf,
+        settings: &RenderSettings,
+    ) -> TestypfResult<crate::shaping::ShapingResult> {
+        let previous = self.backend();
+        self.set_backend(RendererBackend::Json)?;
+        let json_settings = RenderSettings {
+            backend: RendererBackend::Json,
+            ..settings.clone()
+        };
+        let output = self.render_text(font_path, &json_settings);
+        let output = match (output, self.set_backend(previous.clone())) {
+            (output, Ok(())) => output?,
+            (Ok(_), Err(restore)) => return Err(restore),
+            (Err(render), Err(restore)) => {
+                return Err(TestypfError::RenderFailed(format!(
+                    "{render}; restoring the {previous} backend also failed: {restore}"
+                )))
+            }
+        };
+
+        let mut result = crate::shaping::ShapingResult::from_render_result(&output)?;
+        result.fill_glyph_names(font_path);
+        Ok(result)
+    }
 }
 
 /// Main testypf engine
@@ -243,6 +779,16 @@ impl TestypfEngine {
         &mut *self.text_renderer
     }
 
+    /// Create a text renderer of its own, e.g. for renders that run off the UI thread
+    pub fn detached_text_renderer() -> TestypfResult<Box<dyn TextRenderer>> {
+        Ok(Box::new(crate::render::TypfRenderer::new()?))
+    }
+
+    /// Name of the shaper used by the text renderer
+    pub fn shaper(&self) -> &str {
+        self.text_renderer.shaper()
+    }
+
     /// Set the font installation scope (user vs system)
     pub fn set_font_install_scope(&mut self, scope: FontScope) {
         self.font_manager.set_install_scope(scope);
@@ -258,6 +804,41 @@ impl TestypfEngine {
         crate::font::FontListManager::platform_support_enabled()
     }
 
+    /// Check (or bless) a snapshot manifest against its golden images
+    pub fn run_snapshots(
+        &mut self,
+        manifest: &crate::snapshot::SnapshotManifest,
+        options: &crate::snapshot::SnapshotOptions,
+    ) -> TestypfResult<crate::snapshot::SnapshotReport> {
+        crate::snapshot::run_snapshots(&mut *self.text_renderer, manifest, options)
+    }
+
+    /// Shape test strings with the JSON backend and compare them with their expected glyph runs
+    pub fn run_shaping_tests(
+        &mut self,
+        tests: &[crate::shaping::ShapingTest],
+        base: &RenderSettings,
+    ) -> crate::shaping::ShapingReport {
+        crate::shaping::run_shaping_tests(&mut *self.text_renderer, tests, base)
+    }
+
+    /// Render the sample text as NFC and as NFD with one font and pixel-compare the results
+    pub fn compare_normalization(
+        &mut self,
+        font_path: &PathBuf,
+        settings: &RenderSettings,
+        tolerance: u8,
+        style: crate::diff::DiffStyle,
+    ) -> TestypfResult<crate::normalization::NormalizationComparison> {
+        crate::normalization::compare_nfc_nfd(
+            &*self.text_renderer,
+            font_path,
+            settings,
+            tolerance,
+            style,
+        )
+    }
+
     /// Render preview for multiple fonts
     pub fn render_previews(
         &mut self,
@@ -324,6 +905,7 @@ pub mod font {
 
             // Extract variable font axes from fvar table if present
             let variation_axes = Self::extract_variation_axes(&font);
+            let vertical_metrics = TestypfVerticalMetrics::from_font(&font);
 
             // For now, use filename-based extraction since FontLift validation already confirmed it's a valid font
             // The font parsing and name table extraction can be improved later
@@ -346,6 +928,7 @@ pub mod font {
                 style,
                 is_installed: false,
                 variation_axes,
+                vertical_metrics,
             };
 
             // Check if font is already installed using FontLift
@@ -643,14 +1226,48 @@ pub mod render {
         types::{
             PyAnyMethods, PyBytes, PyBytesMethods, PyDict, PyDictMethods, PyString, PyStringMethods,
         },
-        PyObject, Python,
+        IntoPy, PyObject, PyResult, Python,
     };
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::sync::{Mutex, OnceLock};
 
     /// Python typf module cache
     static TYPF_MODULE: OnceLock<Mutex<Option<PyObject>>> = OnceLock::new();
 
+    /// Keyword parameters of `Typf.render_text`, `None` when it accepts `**kwargs`
+    static RENDER_TEXT_KEYWORDS: OnceLock<Option<HashSet<String>>> = OnceLock::new();
+
+    /// Ask Python for the keywords `render_text` declares.
+    ///
+    /// Older typf builds only take the positional arguments, so a signature that cannot be
+    /// inspected is treated as accepting no keywords at all.
+    fn render_text_keywords(py: Python<'_>, typf_instance: &PyObject) -> Option<HashSet<String>> {
+        let probe = || -> PyResult<Option<HashSet<String>>> {
+            let method = typf_instance.getattr(py, "render_text")?;
+            let signature = py
+                .import_bound("inspect")?
+                .call_method1("signature", (method,))?;
+            let mut names = HashSet::new();
+            for parameter in signature
+                .getattr("parameters")?
+                .call_method0("values")?
+                .iter()?
+            {
+                let parameter = parameter?;
+                let kind: String = parameter.getattr("kind")?.getattr("name")?.extract()?;
+                match kind.as_str() {
+                    "VAR_KEYWORD" => return Ok(None),
+                    "POSITIONAL_ONLY" | "VAR_POSITIONAL" => {}
+                    _ => {
+                        names.insert(parameter.getattr("name")?.extract()?);
+                    }
+                }
+            }
+            Ok(Some(names))
+        };
+        probe().unwrap_or_else(|_| Some(HashSet::new()))
+    }
+
     /// Initialize the typf Python module
     fn ensure_typf_module() -> TestypfResult<()> {
         let module_cache = TYPF_MODULE.get_or_init(|| Mutex::new(None));
@@ -675,7 +1292,7 @@ pub mod render {
     /// Typf-based text renderer using Python bindings
     pub struct TypfRenderer {
         shaper: String,
-        backend: String,
+        backend: RendererBackend,
     }
 
     impl TypfRenderer {
@@ -686,7 +1303,7 @@ pub mod render {
 
             Ok(Self {
                 shaper: "harfbuzz".to_string(),
-                backend: "opixa".to_string(),
+                backend: RendererBackend::Orge,
             })
         }
 
@@ -695,6 +1312,7 @@ pub mod render {
             match backend {
                 RendererBackend::Orge => "opixa",
                 RendererBackend::Json => "json",
+                RendererBackend::Svg => "svg",
                 #[cfg(target_os = "macos")]
                 RendererBackend::CoreGraphics => "coregraphics",
                 #[cfg(feature = "render-skia")]
@@ -727,7 +1345,10 @@ pub mod render {
                 })?;
 
                 let typf_instance = typf_class
-                    .call1(py, (self.shaper.as_str(), self.backend.as_str()))
+                    .call1(
+                        py,
+                        (self.shaper.as_str(), self.backend_to_string(&self.backend)),
+                    )
                     .map_err(|e| {
                         TestypfError::RenderFailed(format!("Failed to create Typf instance: {}", e))
                     })?;
@@ -736,30 +1357,63 @@ pub mod render {
                 let color = Some(settings.foreground_color);
                 let background = settings.background_color;
                 let font_path_str = font_path.to_string_lossy();
+                let text = settings.text_for_font(font_path);
                 let variations: HashMap<String, f32> = settings
                     .variation_coords
                     .iter()
                     .map(|(tag, value)| (tag.clone(), *value))
                     .collect();
 
+                // Rasterization options go as keywords, and only when set and supported by the
+                // backend the typf instance was created with. Keywords this typf build's
+                // `render_text` does not declare are left out rather than failing the render.
+                let raster = settings
+                    .raster
+                    .restricted_to(self.backend.raster_capabilities());
+                let defaults = RasterOptions::default();
+                let accepted =
+                    RENDER_TEXT_KEYWORDS.get_or_init(|| render_text_keywords(py, &typf_instance));
+                let kwargs = PyDict::new_bound(py);
+                let set_kwarg = |key: &str, value: PyObject| {
+                    if accepted.as_ref().is_some_and(|names| !names.contains(key)) {
+                        return Ok(());
+                    }
+                    kwargs.set_item(key, value).map_err(|e| {
+                        TestypfError::RenderFailed(format!("Failed to set {key}: {e}"))
+                    })
+                };
+                if raster.antialias != defaults.antialias {
+                    set_kwarg("antialias", raster.antialias.typf_name().into_py(py))?;
+                }
+                if raster.hinting != defaults.hinting {
+                    set_kwarg("hinting", raster.hinting.typf_name().into_py(py))?;
+                }
+                if raster.gamma != defaults.gamma {
+                    set_kwarg("gamma", raster.gamma.into_py(py))?;
+                }
+                if settings.glyph_positions {
+                    set_kwarg("return_glyphs", true.into_py(py))?;
+                }
+
                 // Call render_text method
                 let result = typf_instance
-                    .call_method1(
+                    .call_method_bound(
                         py,
                         "render_text",
                         (
-                            settings.sample_text.as_str(),
+                            text.as_str(),
                             font_path_str.as_ref(),
-                            settings.font_size,
+                            settings.device_font_size(),
                             color,
                             background,
-                            settings.padding,
+                            settings.device_padding(),
                             if variations.is_empty() {
                                 None
                             } else {
                                 Some(variations)
                             },
                         ),
+                        (!kwargs.is_empty()).then_some(&kwargs),
                     )
                     .map_err(|e| {
                         TestypfError::RenderFailed(format!("Failed to render text: {}", e))
@@ -798,13 +1452,60 @@ pub mod render {
                         TestypfError::RenderFailed(format!("Invalid height in typf result: {e}"))
                     })?;
 
-                let format: String = dict
+                let format_name: String = dict
                     .get_item("format")
                     .ok()
                     .flatten()
                     .and_then(|item| item.extract().ok())
                     .unwrap_or_else(|| "unknown".to_string());
 
+                // Optional per-glyph placements; missing or unreadable data leaves the list empty
+                let glyphs = dict
+                    .get_item("glyphs")
+                    .ok()
+                    .flatten()
+                    .and_then(|item| {
+                        py.import_bound("json")
+                            .ok()?
+                            .call_method1("dumps", (item,))
+                            .ok()?
+                            .extract::<String>()
+                            .ok()
+                    })
+                    .and_then(|json| crate::overlay::parse_glyph_placements(&json).ok())
+                    .unwrap_or_default();
+                // Baseline reported by typf, else the pen position of the first glyph
+                let baseline = dict
+                    .get_item("baseline")
+                    .ok()
+                    .flatten()
+                    .and_then(|item| item.extract::<f32>().ok())
+                    .or_else(|| glyphs.first().map(|glyph| glyph.y));
+
+                if format_name.eq_ignore_ascii_case("svg") {
+                    let markup: String = dict
+                        .get_item("data")
+                        .ok()
+                        .flatten()
+                        .and_then(|item| {
+                            item.extract::<String>().ok().or_else(|| {
+                                item.downcast::<PyBytes>()
+                                    .ok()
+                                    .map(|b| String::from_utf8_lossy(b.as_bytes()).into_owned())
+                            })
+                        })
+                        .ok_or_else(|| {
+                            TestypfError::RenderFailed(
+                                "Missing SVG data in typf result".to_string(),
+                            )
+                        })?;
+                    return crate::vector::svg_result(markup).map(|svg| RenderResult {
+                        glyphs,
+                        baseline,
+                        ..svg
+                    });
+                }
+
                 let data_obj = dict
                     .get_item("data")
                     .map_err(|e| TestypfError::RenderFailed(format!("Failed to read data: {e}")))?;
@@ -821,22 +1522,32 @@ pub mod render {
                     .as_bytes()
                     .to_vec();
 
+                let format = PixelFormat::from_typf(&format_name, width, height, data.len());
                 return Ok(RenderResult {
                     width,
                     height,
-                    data,
+                    data: data.into(),
                     format,
+                    glyphs,
+                    baseline,
                 });
             }
 
-            // JSON renderer returns a string
+            // JSON and SVG renderers return a string
             if let Ok(json_str) = result.downcast_bound::<PyString>(py) {
-                let data = json_str.to_string_lossy().as_bytes().to_vec();
+                let text = json_str.to_string_lossy();
+                if crate::vector::looks_like_svg(&text) {
+                    return crate::vector::svg_result(text.into_owned());
+                }
+
+                let data = text.as_bytes().to_vec();
                 return Ok(RenderResult {
                     width: 0,
                     height: 0,
-                    data,
-                    format: "json".to_string(),
+                    data: data.into(),
+                    format: PixelFormat::Json,
+                    glyphs: Vec::new(),
+                    baseline: None,
                 });
             }
 
@@ -860,6 +1571,7 @@ pub mod render {
             vec![
                 RendererBackend::Orge,
                 RendererBackend::Json,
+                RendererBackend::Svg,
                 #[cfg(target_os = "macos")]
                 RendererBackend::CoreGraphics,
                 #[cfg(feature = "render-skia")]
@@ -870,12 +1582,71 @@ pub mod render {
         }
 
         fn set_backend(&mut self, backend: RendererBackend) -> TestypfResult<()> {
-            self.backend = self.backend_to_string(&backend).to_string();
+            self.backend = backend;
             Ok(())
         }
+
+        fn backend(&self) -> RendererBackend {
+            self.backend.clone()
+        }
+
+        fn shaper(&self) -> &str {
+            &self.shaper
+        }
     }
 }
 
+/// Vector (SVG) render output handling
+pub mod vector;
+
+/// Pixel format conversion to straight-alpha RGBA8
+pub mod pixels;
+
+/// Image export with embedded render provenance
+pub mod export;
+
+/// Contact sheet compositor that tiles renders into one image
+pub mod contact_sheet;
+
+/// Repeatable render benchmarks with timing statistics
+pub mod bench;
+
+/// Pixel comparison of two renders with tolerance, scores and a diff bitmap
+pub mod diff;
+
+/// Golden-image regression suite runner
+pub mod snapshot;
+
+/// Shaping regression tests against stored glyph runs
+pub mod shaping;
+
+/// Per-glyph placement data and debug overlays drawn over renders
+pub mod overlay;
+
+/// Font table directory and decoded head/hhea/OS2/post/maxp/name summaries
+pub mod tables;
+
+/// cmap character coverage summarized by Unicode block and script
+pub mod coverage;
+
+/// Language support from bundled CLDR exemplar character sets
+pub mod languages;
+
+/// Built-in and custom character sets for coverage matrices
+pub mod charsets;
+
+/// Codepoint and glyph-name escapes in sample text
+pub mod escapes;
+
+/// Unicode normalization of sample text
+pub mod normalization;
+
+/// Control strings and GPOS kern pairs for spacing review
+pub mod proofs;
+
+/// Built-in sample text library with per-script suggestions
+pub mod samples;
+
 /// Font discovery module using typg
 pub mod discovery {
     use super::*;
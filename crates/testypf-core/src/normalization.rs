@@ -0,0 +1,95 @@
+//! Unicode normalization of sample text and the NFC vs NFD render comparison
+
+use super::*;
+use crate::diff::{DiffStyle, PixelDiff};
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalization form applied to sample text after escape decoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Normalization {
+    /// Text is shaped exactly as typed
+    #[default]
+    None,
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+impl Normalization {
+    pub const ALL: [Normalization; 5] = [
+        Normalization::None,
+        Normalization::Nfc,
+        Normalization::Nfd,
+        Normalization::Nfkc,
+        Normalization::Nfkd,
+    ];
+
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Normalization::None => text.to_string(),
+            Normalization::Nfc => text.nfc().collect(),
+            Normalization::Nfd => text.nfd().collect(),
+            Normalization::Nfkc => text.nfkc().collect(),
+            Normalization::Nfkd => text.nfkd().collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for Normalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Normalization::None => write!(f, "No normalization"),
+            Normalization::Nfc => write!(f, "NFC"),
+            Normalization::Nfd => write!(f, "NFD"),
+            Normalization::Nfkc => write!(f, "NFKC"),
+            Normalization::Nfkd => write!(f, "NFKD"),
+        }
+    }
+}
+
+/// The same text rendered composed and decomposed, with their pixel diff
+#[derive(Debug, Clone)]
+pub struct NormalizationComparison {
+    /// Decoded text in NFC and NFD; equal when the text has nothing to (de)compose
+    pub nfc_text: String,
+    pub nfd_text: String,
+    pub nfc: RenderResult,
+    pub nfd: RenderResult,
+    pub diff: PixelDiff,
+}
+
+impl NormalizationComparison {
+    /// Whether NFC and NFD produced different codepoint sequences at all
+    pub fn forms_differ(&self) -> bool {
+        self.nfc_text != self.nfd_text
+    }
+}
+
+/// Render `settings` once as NFC and once as NFD and pixel-compare the two.
+///
+/// The renderer's backend is left untouched; `settings.normalization` is overridden for both renders.
+pub fn compare_nfc_nfd(
+    renderer: &dyn TextRenderer,
+    font_path: &PathBuf,
+    settings: &RenderSettings,
+    tolerance: u8,
+    style: DiffStyle,
+) -> TestypfResult<NormalizationComparison> {
+    let with = |normalization| RenderSettings {
+        normalization,
+        ..settings.clone()
+    };
+    let nfc_settings = with(Normalization::Nfc);
+    let nfd_settings = with(Normalization::Nfd);
+    let nfc = renderer.render_text(font_path, &nfc_settings)?;
+    let nfd = renderer.render_text(font_path, &nfd_settings)?;
+    let diff = crate::diff::compare(&nfc, &nfd, tolerance, style)?;
+    Ok(NormalizationComparison {
+        nfc_text: nfc_settings.text_for_font(font_path),
+        nfd_text: nfd_settings.text_for_font(font_path),
+        nfc,
+        nfd,
+        diff,
+    })
+}
@@ -0,0 +1,519 @@
+use super::*;
+use crate::shaping::{
+    BOUNDS_KEYS, CLUSTER_KEYS, GLYPH_ID_KEYS, X_ADVANCE_KEYS, X_KEYS, Y_ADVANCE_KEYS, Y_KEYS,
+};
+
+const BOX_COLOR: [u8; 4] = [40, 110, 230, 255];
+const ORIGIN_COLOR: [u8; 4] = [220, 40, 40, 255];
+const ADVANCE_COLOR: [u8; 4] = [30, 160, 70, 255];
+const CLUSTER_COLOR: [u8; 4] = [200, 60, 200, 255];
+const NOTDEF_COLOR: [u8; 4] = [240, 50, 30, 255];
+
+/// Ink rectangle of a glyph in device pixels (y down)
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InkBounds {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Where one glyph landed in a rendered bitmap, in device pixels from the top-left corner
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GlyphPlacement {
+    pub glyph_id: u32,
+    pub cluster: u32,
+
+    /// Pen position on the baseline where the glyph was drawn
+    pub x: f32,
+    pub y: f32,
+
+    pub x_advance: f32,
+    pub y_advance: f32,
+
+    /// Ink bounds; `None` for blank glyphs such as spaces
+    pub ink: Option<InkBounds>,
+}
+
+impl GlyphPlacement {
+    /// Whether every coordinate, including the ink bounds, is finite
+    pub fn is_finite(&self) -> bool {
+        let ink = self.ink.map_or(true, |ink| {
+            [ink.x, ink.y, ink.width, ink.height]
+                .iter()
+                .all(|v| v.is_finite())
+        });
+        ink && [self.x, self.y, self.x_advance, self.y_advance]
+            .iter()
+            .all(|v| v.is_finite())
+    }
+
+    /// Copy with every coordinate multiplied by `factor`
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self {
+            x: self.x * factor,
+            y: self.y * factor,
+            x_advance: self.x_advance * factor,
+            y_advance: self.y_advance * factor,
+            ink: self.ink.map(|ink| InkBounds {
+                x: ink.x * factor,
+                y: ink.y * factor,
+                width: ink.width * factor,
+                height: ink.height * factor,
+            }),
+            ..self.clone()
+        }
+    }
+}
+
+/// Parse per-glyph placements reported by typf next to the pixels.
+///
+/// Accepts an array of glyph objects (or an object holding one under `"glyphs"`). Ink bounds may be an
+/// `[x_min, y_min, x_max, y_max]` array or an object with `x`/`y`/`width`/`height` or `x_min`/`y_min`/`x_max`/`y_max`.
+/// Glyphs with coordinates that do not fit an `f32` are skipped.
+pub fn parse_glyph_placements(json: &str) -> TestypfResult<Vec<GlyphPlacement>> {
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| TestypfError::RenderFailed(format!("Invalid glyph placement data: {e}")))?;
+    let glyphs = match &value {
+        serde_json::Value::Array(items) => items,
+        serde_json::Value::Object(map) => match map.get("glyphs") {
+            Some(serde_json::Value::Array(items)) => items,
+            _ => {
+                return Err(TestypfError::RenderFailed(
+                    "Glyph placement data has no glyph array".to_string(),
+                ))
+            }
+        },
+        _ => {
+            return Err(TestypfError::RenderFailed(
+                "Glyph placement data must be an array".to_string(),
+            ))
+        }
+    };
+
+    let mut placements = Vec::with_capacity(glyphs.len());
+    for (index, glyph) in glyphs.iter().enumerate() {
+        let number = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| glyph.get(*key).and_then(serde_json::Value::as_f64))
+                .map(|v| v as f32)
+        };
+        let glyph_id = number(GLYPH_ID_KEYS).ok_or_else(|| {
+            TestypfError::RenderFailed(format!("Glyph {index} has no numeric glyph ID"))
+        })?;
+        let placement = GlyphPlacement {
+            glyph_id: glyph_id as u32,
+            cluster: number(CLUSTER_KEYS).unwrap_or(0.0) as u32,
+            x: number(X_KEYS).unwrap_or(0.0),
+            y: number(Y_KEYS).unwrap_or(0.0),
+            x_advance: number(X_ADVANCE_KEYS).unwrap_or(0.0),
+            y_advance: number(Y_ADVANCE_KEYS).unwrap_or(0.0),
+            ink: BOUNDS_KEYS
+                .iter()
+                .find_map(|key| glyph.get(*key))
+                .and_then(parse_bounds),
+        };
+        // Coordinates beyond f32 range cannot be drawn
+        if placement.is_finite() {
+            placements.push(placement);
+        }
+    }
+    Ok(placements)
+}
+
+fn parse_bounds(value: &serde_json::Value) -> Option<InkBounds> {
+    let corners = |x_min: f64, y_min: f64, x_max: f64, y_max: f64| InkBounds {
+        x: x_min as f32,
+        y: y_min as f32,
+        width: (x_max - x_min) as f32,
+        height: (y_max - y_min) as f32,
+    };
+    match value {
+        serde_json::Value::Array(items) if items.len() == 4 => {
+            let v: Vec<f64> = items.iter().filter_map(serde_json::Value::as_f64).collect();
+            (v.len() == 4).then(|| corners(v[0], v[1], v[2], v[3]))
+        }
+        serde_json::Value::Object(_) => {
+            let get = |key: &str| value.get(key).and_then(serde_json::Value::as_f64);
+            if let (Some(x), Some(y), Some(w), Some(h)) =
+                (get("x"), get("y"), get("width"), get("height"))
+            {
+                Some(InkBounds {
+                    x: x as f32,
+                    y: y as f32,
+                    width: w as f32,
+                    height: h as f32,
+                })
+            } else {
+                Some(corners(
+                    get("x_min")?,
+                    get("y_min")?,
+                    get("x_max")?,
+                    get("y_max")?,
+                ))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// One kind of mark drawn over a preview
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverlayLayer {
+    /// Ink bounding boxes
+    Boxes,
+    /// Pen positions on the baseline
+    Origins,
+    /// Advance vectors along the baseline
+    Advances,
+    /// Vertical lines where a new cluster starts
+    Clusters,
+    /// Tinted cells over glyphs that fell back to .notdef (glyph 0)
+    Notdef,
+}
+
+impl OverlayLayer {
+    pub const ALL: [OverlayLayer; 5] = [
+        OverlayLayer::Boxes,
+        OverlayLayer::Origins,
+        OverlayLayer::Advances,
+        OverlayLayer::Clusters,
+        OverlayLayer::Notdef,
+    ];
+}
+
+impl std::fmt::Display for OverlayLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverlayLayer::Boxes => write!(f, "Glyph boxes"),
+            OverlayLayer::Origins => write!(f, "Origins"),
+            OverlayLayer::Advances => write!(f, "Advances"),
+            OverlayLayer::Clusters => write!(f, "Clusters"),
+            OverlayLayer::Notdef => write!(f, ".notdef"),
+        }
+    }
+}
+
+/// Which overlay layers to draw
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OverlayOptions {
+    pub boxes: bool,
+    pub origins: bool,
+    pub advances: bool,
+    pub clusters: bool,
+    pub notdef: bool,
+}
+
+impl OverlayOptions {
+    pub fn any(&self) -> bool {
+        self.boxes || self.origins || self.advances || self.clusters || self.notdef
+    }
+
+    pub fn get(&self, layer: OverlayLayer) -> bool {
+        match layer {
+            OverlayLayer::Boxes => self.boxes,
+            OverlayLayer::Origins => self.origins,
+            OverlayLayer::Advances => self.advances,
+            OverlayLayer::Clusters => self.clusters,
+            OverlayLayer::Notdef => self.notdef,
+        }
+    }
+
+    pub fn set(&mut self, layer: OverlayLayer, enabled: bool) {
+        match layer {
+            OverlayLayer::Boxes => self.boxes = enabled,
+            OverlayLayer::Origins => self.origins = enabled,
+            OverlayLayer::Advances => self.advances = enabled,
+            OverlayLayer::Clusters => self.clusters = enabled,
+            OverlayLayer::Notdef => self.notdef = enabled,
+        }
+    }
+}
+
+/// Vertical metric drawn as a horizontal guide across a preview
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GuideLine {
+    Baseline,
+    XHeight,
+    CapHeight,
+    /// hhea ascender
+    Ascender,
+    /// hhea descender
+    Descender,
+    /// usWinAscent and usWinDescent, where Windows clips glyphs
+    WinClip,
+}
+
+impl GuideLine {
+    pub const ALL: [GuideLine; 6] = [
+        GuideLine::Baseline,
+        GuideLine::XHeight,
+        GuideLine::CapHeight,
+        GuideLine::Ascender,
+        GuideLine::Descender,
+        GuideLine::WinClip,
+    ];
+
+    /// Heights above the baseline in font units; empty when the font does not define the metric
+    pub fn heights(&self, metrics: &TestypfVerticalMetrics) -> Vec<f32> {
+        match self {
+            GuideLine::Baseline => vec![0.0],
+            GuideLine::XHeight => metrics.x_height.map(f32::from).into_iter().collect(),
+            GuideLine::CapHeight => metrics.cap_height.map(f32::from).into_iter().collect(),
+            GuideLine::Ascender => vec![f32::from(metrics.hhea_ascender)],
+            GuideLine::Descender => vec![f32::from(metrics.hhea_descender)],
+            GuideLine::WinClip => vec![
+                f32::from(metrics.win_ascent),
+                -f32::from(metrics.win_descent),
+            ],
+        }
+    }
+
+    fn color(&self) -> [u8; 4] {
+        match self {
+            GuideLine::Baseline => [220, 40, 40, 255],
+            GuideLine::XHeight => [30, 160, 70, 255],
+            GuideLine::CapHeight => [40, 110, 230, 255],
+            GuideLine::Ascender => [230, 140, 20, 255],
+            GuideLine::Descender => [140, 70, 200, 255],
+            GuideLine::WinClip => [120, 120, 120, 255],
+        }
+    }
+}
+
+impl std::fmt::Display for GuideLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuideLine::Baseline => write!(f, "Baseline"),
+            GuideLine::XHeight => write!(f, "x-height"),
+            GuideLine::CapHeight => write!(f, "Cap height"),
+            GuideLine::Ascender => write!(f, "Ascender"),
+            GuideLine::Descender => write!(f, "Descender"),
+            GuideLine::WinClip => write!(f, "Win clip"),
+        }
+    }
+}
+
+/// Baseline estimate for renderers that do not report one: padding plus the hhea ascender.
+pub fn estimate_baseline(metrics: &TestypfVerticalMetrics, settings: &RenderSettings) -> f32 {
+    settings.device_padding() as f32
+        + metrics.to_pixels(
+            f32::from(metrics.hhea_ascender),
+            settings.device_font_size(),
+        )
+}
+
+/// Device-pixel rows of the requested guides, given the baseline row and the font size in device pixels
+pub fn guide_rows(
+    metrics: &TestypfVerticalMetrics,
+    baseline: f32,
+    pixel_size: f32,
+    guides: &[GuideLine],
+) -> Vec<(GuideLine, f32)> {
+    guides
+        .iter()
+        .flat_map(|&guide| {
+            guide
+                .heights(metrics)
+                .into_iter()
+                .map(move |height| (guide, baseline - metrics.to_pixels(height, pixel_size)))
+        })
+        .collect()
+}
+
+/// Draw full-width guide lines over a copy of `image` (converted to straight RGBA8).
+///
+/// Win clip lines are dashed; rows outside the image are skipped.
+pub fn draw_guides(image: &RenderResult, rows: &[(GuideLine, f32)]) -> TestypfResult<RenderResult> {
+    let base = if image.is_vector() {
+        crate::vector::rasterize(image, 1.0)?
+    } else {
+        image.to_rgba8()?
+    };
+    let mut canvas = Marks {
+        width: base.width as i64,
+        height: base.height as i64,
+        data: base.data.to_vec(),
+    };
+
+    for &(guide, row) in rows {
+        let y = row.round() as i64;
+        let dashed = guide == GuideLine::WinClip;
+        for x in (0..canvas.width).filter(|x| !dashed || x % 6 < 3) {
+            canvas.put(x, y, guide.color());
+        }
+    }
+
+    Ok(RenderResult {
+        data: canvas.data.into(),
+        ..base
+    })
+}
+
+/// Draw the selected glyph layers over a copy of `image` (converted to straight RGBA8).
+///
+/// Marks are one device pixel wide and clipped to the image; the source render is left untouched.
+pub fn draw_overlay(image: &RenderResult, options: &OverlayOptions) -> TestypfResult<RenderResult> {
+    let base = if image.is_vector() {
+        crate::vector::rasterize(image, 1.0)?
+    } else {
+        image.to_rgba8()?
+    };
+    let mut canvas = Marks {
+        width: base.width as i64,
+        height: base.height as i64,
+        data: base.data.to_vec(),
+    };
+
+    for (index, glyph) in base.glyphs.iter().enumerate() {
+        let (x, y) = (glyph.x.round() as i64, glyph.y.round() as i64);
+
+        if options.notdef && glyph.glyph_id == 0 {
+            // Ink box when known, otherwise the glyph's advance cell over the full height
+            let (x0, y0, x1, y1) = match glyph.ink {
+                Some(ink) => (
+                    ink.x.floor() as i64,
+                    ink.y.floor() as i64,
+                    (ink.x + ink.width).ceil() as i64 - 1,
+                    (ink.y + ink.height).ceil() as i64 - 1,
+                ),
+                None => (
+                    x,
+                    0,
+                    (glyph.x + glyph.x_advance.max(1.0)).round() as i64 - 1,
+                    canvas.height - 1,
+                ),
+            };
+            canvas.tint(x0, y0, x1, y1, NOTDEF_COLOR, 0.4);
+        }
+
+        if options.clusters {
+            let starts_cluster = index == 0 || base.glyphs[index - 1].cluster != glyph.cluster;
+            if starts_cluster {
+                // Dashed so it stays distinguishable from box edges
+                for row in (0..canvas.height).filter(|row| row % 4 < 2) {
+                    canvas.put(x, row, CLUSTER_COLOR);
+                }
+            }
+        }
+
+        if options.boxes {
+            if let Some(ink) = glyph.ink {
+                let x0 = ink.x.floor() as i64;
+                let y0 = ink.y.floor() as i64;
+                let x1 = (ink.x + ink.width).ceil() as i64 - 1;
+                let y1 = (ink.y + ink.height).ceil() as i64 - 1;
+                canvas.line(x0, y0, x1, y0, BOX_COLOR);
+                canvas.line(x0, y1, x1, y1, BOX_COLOR);
+                canvas.line(x0, y0, x0, y1, BOX_COLOR);
+                canvas.line(x1, y0, x1, y1, BOX_COLOR);
+            }
+        }
+
+        if options.advances {
+            let end_x = (glyph.x + glyph.x_advance).round() as i64;
+            let end_y = (glyph.y - glyph.y_advance).round() as i64;
+            canvas.line(x, y, end_x, end_y, ADVANCE_COLOR);
+            canvas.line(end_x, end_y - 2, end_x, end_y + 2, ADVANCE_COLOR);
+        }
+
+        if options.origins {
+            canvas.line(x - 2, y, x + 2, y, ORIGIN_COLOR);
+            canvas.line(x, y - 2, x, y + 2, ORIGIN_COLOR);
+        }
+    }
+
+    Ok(RenderResult {
+        data: canvas.data.into(),
+        ..base
+    })
+}
+
+/// RGBA8 buffer with clipped pixel and line drawing
+struct Marks {
+    width: i64,
+    height: i64,
+    data: Vec<u8>,
+}
+
+impl Marks {
+    fn put(&mut self, x: i64, y: i64, color: [u8; 4]) {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return;
+        }
+        let offset = ((y * self.width + x) * 4) as usize;
+        self.data[offset..offset + 4].copy_from_slice(&color);
+    }
+
+    /// Blend `color` over a rectangle (inclusive corners) with the given opacity
+    fn tint(&mut self, x0: i64, y0: i64, x1: i64, y1: i64, color: [u8; 4], opacity: f32) {
+        for y in y0.max(0)..=y1.min(self.height - 1) {
+            for x in x0.max(0)..=x1.min(self.width - 1) {
+                let offset = ((y * self.width + x) * 4) as usize;
+                for (channel, &target) in self.data[offset..offset + 3].iter_mut().zip(&color) {
+                    *channel =
+                        (*channel as f32 * (1.0 - opacity) + target as f32 * opacity).round() as u8;
+                }
+                self.data[offset + 3] = self.data[offset + 3].max((opacity * 255.0).round() as u8);
+            }
+        }
+    }
+
+    /// Straight line between two points, inclusive (Bresenham), clipped to the canvas first
+    fn line(&mut self, x0: i64, y0: i64, x1: i64, y1: i64, color: [u8; 4]) {
+        let Some((x0, y0, x1, y1)) = self.clip(x0, y0, x1, y1) else {
+            return;
+        };
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+        let (mut x, mut y, mut err) = (x0, y0, dx + dy);
+        loop {
+            self.put(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Segment endpoints moved onto the canvas (Liang–Barsky), `None` when it misses entirely
+    fn clip(&self, x0: i64, y0: i64, x1: i64, y1: i64) -> Option<(i64, i64, i64, i64)> {
+        if self.width <= 0 || self.height <= 0 {
+            return None;
+        }
+        let (x0, y0, x1, y1) = (x0 as f64, y0 as f64, x1 as f64, y1 as f64);
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let (max_x, max_y) = ((self.width - 1) as f64, (self.height - 1) as f64);
+        let (mut t0, mut t1) = (0.0f64, 1.0f64);
+        for (p, q) in [(-dx, x0), (dx, max_x - x0), (-dy, y0), (dy, max_y - y0)] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else if p < 0.0 {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+        if t0 > t1 {
+            return None;
+        }
+        let point = |t: f64| {
+            (
+                (x0 + t * dx).round().clamp(0.0, max_x) as i64,
+                (y0 + t * dy).round().clamp(0.0, max_y) as i64,
+            )
+        };
+        let ((cx0, cy0), (cx1, cy1)) = (point(t0), point(t1));
+        Some((cx0, cy0, cx1, cy1))
+    }
+}
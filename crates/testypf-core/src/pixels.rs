@@ -0,0 +1,143 @@
+use super::*;
+use std::sync::Arc;
+
+/// Reference-counted, immutable byte buffer shared by render results, previews and image handles
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct PixelBuffer(Arc<Vec<u8>>);
+
+impl PixelBuffer {
+    /// Size of the buffer in bytes
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether two buffers share the same allocation
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Address of the shared allocation, for de-duplicating memory accounting
+    pub fn id(&self) -> usize {
+        Arc::as_ptr(&self.0) as usize
+    }
+}
+
+impl std::ops::Deref for PixelBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for PixelBuffer {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for PixelBuffer {
+    fn from(data: Vec<u8>) -> Self {
+        Self(Arc::new(data))
+    }
+}
+
+impl std::fmt::Debug for PixelBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PixelBuffer({} bytes)", self.len())
+    }
+}
+
+/// Total bytes held by a set of buffers, counting shared allocations once
+pub fn unique_bytes<'a>(buffers: impl IntoIterator<Item = &'a PixelBuffer>) -> usize {
+    let mut seen = std::collections::HashSet::new();
+    buffers
+        .into_iter()
+        .filter(|buffer| seen.insert(buffer.id()))
+        .map(PixelBuffer::len)
+        .sum()
+}
+
+/// Convert a bitmap RenderResult of any [`PixelFormat`] into straight-alpha RGBA8
+pub fn to_rgba8(result: &RenderResult) -> TestypfResult<RenderResult> {
+    let bytes_per_pixel = result.format.bytes_per_pixel().ok_or_else(|| {
+        TestypfError::RenderFailed(format!("{} output has no pixels to convert", result.format))
+    })?;
+
+    let expected_len = (result.width as usize)
+        .saturating_mul(result.height as usize)
+        .saturating_mul(bytes_per_pixel);
+    if expected_len == 0 {
+        return Err(TestypfError::RenderFailed(
+            "Render result contains no pixel data".to_string(),
+        ));
+    }
+    if result.data.len() != expected_len {
+        return Err(TestypfError::RenderFailed(format!(
+            "Pixel data length mismatch for {} (expected {}, got {})",
+            result.format,
+            expected_len,
+            result.data.len()
+        )));
+    }
+
+    let data: Vec<u8> = match result.format {
+        // Already straight RGBA: share the buffer instead of copying it
+        PixelFormat::Rgba8 => return Ok(result.clone()),
+        PixelFormat::Bgra8 => result
+            .data
+            .chunks_exact(4)
+            .flat_map(|px| [px[2], px[1], px[0], px[3]])
+            .collect(),
+        PixelFormat::Rgba8Premultiplied => result
+            .data
+            .chunks_exact(4)
+            .flat_map(|px| demultiply([px[0], px[1], px[2], px[3]]))
+            .collect(),
+        PixelFormat::Bgra8Premultiplied => result
+            .data
+            .chunks_exact(4)
+            .flat_map(|px| demultiply([px[2], px[1], px[0], px[3]]))
+            .collect(),
+        PixelFormat::Gray8 => result.data.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        PixelFormat::GrayAlpha8 => result
+            .data
+            .chunks_exact(2)
+            .flat_map(|px| [px[0], px[0], px[0], px[1]])
+            .collect(),
+        PixelFormat::LcdRgb8 => result
+            .data
+            .chunks_exact(3)
+            .flat_map(|px| [px[0], px[1], px[2], 255])
+            .collect(),
+        PixelFormat::LcdBgr8 => result
+            .data
+            .chunks_exact(3)
+            .flat_map(|px| [px[2], px[1], px[0], 255])
+            .collect(),
+        PixelFormat::Svg | PixelFormat::Json => unreachable!("rejected above"),
+    };
+
+    Ok(RenderResult {
+        width: result.width,
+        height: result.height,
+        data: data.into(),
+        format: PixelFormat::Rgba8,
+        glyphs: result.glyphs.clone(),
+        baseline: result.baseline,
+    })
+}
+
+/// Undo alpha premultiplication of one RGBA pixel
+pub fn demultiply(px: [u8; 4]) -> [u8; 4] {
+    let alpha = px[3] as u32;
+    if alpha == 0 {
+        return [0, 0, 0, 0];
+    }
+    let channel = |c: u8| ((c as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+    [channel(px[0]), channel(px[1]), channel(px[2]), px[3]]
+}
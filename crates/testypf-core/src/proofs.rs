@@ -0,0 +1,412 @@
+//! Spacing proofs generated from a font's cmap and GPOS kerning
+//!
+//! Control strings set each letter between its script's straight and round controls
+//! (`HOHxHOH`, `nonxnon`); caseless letters such as Arabic, Hebrew or Han take the lowercase
+//! controls, which are Latin for scripts without their own. Kern pairs come from the pair adjustment lookups of the `kern`
+//! feature: glyph pairs (format 1) and class pairs (format 2) are listed separately, largest
+//! adjustment first. Proof text uses the sample-text escape syntax and can go straight into
+//! `RenderSettings::sample_text`; glyphs without a codepoint are named as `/glyphname`, which
+//! the escape decoder keeps and reports, so such pairs stay visible in the list.
+
+use super::*;
+use read_fonts::tables::cmap::CmapIterLimits;
+use read_fonts::tables::gpos::{
+    PairPos, PairPosFormat1, PairPosFormat2, PositionSubtables, ValueRecord,
+};
+use read_fonts::tables::post::Post;
+use read_fonts::types::{GlyphId16, Tag};
+use read_fonts::{FontRef, TableProvider};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use unicode_script::{Script, UnicodeScript};
+
+/// Straight and round controls as (script, uppercase, lowercase)
+const CONTROLS: &[(Script, [char; 2], [char; 2])] = &[
+    (Script::Latin, ['H', 'O'], ['n', 'o']),
+    (Script::Greek, ['Η', 'Ο'], ['η', 'ο']),
+    (Script::Cyrillic, ['Н', 'О'], ['п', 'о']),
+];
+
+/// Part of a spacing proof
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ProofSection {
+    /// `HOHxHOH` / `nonxnon` for every letter
+    #[default]
+    Controls,
+    /// Glyph pairs from pair adjustment format 1
+    KernPairs,
+    /// One representative pair per class pair from pair adjustment format 2
+    ClassPairs,
+}
+
+impl ProofSection {
+    pub const ALL: [ProofSection; 3] = [
+        ProofSection::Controls,
+        ProofSection::KernPairs,
+        ProofSection::ClassPairs,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProofSection::Controls => "Control strings",
+            ProofSection::KernPairs => "Kern pairs",
+            ProofSection::ClassPairs => "Class kern pairs",
+        }
+    }
+}
+
+impl std::fmt::Display for ProofSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// One letter set between its controls
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ControlString {
+    pub letter: char,
+    pub text: String,
+}
+
+/// A kerned pair read from GPOS
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct KernPair {
+    /// The two glyphs in sample-text escape syntax
+    pub text: String,
+    pub left_glyph: u16,
+    pub right_glyph: u16,
+    /// Change to the gap between the glyphs in font units (first x advance plus second x placement)
+    pub value: i32,
+    /// First and second class of a class pair, whose glyphs are then the lowest encoded members
+    pub classes: Option<(u16, u16)>,
+}
+
+impl std::fmt::Display for KernPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:+}", self.text, self.value)
+    }
+}
+
+/// Control strings and kern pairs of one font
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SpacingProof {
+    /// In codepoint order
+    pub controls: Vec<ControlString>,
+    /// Largest adjustment first; a pair kerned by several subtables keeps the first one
+    pub kern_pairs: Vec<KernPair>,
+    /// Largest adjustment first
+    pub class_pairs: Vec<KernPair>,
+    /// Pairs left out because a glyph has neither a codepoint nor a `post` name
+    pub unreachable_pairs: usize,
+    /// Letters left out because the font maps neither their script's controls nor Latin ones
+    #[serde(default)]
+    pub letters_without_controls: usize,
+}
+
+impl SpacingProof {
+    /// Build the proof from the cmap, `post` names and the `kern` feature's GPOS lookups
+    pub fn from_font(font: &FontRef) -> Self {
+        let glyphs = GlyphText::from_font(font);
+        let (controls, letters_without_controls) = control_strings(&glyphs);
+        let mut proof = Self {
+            controls,
+            letters_without_controls,
+            ..Self::default()
+        };
+        proof.collect_kerning(font, &glyphs);
+        for pairs in [&mut proof.kern_pairs, &mut proof.class_pairs] {
+            pairs.sort_by(|a, b| {
+                b.value
+                    .abs()
+                    .cmp(&a.value.abs())
+                    .then(a.value.cmp(&b.value))
+                    .then(a.text.cmp(&b.text))
+            });
+        }
+        proof
+    }
+
+    /// Parse one face of a font file and build its proof
+    pub fn read(data: &[u8], face_index: u32) -> TestypfResult<Self> {
+        let font = FontRef::from_index(data, face_index)
+            .map_err(|e| TestypfError::InvalidFont(format!("Failed to parse font: {}", e)))?;
+        Ok(Self::from_font(&font))
+    }
+
+    /// Number of strings in a section
+    pub fn count(&self, section: ProofSection) -> usize {
+        match section {
+            ProofSection::Controls => self.controls.len(),
+            ProofSection::KernPairs => self.kern_pairs.len(),
+            ProofSection::ClassPairs => self.class_pairs.len(),
+        }
+    }
+
+    /// The section's strings, one per paragraph line
+    pub fn lines(&self, section: ProofSection) -> Vec<String> {
+        match section {
+            ProofSection::Controls => self.controls.iter().map(|c| c.text.clone()).collect(),
+            ProofSection::KernPairs => self.kern_pairs.iter().map(|p| p.text.clone()).collect(),
+            ProofSection::ClassPairs => self.class_pairs.iter().map(|p| p.text.clone()).collect(),
+        }
+    }
+
+    /// The first `limit` strings of a section (all with `None`), space-separated for a sample text
+    pub fn text(&self, section: ProofSection, limit: Option<usize>) -> String {
+        let mut lines = self.lines(section);
+        lines.truncate(limit.unwrap_or(usize::MAX));
+        lines.join(" ")
+    }
+
+    fn collect_kerning(&mut self, font: &FontRef, glyphs: &GlyphText) {
+        let Ok(gpos) = font.gpos() else {
+            return;
+        };
+        let (Ok(features), Ok(lookups)) = (gpos.feature_list(), gpos.lookup_list()) else {
+            return;
+        };
+        let kern = Tag::new(b"kern");
+        let indices: BTreeSet<u16> = features
+            .feature_records()
+            .iter()
+            .filter(|record| record.feature_tag() == kern)
+            .filter_map(|record| record.feature(features.offset_data()).ok())
+            .flat_map(|feature| {
+                feature
+                    .lookup_list_indices()
+                    .iter()
+                    .map(|index| index.get())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let mut seen: HashSet<(u16, u16)> = HashSet::new();
+        for index in indices {
+            let Ok(lookup) = lookups.lookups().get(usize::from(index)) else {
+                continue;
+            };
+            let Ok(PositionSubtables::Pair(subtables)) = lookup.subtables() else {
+                continue;
+            };
+            for subtable in subtables.iter().flatten() {
+                match subtable {
+                    PairPos::Format1(table) => self.add_glyph_pairs(&table, glyphs, &mut seen),
+                    PairPos::Format2(table) => self.add_class_pairs(&table, glyphs),
+                }
+            }
+        }
+    }
+
+    fn add_glyph_pairs(
+        &mut self,
+        table: &PairPosFormat1,
+        glyphs: &GlyphText,
+        seen: &mut HashSet<(u16, u16)>,
+    ) {
+        let Ok(coverage) = table.coverage() else {
+            return;
+        };
+        for (first, pair_set) in coverage.iter().zip(table.pair_sets().iter()) {
+            let Ok(pair_set) = pair_set else {
+                continue;
+            };
+            for record in pair_set.pair_value_records().iter().flatten() {
+                let second = record.second_glyph();
+                let value = gap_adjustment(record.value_record1(), record.value_record2());
+                if !seen.insert((first.to_u16(), second.to_u16())) || value == 0 {
+                    continue;
+                }
+                match glyphs.pair_text(first, second) {
+                    Some(text) => self.kern_pairs.push(KernPair {
+                        text,
+                        left_glyph: first.to_u16(),
+                        right_glyph: second.to_u16(),
+                        value,
+                        classes: None,
+                    }),
+                    None => self.unreachable_pairs += 1,
+                }
+            }
+        }
+    }
+
+    fn add_class_pairs(&mut self, table: &PairPosFormat2, glyphs: &GlyphText) {
+        let (Ok(coverage), Ok(class_def1), Ok(class_def2)) =
+            (table.coverage(), table.class_def1(), table.class_def2())
+        else {
+            return;
+        };
+        // Covered glyphs missing from the first class definition are class 0
+        let assigned: HashMap<GlyphId16, u16> = class_def1.iter().collect();
+        let mut firsts: BTreeMap<u16, Vec<GlyphId16>> = BTreeMap::new();
+        for glyph in coverage.iter() {
+            let class = assigned.get(&glyph).copied().unwrap_or(0);
+            firsts.entry(class).or_default().push(glyph);
+        }
+        // Class 0 of the second definition is every other glyph, so it is skipped
+        let mut seconds: BTreeMap<u16, Vec<GlyphId16>> = BTreeMap::new();
+        for (glyph, class) in class_def2.iter().filter(|(_, class)| *class != 0) {
+            seconds.entry(class).or_default().push(glyph);
+        }
+
+        for (class1, record) in table.class1_records().iter().enumerate() {
+            let (Ok(record), Some(left)) = (record, firsts.get(&(class1 as u16))) else {
+                continue;
+            };
+            for (class2, values) in record.class2_records().iter().enumerate() {
+                let (Ok(values), Some(right)) = (values, seconds.get(&(class2 as u16))) else {
+                    continue;
+                };
+                let value = gap_adjustment(values.value_record1(), values.value_record2());
+                if value == 0 {
+                    continue;
+                }
+                let pair = glyphs
+                    .representative(left)
+                    .zip(glyphs.representative(right))
+                    .and_then(|(first, second)| {
+                        Some((first, second, glyphs.pair_text(first, second)?))
+                    });
+                match pair {
+                    Some((first, second, text)) => self.class_pairs.push(KernPair {
+                        text,
+                        left_glyph: first.to_u16(),
+                        right_glyph: second.to_u16(),
+                        value,
+                        classes: Some((class1 as u16, class2 as u16)),
+                    }),
+                    None => self.unreachable_pairs += 1,
+                }
+            }
+        }
+    }
+}
+
+/// How far a pair adjustment moves the second glyph away from the first
+fn gap_adjustment(first: &ValueRecord, second: &ValueRecord) -> i32 {
+    i32::from(first.x_advance().unwrap_or(0)) + i32::from(second.x_placement().unwrap_or(0))
+}
+
+/// Control strings for every letter the font maps, using Latin controls for other scripts,
+/// and the number of letters for which the font has no controls at all
+fn control_strings(glyphs: &GlyphText) -> (Vec<ControlString>, usize) {
+    let has = |ch: &char| glyphs.chars.contains(ch);
+    let mut controls = Vec::new();
+    let mut without_controls = 0;
+    for &letter in &glyphs.chars {
+        if !letter.is_alphabetic() || unicode_normalization::char::is_combining_mark(letter) {
+            continue;
+        }
+        // Titlecase digraphs such as ǅ are neither upper- nor lowercase but change when lowered;
+        // caseless letters sit between lowercase controls
+        let upper = !letter.is_lowercase()
+            && (letter.is_uppercase() || letter.to_lowercase().ne(std::iter::once(letter)));
+        let script = letter.script();
+        let own = CONTROLS.iter().filter(|(control, _, _)| *control == script);
+        let latin = CONTROLS
+            .iter()
+            .filter(|(control, _, _)| *control == Script::Latin);
+        let pair = own
+            .chain(latin)
+            .map(|&(_, upper_pair, lower_pair)| if upper { upper_pair } else { lower_pair })
+            .find(|pair| pair.iter().all(has));
+        if let Some([straight, round]) = pair {
+            let side: String = [straight, round, straight].iter().collect();
+            controls.push(ControlString {
+                letter,
+                text: format!("{side}{}{side}", literal(letter)),
+            });
+        } else {
+            without_controls += 1;
+        }
+    }
+    (controls, without_controls)
+}
+
+/// A character as typed in sample text, with escape syntax characters escaped
+fn literal(ch: char) -> String {
+    match ch {
+        '\\' | '/' => format!("\\{ch}"),
+        _ => ch.to_string(),
+    }
+}
+
+/// How glyphs can be written in sample text
+struct GlyphText<'a> {
+    /// Every mapped codepoint, for control strings
+    chars: BTreeSet<char>,
+    /// Lowest codepoint mapped to each glyph
+    by_glyph: HashMap<u16, char>,
+    post: Option<Post<'a>>,
+}
+
+impl<'a> GlyphText<'a> {
+    fn from_font(font: &FontRef<'a>) -> Self {
+        let mut chars = BTreeSet::new();
+        let mut by_glyph: HashMap<u16, char> = HashMap::new();
+        if let Some((_, _, subtable)) = font.cmap().ok().and_then(|cmap| cmap.best_subtable()) {
+            let glyph_count = font
+                .maxp()
+                .map(|maxp| u32::from(maxp.num_glyphs()))
+                .unwrap_or(u32::MAX);
+            let limits = CmapIterLimits {
+                max_char: 0x10FFFF,
+                glyph_count,
+            };
+            for (codepoint, glyph) in subtable.iter_with_limits(limits) {
+                let (Some(ch), Ok(glyph)) =
+                    (char::from_u32(codepoint), u16::try_from(glyph.to_u32()))
+                else {
+                    continue;
+                };
+                if glyph == 0 {
+                    continue;
+                }
+                chars.insert(ch);
+                let entry = by_glyph.entry(glyph).or_insert(ch);
+                *entry = (*entry).min(ch);
+            }
+        }
+        Self {
+            chars,
+            by_glyph,
+            post: font.post().ok(),
+        }
+    }
+
+    fn name(&self, glyph: GlyphId16) -> Option<&str> {
+        self.post.as_ref()?.glyph_name(glyph)
+    }
+
+    /// Member with the lowest codepoint, else the lowest glyph ID that has a name
+    fn representative(&self, members: &[GlyphId16]) -> Option<GlyphId16> {
+        members
+            .iter()
+            .filter_map(|&glyph| Some((self.by_glyph.get(&glyph.to_u16())?, glyph)))
+            .min()
+            .map(|(_, glyph)| glyph)
+            .or_else(|| {
+                members
+                    .iter()
+                    .copied()
+                    .filter(|&glyph| self.name(glyph).is_some())
+                    .min()
+            })
+    }
+
+    /// Both glyphs as sample text; when either needs `/name`, the other is written as `\u{…}`
+    /// so that glyph references start right after an escape.
+    fn pair_text(&self, first: GlyphId16, second: GlyphId16) -> Option<String> {
+        let chars = (
+            self.by_glyph.get(&first.to_u16()),
+            self.by_glyph.get(&second.to_u16()),
+        );
+        if let (Some(&left), Some(&right)) = chars {
+            return Some(format!("{}{}", literal(left), literal(right)));
+        }
+        let token = |glyph: GlyphId16, ch: Option<&char>| match ch {
+            Some(&ch) => Some(format!("\\u{{{:04X}}}", u32::from(ch))),
+            None => self.name(glyph).map(|name| format!("/{name}")),
+        };
+        Some(token(first, chars.0)? + &token(second, chars.1)?)
+    }
+}
@@ -0,0 +1,189 @@
+//! Built-in sample text library: pangrams and test strings per script
+//!
+//! Entries carry the Unicode script they exercise (as `Script::full_name`), so a
+//! font's cmap coverage can pick the strings it is able to render in full.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use unicode_script::{Script, UnicodeScript};
+
+use crate::coverage::FontCoverage;
+
+/// What a sample text is meant to exercise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SampleCategory {
+    Pangram,
+    Kerning,
+    Diacritics,
+    Numerals,
+    Joining,
+    Conjuncts,
+    CjkFrequency,
+    /// Entry added by the user
+    Custom,
+}
+
+impl SampleCategory {
+    pub const ALL: [SampleCategory; 8] = [
+        SampleCategory::Pangram,
+        SampleCategory::Kerning,
+        SampleCategory::Diacritics,
+        SampleCategory::Numerals,
+        SampleCategory::Joining,
+        SampleCategory::Conjuncts,
+        SampleCategory::CjkFrequency,
+        SampleCategory::Custom,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SampleCategory::Pangram => "Pangram",
+            SampleCategory::Kerning => "Kerning",
+            SampleCategory::Diacritics => "Diacritics",
+            SampleCategory::Numerals => "Numerals",
+            SampleCategory::Joining => "Joining",
+            SampleCategory::Conjuncts => "Conjuncts",
+            SampleCategory::CjkFrequency => "CJK frequency",
+            SampleCategory::Custom => "Custom",
+        }
+    }
+}
+
+impl fmt::Display for SampleCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// A named sample text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SampleText {
+    pub name: String,
+    pub category: SampleCategory,
+    /// Script the text exercises, as `Script::full_name`; `None` for script-neutral strings
+    #[serde(default)]
+    pub script: Option<String>,
+    pub text: String,
+}
+
+impl SampleText {
+    /// User entry whose script is the most frequent non-common script in `text`.
+    pub fn custom(name: impl Into<String>, text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self {
+            name: name.into(),
+            category: SampleCategory::Custom,
+            script: dominant_script(&text),
+            text,
+        }
+    }
+
+    /// Whether the font maps every visible character of the text.
+    pub fn renders_with(&self, coverage: &FontCoverage) -> bool {
+        coverage.missing(&self.text).is_empty()
+    }
+}
+
+impl fmt::Display for SampleText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.category, self.name)
+    }
+}
+
+/// Most frequent script in `text`, ignoring Common, Inherited and unassigned characters
+fn dominant_script(text: &str) -> Option<String> {
+    let mut counts: HashMap<Script, usize> = HashMap::new();
+    for ch in text.chars() {
+        let script = ch.script();
+        if !matches!(script, Script::Common | Script::Inherited | Script::Unknown) {
+            *counts.entry(script).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.full_name().cmp(a.0.full_name())))
+        .map(|(script, _)| script.full_name().to_string())
+}
+
+/// (name, category, script, text) for every built-in entry
+#[rustfmt::skip]
+const BUILTIN: &[(&str, SampleCategory, Option<&str>, &str)] = &[
+    ("English", SampleCategory::Pangram, Some("Latin"), "The quick brown fox jumps over the lazy dog"),
+    ("German", SampleCategory::Pangram, Some("Latin"), "Victor jagt zwölf Boxkämpfer quer über den großen Sylter Deich"),
+    ("French", SampleCategory::Pangram, Some("Latin"), "Portez ce vieux whisky au juge blond qui fume"),
+    ("Spanish", SampleCategory::Pangram, Some("Latin"), "El veloz murciélago hindú comía feliz cardillo y kiwi. La cigüeña tocaba el saxofón detrás del palenque de paja."),
+    ("Polish", SampleCategory::Pangram, Some("Latin"), "Pchnąć w tę łódź jeża lub ośm skrzyń fig"),
+    ("Czech", SampleCategory::Pangram, Some("Latin"), "Příliš žluťoučký kůň úpěl ďábelské ódy"),
+    ("Turkish", SampleCategory::Pangram, Some("Latin"), "Pijamalı hasta yağız şoföre çabucak güvendi"),
+    ("Russian", SampleCategory::Pangram, Some("Cyrillic"), "Съешь же ещё этих мягких французских булок, да выпей чаю"),
+    ("Ukrainian", SampleCategory::Pangram, Some("Cyrillic"), "Чуєш їх, доцю, га? Кумедна ж ти, прощайся без ґольфів!"),
+    ("Greek", SampleCategory::Pangram, Some("Greek"), "Ξεσκεπάζω την ψυχοφθόρα βδελυγμία"),
+    ("Hebrew", SampleCategory::Pangram, Some("Hebrew"), "דג סקרן שט בים מאוכזב ולפתע מצא חברה"),
+    ("Arabic", SampleCategory::Pangram, Some("Arabic"), "صِف خَلقَ خَودِ كَمِثلِ الشَمسِ إِذ بَزَغَت يَحظى الضَجيعُ بِها نَجلاءَ مِعطارِ"),
+    ("Hindi", SampleCategory::Pangram, Some("Devanagari"), "ऋषियों को सताने वाले दुष्ट राक्षसों के राजा रावण का सर्वनाश करने वाले विष्णुवतार भगवान श्रीराम, अयोध्या के महाराज दशरथ के बड़े सपुत्र थे।"),
+    ("Japanese iroha", SampleCategory::Pangram, Some("Hiragana"), "いろはにほへと ちりぬるを わかよたれそ つねならむ うゐのおくやま けふこえて あさきゆめみし ゑひもせす"),
+    ("Korean", SampleCategory::Pangram, Some("Hangul"), "키스의 고유조건은 입술끼리 만나야 하고 특별한 기술은 필요치 않다"),
+    ("Latin capitals", SampleCategory::Kerning, Some("Latin"), "AV AW AY AT LT LV LW LY FA PA TA VA WA YA AC AG AO AQ AU"),
+    ("Latin mixed case", SampleCategory::Kerning, Some("Latin"), "Ta Te To Tr Tu Tw Ty Va Ve Vo Wa We Wo Ya Ye Yo Fa Fe Fo Pa Pe Po LT’ “A” r. y, f)"),
+    ("Latin words", SampleCategory::Kerning, Some("Latin"), "AVATAR WAVY TOWAYA LYNX Toyota Wavy Yawn Vowel Awkward P.V.T. fjord"),
+    ("Cyrillic", SampleCategory::Kerning, Some("Cyrillic"), "ГА ТА УА ЛУ АТ АУ Га Го Та То Тя Уа Уо Ху ГЛАВА УТКА ТУЛА"),
+    ("Greek", SampleCategory::Kerning, Some("Greek"), "ΑΤ ΑΥ ΑΨ ΓΑ ΤΑ ΥΑ Τα Το Τω Υα Υο Γα Γο ΑΥΤΟ ΤΥΠΟΣ"),
+    ("Latin accented capitals", SampleCategory::Diacritics, Some("Latin"), "ÁÀÂÄÃÅĀĂĄ ÉÈÊËĒĖĘĚ ÍÌÎÏĪĮİ ÓÒÔÖÕŐØŌ ÚÙÛÜŰŮŪŲ ÝŸ ĆČÇ ĎĐ ĞĢ ĶĹĽĻŁ ŃŇŅÑ ŔŘ ŚŠŞȘ ŤŢȚ ŹŽŻ"),
+    ("Latin accented lowercase", SampleCategory::Diacritics, Some("Latin"), "áàâäãåāăą éèêëēėęě íìîïīįı óòôöõőøō úùûüűůūų ýÿ ćčç ďđ ğģ ķĺľļł ńňņñ ŕř śšşș ťţț źžż"),
+    ("Vietnamese stacking", SampleCategory::Diacritics, Some("Latin"), "Ấ Ầ Ẩ Ẫ Ậ ấ ầ ẩ ẫ ậ Ắ Ằ Ẳ Ẵ Ặ ắ ằ ẳ ẵ ặ Ế Ề Ể Ễ Ệ ế ề ể ễ ệ Ố Ồ Ổ Ỗ Ộ ố ồ ổ ỗ ộ Ớ Ờ Ở Ỡ Ợ ớ ờ ở ỡ ợ Ứ Ừ Ử Ữ Ự ứ ừ ử ữ ự"),
+    ("Combining marks", SampleCategory::Diacritics, Some("Latin"), "a\u{301}\u{302} e\u{308}\u{301} i\u{301} j\u{30C} n\u{303}\u{323} o\u{31B}\u{309} u\u{30A}\u{301} g\u{327} A\u{30A}\u{301} O\u{308}\u{304}"),
+    ("Cyrillic accents", SampleCategory::Diacritics, Some("Cyrillic"), "Ё ё Й й Ї ї Ў ў Ґ ґ Ѓ ѓ Ќ ќ а\u{301} е\u{301} и\u{301} о\u{301} у\u{301} ы\u{301} э\u{301} ю\u{301} я\u{301}"),
+    ("Greek tonos", SampleCategory::Diacritics, Some("Greek"), "Ά Έ Ή Ί Ό Ύ Ώ ά έ ή ί ό ύ ώ Ϊ Ϋ ϊ ϋ ΐ ΰ"),
+    ("Figures and currency", SampleCategory::Numerals, None, "0123456789 $1,234.56 €78.90 £12 ¥345 ¢99 45% 3×4=12 8÷2 ±7 −2 °C #1"),
+    ("Fractions", SampleCategory::Numerals, None, "½ ¼ ¾ ⅓ ⅔ ⅛ ⅜ ⅝ ⅞ 1⁄2 3⁄4 5⁄8"),
+    ("Superiors and inferiors", SampleCategory::Numerals, None, "x² y³ 10⁻⁶ ⁰¹²³⁴⁵⁶⁷⁸⁹ H₂O CO₂ ₀₁₂₃₄₅₆₇₈₉"),
+    ("Arabic positional forms", SampleCategory::Joining, Some("Arabic"), "ب بب ببب ـبـ ع عع ععع ـعـ ه هه ههه ـهـ ي يي ييي ـيـ س سس سسس ـسـ"),
+    ("Arabic lam-alef", SampleCategory::Joining, Some("Arabic"), "لا لأ لإ لآ بلا سلام الله للّه"),
+    ("Arabic phrase", SampleCategory::Joining, Some("Arabic"), "بسم الله الرحمن الرحيم"),
+    ("Syriac", SampleCategory::Joining, Some("Syriac"), "ܒ ܒܒ ܒܒܒ ܫܠܡܐ ܥܠܡܐ"),
+    ("Devanagari", SampleCategory::Conjuncts, Some("Devanagari"), "क्ष त्र ज्ञ श्र द्ध द्व द्य ह्म ह्य ट्ट ड्ड क्क स्त्र प्र क्र र्क र्म्य कि की कु कू कृ"),
+    ("Bengali", SampleCategory::Conjuncts, Some("Bengali"), "ক্ষ ত্র জ্ঞ ন্ত ঙ্ক ন্দ স্ত প্র ক্র র্ক কি কী কু কূ কৃ"),
+    ("Tamil", SampleCategory::Conjuncts, Some("Tamil"), "க்ஷ ஸ்ரீ கி கீ கு கூ கெ கே கை கொ கோ கௌ"),
+    ("Simplified Chinese, most frequent", SampleCategory::CjkFrequency, Some("Han"), "的一是不了在人有我他这个们中来上大为和国地到以说时要就出会可也你对生能而子那得于着下自之年过发后作里用道行所然家种事成方多经么去法学如都同现当没动面起看定天分还进好小部其些主样理心她本前开但因只从想实日军者意无力它与长把机十民第"),
+    ("Traditional Chinese, most frequent", SampleCategory::CjkFrequency, Some("Han"), "的一是不了在人有我他這個們中來上大為和國地到以說時要就出會可也你對生能而子那得於著下自之年過發後作裡用道行所然家種事成方多經麼去法學如都同現當沒動面起看定天分還進好小部其些主樣理心她本前開但因只從想實日"),
+    ("Japanese kana and common kanji", SampleCategory::CjkFrequency, Some("Han"), "日本語の文章には、漢字・ひらがな・カタカナが混在します。人日一大年出本中子見国言上分生手自行者二間事思時気会十家女三前的方入小地合後目長場代私下立部学物月田何来彼話体動社知理山内同心発高実作当新世今書度明五戦力名金性対意用男主通関文屋感郎業定政持道外取所"),
+    ("Korean common syllables", SampleCategory::CjkFrequency, Some("Hangul"), "이 의 가 에 는 을 를 하 고 다 지 한 서 로 기 도 있 으 사 게 대 리 자 어 나 일 수 시 적 인 아 정 해 국 전 들 것 상 부 장 주 만 내 보 중 제 사람 우리 그리고 하지만 대한민국"),
+];
+
+/// The curated library, in display order.
+pub fn builtin_samples() -> Vec<SampleText> {
+    BUILTIN
+        .iter()
+        .map(|&(name, category, script, text)| SampleText {
+            name: name.to_string(),
+            category,
+            script: script.map(str::to_string),
+            text: text.to_string(),
+        })
+        .collect()
+}
+
+/// Entries the font renders without .notdef, those in its best-covered scripts first.
+///
+/// Script-neutral entries follow the script-specific ones; ties keep library order.
+pub fn suggest_samples<'a>(
+    samples: &'a [SampleText],
+    coverage: &FontCoverage,
+) -> Vec<&'a SampleText> {
+    let scripts: Vec<String> = coverage.scripts().into_iter().map(|s| s.name).collect();
+    let rank = |sample: &SampleText| match &sample.script {
+        Some(script) => scripts
+            .iter()
+            .position(|name| name == script)
+            .unwrap_or(usize::MAX - 1),
+        None => usize::MAX,
+    };
+    let mut suggested: Vec<&SampleText> = samples
+        .iter()
+        .filter(|sample| sample.renders_with(coverage))
+        .collect();
+    suggested.sort_by_key(|sample| rank(sample));
+    suggested
+}
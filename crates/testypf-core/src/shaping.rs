@@ -0,0 +1,617 @@
+use super::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Keys accepted for each glyph field in typf glyph output (first match wins): the long
+/// snake_case name, then the hb-shape short form. Shared with [`crate::overlay`], which reads
+/// the same glyph objects next to rendered pixels.
+pub(crate) const GLYPH_ID_KEYS: &[&str] = &["glyph_id", "gid", "g"];
+pub(crate) const CLUSTER_KEYS: &[&str] = &["cluster", "cl"];
+pub(crate) const X_ADVANCE_KEYS: &[&str] = &["x_advance", "ax", "advance"];
+pub(crate) const Y_ADVANCE_KEYS: &[&str] = &["y_advance", "ay"];
+const X_OFFSET_KEYS: &[&str] = &["x_offset", "dx"];
+const Y_OFFSET_KEYS: &[&str] = &["y_offset", "dy"];
+const GLYPH_NAME_KEYS: &[&str] = &["glyph_name", "name", "n"];
+/// Pen position and ink bounds, only present in rendered output
+pub(crate) const X_KEYS: &[&str] = &["x", "origin_x", "pen_x"];
+pub(crate) const Y_KEYS: &[&str] = &["y", "origin_y", "pen_y", "baseline"];
+pub(crate) const BOUNDS_KEYS: &[&str] = &["bbox", "bounds", "ink", "ink_bounds"];
+/// Run fields
+const SCRIPT_KEYS: &[&str] = &["script", "script_tag"];
+const DIRECTION_KEYS: &[&str] = &["direction", "dir"];
+
+/// Largest position difference still treated as equal (absorbs float rounding in typf output)
+pub const POSITION_EPSILON: f32 = 0.01;
+
+/// One shaped glyph: ID, source cluster, advance and offset
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShapedGlyph {
+    pub glyph_id: u32,
+
+    /// Glyph name from typf output or the font's `post` table, when known
+    #[serde(default)]
+    pub glyph_name: Option<String>,
+
+    pub cluster: u32,
+    pub x_advance: f32,
+    pub y_advance: f32,
+    pub x_offset: f32,
+    pub y_offset: f32,
+}
+
+/// Text direction of a shaped run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TextDirection {
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+}
+
+impl TextDirection {
+    /// Parse HarfBuzz-style names (`ltr`, `rtl`, `ttb`, `btt`) and their long forms
+    pub fn parse(value: &str) -> Option<Self> {
+        let normalized: String = value
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match normalized.as_str() {
+            "ltr" | "lefttoright" => Some(Self::LeftToRight),
+            "rtl" | "righttoleft" => Some(Self::RightToLeft),
+            "ttb" | "toptobottom" => Some(Self::TopToBottom),
+            "btt" | "bottomtotop" => Some(Self::BottomToTop),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TextDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextDirection::LeftToRight => write!(f, "LTR"),
+            TextDirection::RightToLeft => write!(f, "RTL"),
+            TextDirection::TopToBottom => write!(f, "TTB"),
+            TextDirection::BottomToTop => write!(f, "BTT"),
+        }
+    }
+}
+
+/// A run of glyphs sharing one script and direction
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShapingRun {
+    /// ISO 15924 script tag (e.g. "Arab"), when typf reports it
+    pub script: Option<String>,
+    pub direction: Option<TextDirection>,
+    pub glyphs: Vec<ShapedGlyph>,
+}
+
+/// Typed shaping output of the JSON backend
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShapingResult {
+    pub runs: Vec<ShapingRun>,
+}
+
+impl ShapingResult {
+    /// Parse typf JSON output; see [`parse_typf_json`] for the accepted shapes.
+    ///
+    /// A `"runs"` array yields one run per entry; otherwise the whole output is a single run.
+    pub fn from_typf_json(json: &str) -> TestypfResult<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| TestypfError::RenderFailed(format!("Invalid typf JSON output: {e}")))?;
+
+        let runs = match find_runs(&value) {
+            Some(runs) => runs.iter().map(parse_run).collect::<TestypfResult<_>>()?,
+            None => vec![parse_run(&value)?],
+        };
+        Ok(Self { runs })
+    }
+
+    /// Parse the JSON payload of a `RendererBackend::Json` render
+    pub fn from_render_result(output: &RenderResult) -> TestypfResult<Self> {
+        if output.format != PixelFormat::Json {
+            return Err(TestypfError::RenderFailed(format!(
+                "Expected JSON shaping output, got {}",
+                output.format
+            )));
+        }
+        Self::from_typf_json(&String::from_utf8_lossy(&output.data))
+    }
+
+    /// All glyphs in visual order across runs
+    pub fn glyphs(&self) -> impl Iterator<Item = &ShapedGlyph> {
+        self.runs.iter().flat_map(|run| &run.glyphs)
+    }
+
+    pub fn glyph_count(&self) -> usize {
+        self.runs.iter().map(|run| run.glyphs.len()).sum()
+    }
+
+    /// Fill missing glyph names from the font's `post` table; fonts without names are left as is
+    pub fn fill_glyph_names(&mut self, font_path: &Path) {
+        let Ok(data) = std::fs::read(font_path) else {
+            return;
+        };
+        let Ok(font) = read_fonts::FontRef::from_index(&data, 0) else {
+            return;
+        };
+        let Ok(post) = read_fonts::TableProvider::post(&font) else {
+            return;
+        };
+
+        for glyph in self.runs.iter_mut().flat_map(|run| &mut run.glyphs) {
+            if glyph.glyph_name.is_none() {
+                glyph.glyph_name = u16::try_from(glyph.glyph_id)
+                    .ok()
+                    .and_then(|id| post.glyph_name(read_fonts::types::GlyphId16::new(id)))
+                    .map(str::to_string);
+            }
+        }
+    }
+}
+
+/// Extract the glyph run from typf JSON backend output, flattened across runs.
+///
+/// Accepts a bare array of glyph objects or any object nesting one (e.g. under `"glyphs"`), with either
+/// long (`glyph_id`, `x_advance`, ...) or hb-shape style (`g`, `cl`, `ax`, `dx`, ...) keys.
+pub fn parse_typf_json(json: &str) -> TestypfResult<Vec<ShapedGlyph>> {
+    let result = ShapingResult::from_typf_json(json)?;
+    Ok(result.runs.into_iter().flat_map(|run| run.glyphs).collect())
+}
+
+/// Depth-first search for a non-empty `"runs"` array of objects
+fn find_runs(value: &serde_json::Value) -> Option<&Vec<serde_json::Value>> {
+    match value {
+        serde_json::Value::Object(map) => match map.get("runs") {
+            Some(serde_json::Value::Array(runs))
+                if !runs.is_empty() && runs.iter().all(serde_json::Value::is_object) =>
+            {
+                Some(runs)
+            }
+            _ => map.values().find_map(find_runs),
+        },
+        _ => None,
+    }
+}
+
+fn parse_run(value: &serde_json::Value) -> TestypfResult<ShapingRun> {
+    let glyphs = find_glyph_array(value).ok_or_else(|| {
+        TestypfError::RenderFailed("No glyph array found in typf JSON output".to_string())
+    })?;
+    let string = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| value.get(*key).and_then(serde_json::Value::as_str))
+    };
+
+    Ok(ShapingRun {
+        script: string(SCRIPT_KEYS).map(str::to_string),
+        direction: string(DIRECTION_KEYS).and_then(TextDirection::parse),
+        glyphs: glyphs
+            .iter()
+            .enumerate()
+            .map(|(index, glyph)| parse_glyph(index, glyph))
+            .collect::<TestypfResult<_>>()?,
+    })
+}
+
+fn parse_glyph(index: usize, glyph: &serde_json::Value) -> TestypfResult<ShapedGlyph> {
+    let number = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| glyph.get(*key).and_then(serde_json::Value::as_f64))
+    };
+    let glyph_id = number(GLYPH_ID_KEYS).ok_or_else(|| {
+        TestypfError::RenderFailed(format!("Glyph {index} has no numeric glyph ID"))
+    })?;
+    Ok(ShapedGlyph {
+        glyph_id: glyph_id as u32,
+        glyph_name: GLYPH_NAME_KEYS
+            .iter()
+            .find_map(|key| glyph.get(*key).and_then(serde_json::Value::as_str))
+            .map(str::to_string),
+        cluster: number(CLUSTER_KEYS).unwrap_or(0.0) as u32,
+        x_advance: number(X_ADVANCE_KEYS).unwrap_or(0.0) as f32,
+        y_advance: number(Y_ADVANCE_KEYS).unwrap_or(0.0) as f32,
+        x_offset: number(X_OFFSET_KEYS).unwrap_or(0.0) as f32,
+        y_offset: number(Y_OFFSET_KEYS).unwrap_or(0.0) as f32,
+    })
+}
+
+/// Depth-first search for the first array of objects that carry a glyph ID
+fn find_glyph_array(value: &serde_json::Value) -> Option<&Vec<serde_json::Value>> {
+    match value {
+        serde_json::Value::Array(items) => {
+            let is_glyph = |item: &serde_json::Value| {
+                GLYPH_ID_KEYS
+                    .iter()
+                    .any(|key| item.get(*key).is_some_and(serde_json::Value::is_number))
+            };
+            if items.first().is_some_and(is_glyph) || items.is_empty() {
+                Some(items)
+            } else {
+                items.iter().find_map(find_glyph_array)
+            }
+        }
+        serde_json::Value::Object(map) => {
+            // Prefer the conventional key so unrelated arrays (e.g. features) are skipped
+            if let Some(glyphs) = map.get("glyphs").and_then(find_glyph_array) {
+                return Some(glyphs);
+            }
+            map.values().find_map(|v| match v {
+                serde_json::Value::Array(items) if items.is_empty() => None,
+                other => find_glyph_array(other),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Serialize a glyph run in hb-shape notation with numeric glyph IDs: `[gid=cluster@dx,dy+ax,ay|...]`.
+///
+/// Zero offsets and a zero vertical advance are omitted, as hb-shape does.
+pub fn format_glyph_run(glyphs: &[ShapedGlyph]) -> String {
+    let items: Vec<String> = glyphs
+        .iter()
+        .map(|g| {
+            let mut item = format!("{}={}", g.glyph_id, g.cluster);
+            if g.x_offset != 0.0 || g.y_offset != 0.0 {
+                item.push_str(&format!(
+                    "@{},{}",
+                    format_number(g.x_offset),
+                    format_number(g.y_offset)
+                ));
+            }
+            item.push_str(&format!("+{}", format_number(g.x_advance)));
+            if g.y_advance != 0.0 {
+                item.push_str(&format!(",{}", format_number(g.y_advance)));
+            }
+            item
+        })
+        .collect();
+    format!("[{}]", items.join("|"))
+}
+
+/// Parse hb-shape glyph-run notation as written by [`format_glyph_run`]
+pub fn parse_glyph_run(run: &str) -> TestypfResult<Vec<ShapedGlyph>> {
+    let invalid =
+        |detail: String| TestypfError::RenderFailed(format!("Invalid glyph run: {detail}"));
+    let inner = run
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| invalid(format!("{run:?} is not wrapped in [...]")))?;
+    if inner.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    inner
+        .split('|')
+        .map(|item| {
+            let item = item.trim();
+            let (glyph, rest) = item
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("{item:?} has no '=cluster'")))?;
+            let glyph_id = glyph
+                .trim_start_matches("gid")
+                .parse::<u32>()
+                .map_err(|_| invalid(format!("{glyph:?} is not a numeric glyph ID")))?;
+
+            let (position, advance) = match rest.split_once('+') {
+                Some((position, advance)) => (position, Some(advance)),
+                None => (rest, None),
+            };
+            let (cluster, offset) = match position.split_once('@') {
+                Some((cluster, offset)) => (cluster, Some(offset)),
+                None => (position, None),
+            };
+
+            let pair = |text: Option<&str>| -> TestypfResult<(f32, f32)> {
+                let Some(text) = text else {
+                    return Ok((0.0, 0.0));
+                };
+                let mut parts = text.split(',');
+                let mut next = || -> TestypfResult<f32> {
+                    parts
+                        .next()
+                        .map(|p| {
+                            p.trim()
+                                .parse::<f32>()
+                                .map_err(|_| invalid(format!("{p:?} in {item:?} is not a number")))
+                        })
+                        .unwrap_or(Ok(0.0))
+                };
+                Ok((next()?, next()?))
+            };
+            let (x_offset, y_offset) = pair(offset)?;
+            let (x_advance, y_advance) = pair(advance)?;
+
+            Ok(ShapedGlyph {
+                glyph_id,
+                glyph_name: None,
+                cluster: cluster
+                    .parse::<u32>()
+                    .map_err(|_| invalid(format!("{cluster:?} is not a cluster index")))?,
+                x_advance,
+                y_advance,
+                x_offset,
+                y_offset,
+            })
+        })
+        .collect()
+}
+
+/// Describe every difference between an expected and an actual glyph run
+pub fn compare_glyph_runs(expected: &[ShapedGlyph], actual: &[ShapedGlyph]) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    if expected.len() != actual.len() {
+        mismatches.push(format!(
+            "expected {} glyphs, got {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+
+    for (index, (e, a)) in expected.iter().zip(actual).enumerate() {
+        if e.glyph_id != a.glyph_id {
+            mismatches.push(format!(
+                "glyph {index}: expected gid {}, got {}",
+                e.glyph_id, a.glyph_id
+            ));
+        }
+        if e.cluster != a.cluster {
+            mismatches.push(format!(
+                "glyph {index}: expected cluster {}, got {}",
+                e.cluster, a.cluster
+            ));
+        }
+        let positions = [
+            ("x advance", e.x_advance, a.x_advance),
+            ("y advance", e.y_advance, a.y_advance),
+            ("x offset", e.x_offset, a.x_offset),
+            ("y offset", e.y_offset, a.y_offset),
+        ];
+        for (name, want, got) in positions {
+            if (want - got).abs() > POSITION_EPSILON {
+                mismatches.push(format!(
+                    "glyph {index}: expected {name} {}, got {}",
+                    format_number(want),
+                    format_number(got)
+                ));
+            }
+        }
+    }
+
+    mismatches
+}
+
+fn format_number(value: f32) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{value}")
+    }
+}
+
+/// One line of a shaping test file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapingTest {
+    /// 1-based line number in the test file
+    pub line: usize,
+    pub font: PathBuf,
+    pub text: String,
+    pub font_size: Option<f32>,
+    pub variations: HashMap<String, f32>,
+    pub expected: Vec<ShapedGlyph>,
+}
+
+impl ShapingTest {
+    /// `base` with this test's text, size and variations
+    ///
+    /// The text is escaped and normalization is turned off, so it is shaped exactly as written.
+    pub fn settings(&self, base: &RenderSettings) -> RenderSettings {
+        let mut settings = base.clone();
+        settings.sample_text = crate::escapes::escape(&self.text);
+        settings.normalization = crate::normalization::Normalization::None;
+        if let Some(size) = self.font_size {
+            settings.font_size = size;
+        }
+        settings.variation_coords.extend(self.variations.clone());
+        settings
+    }
+}
+
+/// Parse a shaping test file in the style of HarfBuzz's `tests/shaping` data.
+///
+/// Each non-empty, non-`#` line is `font;options;text;expected`:
+/// - `font` is relative to `base_dir` unless absolute
+/// - `options` is space-separated `--font-size=N` and `--variations=wght=700,wdth=90`
+/// - `text` is `U+0627,U+0644` code points or literal text
+/// - `expected` is a glyph run as written by [`format_glyph_run`]
+pub fn parse_shaping_tests(contents: &str, base_dir: &Path) -> TestypfResult<Vec<ShapingTest>> {
+    let mut tests = Vec::new();
+    for (index, raw) in contents.lines().enumerate() {
+        let line = index + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let invalid = |detail: String| {
+            TestypfError::RenderFailed(format!("Shaping test line {line}: {detail}"))
+        };
+        let fields: Vec<&str> = trimmed.splitn(4, ';').collect();
+        let [font, options, text, expected] = fields[..] else {
+            return Err(invalid("expected font;options;text;glyphs".to_string()));
+        };
+
+        let mut font_size = None;
+        let mut variations = HashMap::new();
+        for option in options.split_whitespace() {
+            if let Some(size) = option.strip_prefix("--font-size=") {
+                font_size = Some(
+                    size.parse::<f32>()
+                        .map_err(|_| invalid(format!("invalid font size {size:?}")))?,
+                );
+            } else if let Some(list) = option.strip_prefix("--variations=") {
+                for setting in list.split(',').filter(|s| !s.is_empty()) {
+                    let (tag, value) = setting
+                        .split_once('=')
+                        .and_then(|(tag, value)| Some((tag, value.parse::<f32>().ok()?)))
+                        .ok_or_else(|| invalid(format!("invalid variation {setting:?}")))?;
+                    variations.insert(tag.to_string(), value);
+                }
+            } else {
+                return Err(invalid(format!("unsupported option {option:?}")));
+            }
+        }
+
+        let font = PathBuf::from(font.trim());
+        tests.push(ShapingTest {
+            line,
+            font: if font.is_relative() {
+                base_dir.join(font)
+            } else {
+                font
+            },
+            text: decode_test_text(text).map_err(invalid)?,
+            font_size,
+            variations,
+            expected: parse_glyph_run(expected).map_err(|e| invalid(e.to_string()))?,
+        });
+    }
+    Ok(tests)
+}
+
+/// Read a shaping test file; fonts are resolved against its directory
+pub fn load_shaping_tests(path: &Path) -> TestypfResult<Vec<ShapingTest>> {
+    let contents = std::fs::read_to_string(path)?;
+    parse_shaping_tests(&contents, path.parent().unwrap_or_else(|| Path::new(".")))
+}
+
+/// `U+0627,U+0644` lists become their characters; anything else is taken literally
+fn decode_test_text(text: &str) -> Result<String, String> {
+    let items: Vec<&str> = text.split(',').map(str::trim).collect();
+    if !items
+        .iter()
+        .all(|item| item.starts_with("U+") || item.starts_with("u+"))
+    {
+        return Ok(text.to_string());
+    }
+
+    items
+        .iter()
+        .map(|item| {
+            u32::from_str_radix(&item[2..], 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| format!("invalid code point {item:?}"))
+        })
+        .collect()
+}
+
+/// Outcome of shaping one test string
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShapingTestResult {
+    pub line: usize,
+    pub font: PathBuf,
+    pub text: String,
+    pub expected: String,
+    /// Actual glyph run in the same notation, ready to paste into the test file
+    pub actual: Option<String>,
+    pub mismatches: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl ShapingTestResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.mismatches.is_empty()
+    }
+}
+
+/// Results of a shaping test run
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShapingReport {
+    pub results: Vec<ShapingTestResult>,
+}
+
+impl ShapingReport {
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.passed()).count()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(ShapingTestResult::passed)
+    }
+
+    /// Per-string report: one line per test, mismatches indented below failures
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "{} of {} shaping tests passed\n",
+            self.passed(),
+            self.results.len()
+        );
+        for result in self.results.iter().filter(|r| !r.passed()) {
+            out.push_str(&format!(
+                "FAIL line {} ({}): {:?}\n",
+                result.line,
+                result.font.display(),
+                result.text
+            ));
+            if let Some(error) = &result.error {
+                out.push_str(&format!("  error: {error}\n"));
+            }
+            for mismatch in &result.mismatches {
+                out.push_str(&format!("  {mismatch}\n"));
+            }
+            if let Some(actual) = &result.actual {
+                out.push_str(&format!(
+                    "  expected {}\n  actual   {}\n",
+                    result.expected, actual
+                ));
+            }
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> TestypfResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            TestypfError::ExportFailed(format!("Failed to serialize shaping report: {e}"))
+        })
+    }
+}
+
+/// Shape every test string with the JSON backend and compare against its expected glyph run.
+///
+/// `base` supplies everything a test line doesn't set. The renderer's current backend is left
+/// unchanged, since `shape_text` restores it after every line.
+pub fn run_shaping_tests(
+    renderer: &mut dyn TextRenderer,
+    tests: &[ShapingTest],
+    base: &RenderSettings,
+) -> ShapingReport {
+    let results = tests
+        .iter()
+        .map(|test| {
+            let mut result = ShapingTestResult {
+                line: test.line,
+                font: test.font.clone(),
+                text: test.text.clone(),
+                expected: format_glyph_run(&test.expected),
+                actual: None,
+                mismatches: Vec::new(),
+                error: None,
+            };
+
+            match renderer.shape_text(&test.font, &test.settings(base)) {
+                Ok(shaped) => {
+                    let glyphs: Vec<ShapedGlyph> = shaped.glyphs().cloned().collect();
+                    result.mismatches = compare_glyph_runs(&test.expected, &glyphs);
+                    result.actual = Some(format_glyph_run(&glyphs));
+                }
+                Err(e) => result.error = Some(e.to_string()),
+            }
+            result
+        })
+        .collect();
+
+    ShapingReport { results }
+}
@@ -0,0 +1,416 @@
+use super::*;
+use crate::diff::{DiffReport, DiffStyle, DiffTolerance};
+use crate::export::{sanitize_component, ExportFormat, RenderProvenance};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// File name of the JSON report written next to the HTML gallery
+pub const REPORT_JSON: &str = "report.json";
+/// File name of the HTML gallery of failures
+pub const REPORT_HTML: &str = "report.html";
+
+/// A list of renders to check against golden images
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SnapshotManifest {
+    /// Tolerance for cases that don't set their own (exact match by default)
+    #[serde(default)]
+    pub tolerance: DiffTolerance,
+
+    pub cases: Vec<SnapshotCase>,
+}
+
+/// One (font, text, settings, backend) render in a snapshot manifest
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SnapshotCase {
+    /// Unique name; also the golden file stem
+    pub name: String,
+
+    /// Font file; relative paths are resolved against the manifest's directory
+    pub font: PathBuf,
+
+    pub text: String,
+
+    pub backend: RendererBackend,
+
+    /// Overrides the font size of `settings`
+    #[serde(default)]
+    pub font_size: Option<f32>,
+
+    /// Base render settings (defaults when omitted); `text` and `backend` always win
+    #[serde(default)]
+    pub settings: Option<RenderSettings>,
+
+    /// Overrides the manifest tolerance for this case
+    #[serde(default)]
+    pub tolerance: Option<DiffTolerance>,
+}
+
+impl SnapshotCase {
+    /// Settings the case is rendered with
+    pub fn render_settings(&self) -> RenderSettings {
+        let mut settings = self.settings.clone().unwrap_or_default();
+        settings.sample_text = self.text.clone();
+        settings.backend = self.backend.clone();
+        if let Some(size) = self.font_size {
+            settings.font_size = size;
+        }
+        settings
+    }
+
+    /// File stem shared by the golden, actual and diff images
+    pub fn file_stem(&self) -> String {
+        sanitize_component(&self.name)
+    }
+}
+
+impl SnapshotManifest {
+    /// Read a JSON manifest, resolving relative font paths against its directory
+    pub fn load(path: &Path) -> TestypfResult<Self> {
+        let json = std::fs::read_to_string(path)?;
+        let mut manifest: Self = serde_json::from_str(&json).map_err(|e| {
+            TestypfError::RenderFailed(format!("Invalid snapshot manifest {}: {e}", path.display()))
+        })?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        for case in &mut manifest.cases {
+            if case.font.is_relative() {
+                case.font = base.join(&case.font);
+            }
+        }
+
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Reject empty or duplicate case names, which would share a golden file
+    pub fn validate(&self) -> TestypfResult<()> {
+        let mut stems = HashSet::new();
+        for case in &self.cases {
+            let stem = case.file_stem();
+            if stem.is_empty() {
+                return Err(TestypfError::RenderFailed(format!(
+                    "Snapshot case name {:?} has no usable file name",
+                    case.name
+                )));
+            }
+            if !stems.insert(stem.clone()) {
+                return Err(TestypfError::RenderFailed(format!(
+                    "Snapshot cases share the golden file name {stem}.png"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether a run compares against goldens or rewrites them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SnapshotMode {
+    #[default]
+    Check,
+    /// Write goldens for missing or changed cases; matching goldens are left untouched
+    Bless,
+}
+
+/// Where goldens live and where the report goes
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotOptions {
+    pub golden_dir: PathBuf,
+    pub report_dir: PathBuf,
+    pub mode: SnapshotMode,
+}
+
+/// Outcome of one snapshot case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SnapshotStatus {
+    Passed,
+    Failed,
+    /// No golden exists yet (check mode)
+    Missing,
+    /// Golden written or replaced (bless mode)
+    Blessed,
+    /// The case could not be rendered or compared
+    Error,
+}
+
+impl SnapshotStatus {
+    pub fn is_failure(self) -> bool {
+        matches!(self, Self::Failed | Self::Missing | Self::Error)
+    }
+}
+
+/// Result of one snapshot case; image paths are relative to the report directory
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SnapshotCaseResult {
+    pub name: String,
+    pub backend: String,
+    pub status: SnapshotStatus,
+    pub golden: PathBuf,
+    pub diff: Option<DiffReport>,
+    pub expected_image: Option<PathBuf>,
+    pub actual_image: Option<PathBuf>,
+    pub diff_image: Option<PathBuf>,
+    pub error: Option<String>,
+}
+
+/// Results of a whole snapshot run
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SnapshotReport {
+    pub mode: SnapshotMode,
+    pub results: Vec<SnapshotCaseResult>,
+}
+
+impl SnapshotReport {
+    pub fn count(&self, status: SnapshotStatus) -> usize {
+        self.results.iter().filter(|r| r.status == status).count()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &SnapshotCaseResult> {
+        self.results.iter().filter(|r| r.status.is_failure())
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// e.g. "12 passed, 1 failed, 0 missing, 0 blessed, 0 errors"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} passed, {} failed, {} missing, {} blessed, {} errors",
+            self.count(SnapshotStatus::Passed),
+            self.count(SnapshotStatus::Failed),
+            self.count(SnapshotStatus::Missing),
+            self.count(SnapshotStatus::Blessed),
+            self.count(SnapshotStatus::Error)
+        )
+    }
+
+    pub fn to_json(&self) -> TestypfResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            TestypfError::ExportFailed(format!("Failed to serialize snapshot report: {e}"))
+        })
+    }
+
+    /// Self-contained HTML gallery of failing cases (expected, actual and diff side by side)
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>testypf snapshot report</title>\n\
+             <style>body{font-family:sans-serif;margin:24px}table{border-collapse:collapse}\
+             td,th{border:1px solid #ccc;padding:8px;vertical-align:top;text-align:left}\
+             img{image-rendering:pixelated;background:#eee;max-width:480px}\
+             .failed,.missing,.error{color:#b00}</style></head><body>\n",
+        );
+        html.push_str(&format!(
+            "<h1>Snapshot report</h1>\n<p>{}</p>\n",
+            escape_html(&self.summary())
+        ));
+
+        if self.is_success() {
+            html.push_str("<p>All snapshots match.</p>\n</body></html>\n");
+            return html;
+        }
+
+        html.push_str(
+            "<table>\n<tr><th>Case</th><th>Expected</th><th>Actual</th><th>Diff</th></tr>\n",
+        );
+        for result in self.failures() {
+            let image = |path: &Option<PathBuf>| {
+                path.as_ref()
+                    .map(|p| {
+                        format!(
+                            "<img src=\"{}\">",
+                            escape_html(&p.to_string_lossy().replace('\\', "/"))
+                        )
+                    })
+                    .unwrap_or_else(|| "&mdash;".to_string())
+            };
+            let detail = result
+                .error
+                .clone()
+                .or_else(|| result.diff.as_ref().map(DiffReport::summary))
+                .unwrap_or_else(|| "No golden image".to_string());
+            let status = format!("{:?}", result.status).to_lowercase();
+            html.push_str(&format!(
+                "<tr><td><strong>{}</strong><br>{}<br><span class=\"{status}\">{status}</span><br><small>{}</small></td>\
+                 <td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&result.name),
+                escape_html(&result.backend),
+                escape_html(&detail),
+                image(&result.expected_image),
+                image(&result.actual_image),
+                image(&result.diff_image),
+            ));
+        }
+        html.push_str("</table>\n</body></html>\n");
+        html
+    }
+
+    /// Write `report.json` and `report.html` into `dir`
+    pub fn write(&self, dir: &Path) -> TestypfResult<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join(REPORT_JSON), self.to_json()?)?;
+        std::fs::write(dir.join(REPORT_HTML), self.to_html())?;
+        Ok(())
+    }
+}
+
+/// Render every manifest case and compare it with (or bless) its golden PNG.
+///
+/// Failing cases get `expected/`, `actual/` and `diff/` PNGs in the report directory, and the JSON and HTML
+/// reports are written there too. The renderer's backend is switched per case and restored afterwards.
+pub fn run_snapshots(
+    renderer: &mut dyn TextRenderer,
+    manifest: &SnapshotManifest,
+    options: &SnapshotOptions,
+) -> TestypfResult<SnapshotReport> {
+    manifest.validate()?;
+    std::fs::create_dir_all(&options.golden_dir)?;
+    std::fs::create_dir_all(&options.report_dir)?;
+
+    let previous = renderer.backend();
+    let mut results = Vec::with_capacity(manifest.cases.len());
+    for case in &manifest.cases {
+        let tolerance = case.tolerance.unwrap_or(manifest.tolerance);
+        let golden = options.golden_dir.join(format!("{}.png", case.file_stem()));
+        let mut result = SnapshotCaseResult {
+            name: case.name.clone(),
+            backend: case.backend.to_string(),
+            status: SnapshotStatus::Error,
+            golden: golden.clone(),
+            diff: None,
+            expected_image: None,
+            actual_image: None,
+            diff_image: None,
+            error: None,
+        };
+
+        if let Err(e) = run_case(renderer, case, tolerance, &golden, options, &mut result) {
+            result.status = SnapshotStatus::Error;
+            result.error = Some(e.to_string());
+        }
+        results.push(result);
+    }
+    renderer.set_backend(previous)?;
+
+    let report = SnapshotReport {
+        mode: options.mode,
+        results,
+    };
+    report.write(&options.report_dir)?;
+    Ok(report)
+}
+
+fn run_case(
+    renderer: &mut dyn TextRenderer,
+    case: &SnapshotCase,
+    tolerance: DiffTolerance,
+    golden: &Path,
+    options: &SnapshotOptions,
+    result: &mut SnapshotCaseResult,
+) -> TestypfResult<()> {
+    let settings = case.render_settings();
+    renderer.set_backend(case.backend.clone())?;
+    let rendered = renderer.render_text(&case.font, &settings)?;
+    let actual = if rendered.is_vector() {
+        crate::vector::rasterize(&rendered, 1.0)?
+    } else {
+        rendered.to_rgba8()?
+    };
+
+    let expected = if golden.exists() {
+        Some(read_png_rgba(golden)?)
+    } else {
+        None
+    };
+    let diff = expected
+        .as_ref()
+        .map(|expected| {
+            crate::diff::compare(expected, &actual, tolerance.channel, DiffStyle::Heatmap)
+        })
+        .transpose()?;
+    let matches = diff
+        .as_ref()
+        .is_some_and(|diff| diff.report.within(&tolerance));
+    result.diff = diff.as_ref().map(|diff| diff.report.clone());
+
+    if matches {
+        result.status = SnapshotStatus::Passed;
+        return Ok(());
+    }
+
+    if options.mode == SnapshotMode::Bless {
+        let provenance = RenderProvenance::capture(&case.font, &settings, renderer.shaper());
+        write_png_rgba(golden, &actual, Some(&provenance))?;
+        result.status = SnapshotStatus::Blessed;
+        return Ok(());
+    }
+
+    let stem = case.file_stem();
+    let relative = |kind: &str| PathBuf::from(kind).join(format!("{stem}.png"));
+    let save = |relative: &PathBuf, image: &RenderResult| -> TestypfResult<()> {
+        let path = options.report_dir.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_png_rgba(&path, image, None)
+    };
+
+    let actual_path = relative("actual");
+    save(&actual_path, &actual)?;
+    result.actual_image = Some(actual_path);
+
+    match (expected, diff) {
+        (Some(expected), Some(diff)) => {
+            let expected_path = relative("expected");
+            save(&expected_path, &expected)?;
+            result.expected_image = Some(expected_path);
+
+            let diff_path = relative("diff");
+            save(&diff_path, &diff.image)?;
+            result.diff_image = Some(diff_path);
+            result.status = SnapshotStatus::Failed;
+        }
+        _ => result.status = SnapshotStatus::Missing,
+    }
+    Ok(())
+}
+
+fn read_png_rgba(path: &Path) -> TestypfResult<RenderResult> {
+    let image = image::open(path)
+        .map_err(|e| {
+            TestypfError::RenderFailed(format!("Failed to read golden {}: {e}", path.display()))
+        })?
+        .to_rgba8();
+    Ok(RenderResult {
+        width: image.width(),
+        height: image.height(),
+        data: image.into_raw().into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
+    })
+}
+
+fn write_png_rgba(
+    path: &Path,
+    image: &RenderResult,
+    provenance: Option<&RenderProvenance>,
+) -> TestypfResult<()> {
+    crate::export::write_image(
+        path,
+        ExportFormat::Png,
+        image.width,
+        image.height,
+        &image.data,
+        None,
+        provenance,
+    )
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,521 @@
+use super::*;
+use read_fonts::{FontRef, TableProvider};
+
+/// OS/2 ulUnicodeRange bit names, indexed by bit (bits 123–127 are reserved)
+const UNICODE_RANGES: [&str; 123] = [
+    "Basic Latin",
+    "Latin-1 Supplement",
+    "Latin Extended-A",
+    "Latin Extended-B",
+    "IPA Extensions",
+    "Spacing Modifier Letters",
+    "Combining Diacritical Marks",
+    "Greek and Coptic",
+    "Coptic",
+    "Cyrillic",
+    "Armenian",
+    "Hebrew",
+    "Vai",
+    "Arabic",
+    "NKo",
+    "Devanagari",
+    "Bengali",
+    "Gurmukhi",
+    "Gujarati",
+    "Oriya",
+    "Tamil",
+    "Telugu",
+    "Kannada",
+    "Malayalam",
+    "Thai",
+    "Lao",
+    "Georgian",
+    "Balinese",
+    "Hangul Jamo",
+    "Latin Extended Additional",
+    "Greek Extended",
+    "General Punctuation",
+    "Superscripts And Subscripts",
+    "Currency Symbols",
+    "Combining Diacritical Marks For Symbols",
+    "Letterlike Symbols",
+    "Number Forms",
+    "Arrows",
+    "Mathematical Operators",
+    "Miscellaneous Technical",
+    "Control Pictures",
+    "Optical Character Recognition",
+    "Enclosed Alphanumerics",
+    "Box Drawing",
+    "Block Elements",
+    "Geometric Shapes",
+    "Miscellaneous Symbols",
+    "Dingbats",
+    "CJK Symbols And Punctuation",
+    "Hiragana",
+    "Katakana",
+    "Bopomofo",
+    "Hangul Compatibility Jamo",
+    "Phags-pa",
+    "Enclosed CJK Letters And Months",
+    "CJK Compatibility",
+    "Hangul Syllables",
+    "Non-Plane 0",
+    "Phoenician",
+    "CJK Unified Ideographs",
+    "Private Use Area (plane 0)",
+    "CJK Strokes",
+    "Alphabetic Presentation Forms",
+    "Arabic Presentation Forms-A",
+    "Combining Half Marks",
+    "Vertical Forms",
+    "Small Form Variants",
+    "Arabic Presentation Forms-B",
+    "Halfwidth And Fullwidth Forms",
+    "Specials",
+    "Tibetan",
+    "Syriac",
+    "Thaana",
+    "Sinhala",
+    "Myanmar",
+    "Ethiopic",
+    "Cherokee",
+    "Unified Canadian Aboriginal Syllabics",
+    "Ogham",
+    "Runic",
+    "Khmer",
+    "Mongolian",
+    "Braille Patterns",
+    "Yi Syllables",
+    "Tagalog",
+    "Old Italic",
+    "Gothic",
+    "Deseret",
+    "Byzantine Musical Symbols",
+    "Mathematical Alphanumeric Symbols",
+    "Private Use (plane 15)",
+    "Variation Selectors",
+    "Tags",
+    "Limbu",
+    "Tai Le",
+    "New Tai Lue",
+    "Buginese",
+    "Glagolitic",
+    "Tifinagh",
+    "Yijing Hexagram Symbols",
+    "Syloti Nagri",
+    "Linear B Syllabary",
+    "Ancient Greek Numbers",
+    "Ugaritic",
+    "Old Persian",
+    "Shavian",
+    "Osmanya",
+    "Cypriot Syllabary",
+    "Kharoshthi",
+    "Tai Xuan Jing Symbols",
+    "Cuneiform",
+    "Counting Rod Numerals",
+    "Sundanese",
+    "Lepcha",
+    "Ol Chiki",
+    "Saurashtra",
+    "Kayah Li",
+    "Rejang",
+    "Cham",
+    "Ancient Symbols",
+    "Phaistos Disc",
+    "Carian",
+    "Domino Tiles",
+];
+
+/// fsSelection bit names, indexed by bit
+const FS_SELECTION_FLAGS: [&str; 10] = [
+    "ITALIC",
+    "UNDERSCORE",
+    "NEGATIVE",
+    "OUTLINED",
+    "STRIKEOUT",
+    "BOLD",
+    "REGULAR",
+    "USE_TYPO_METRICS",
+    "WWS",
+    "OBLIQUE",
+];
+
+/// Seconds between the OpenType epoch (1904-01-01) and the Unix epoch
+const MAC_EPOCH_OFFSET: i64 = 2_082_844_800;
+
+/// One entry of the sfnt table directory
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TableRecord {
+    pub tag: String,
+    pub checksum: u32,
+    pub offset: u32,
+    pub length: u32,
+}
+
+/// Decoded `head` table fields
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HeadSummary {
+    pub font_revision: f64,
+    pub units_per_em: u16,
+    pub flags: u16,
+    pub mac_style: u16,
+    /// Creation and modification times in seconds since the Unix epoch
+    pub created: i64,
+    pub modified: i64,
+    pub x_min: i16,
+    pub y_min: i16,
+    pub x_max: i16,
+    pub y_max: i16,
+    pub lowest_rec_ppem: u16,
+    pub index_to_loc_format: i16,
+}
+
+impl HeadSummary {
+    /// Creation date as YYYY-MM-DD (UTC)
+    pub fn created_date(&self) -> String {
+        format_date(self.created)
+    }
+
+    /// Modification date as YYYY-MM-DD (UTC)
+    pub fn modified_date(&self) -> String {
+        format_date(self.modified)
+    }
+}
+
+fn format_date(unix_secs: i64) -> String {
+    let (year, month, day) = crate::export::civil_from_days(unix_secs.div_euclid(86_400));
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Decoded `hhea` table fields
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HheaSummary {
+    pub ascender: i16,
+    pub descender: i16,
+    pub line_gap: i16,
+    pub advance_width_max: u16,
+    pub caret_slope_rise: i16,
+    pub caret_slope_run: i16,
+    pub number_of_h_metrics: u16,
+}
+
+/// Decoded `OS/2` table fields
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Os2Summary {
+    pub version: u16,
+    pub weight_class: u16,
+    pub width_class: u16,
+    pub fs_type: u16,
+    pub fs_selection: u16,
+    pub vendor_id: String,
+    /// ulUnicodeRange1–4 as one 128-bit mask (bit 0 = Basic Latin)
+    pub unicode_ranges: u128,
+}
+
+impl Os2Summary {
+    /// Weight class name, e.g. "Regular" for 400
+    pub fn weight_name(&self) -> &'static str {
+        match self.weight_class {
+            0..=149 => "Thin",
+            150..=249 => "ExtraLight",
+            250..=349 => "Light",
+            350..=449 => "Regular",
+            450..=549 => "Medium",
+            550..=649 => "SemiBold",
+            650..=749 => "Bold",
+            750..=849 => "ExtraBold",
+            _ => "Black",
+        }
+    }
+
+    /// Width class name, e.g. "Normal" for 5
+    pub fn width_name(&self) -> &'static str {
+        match self.width_class {
+            1 => "Ultra-condensed",
+            2 => "Extra-condensed",
+            3 => "Condensed",
+            4 => "Semi-condensed",
+            5 => "Normal",
+            6 => "Semi-expanded",
+            7 => "Expanded",
+            8 => "Extra-expanded",
+            9 => "Ultra-expanded",
+            _ => "Invalid",
+        }
+    }
+
+    /// Embedding permissions described by fsType
+    pub fn fs_type_flags(&self) -> Vec<&'static str> {
+        let mut flags = Vec::new();
+        match self.fs_type & 0x000F {
+            0 => flags.push("Installable"),
+            t if t & 0x0002 != 0 => flags.push("Restricted License"),
+            t if t & 0x0004 != 0 => flags.push("Preview & Print"),
+            t if t & 0x0008 != 0 => flags.push("Editable"),
+            _ => {}
+        }
+        if self.fs_type & 0x0100 != 0 {
+            flags.push("No subsetting");
+        }
+        if self.fs_type & 0x0200 != 0 {
+            flags.push("Bitmap embedding only");
+        }
+        flags
+    }
+
+    /// Names of the set fsSelection bits
+    pub fn fs_selection_flags(&self) -> Vec<&'static str> {
+        FS_SELECTION_FLAGS
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| self.fs_selection & (1 << bit) != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// Names of the Unicode ranges the font claims to cover
+    pub fn unicode_range_names(&self) -> Vec<&'static str> {
+        UNICODE_RANGES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| self.unicode_ranges & (1u128 << bit) != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+/// Decoded `post` table fields
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PostSummary {
+    /// Table version, e.g. "2.0"
+    pub version: String,
+    pub italic_angle: f64,
+    pub underline_position: i16,
+    pub underline_thickness: i16,
+    pub is_fixed_pitch: bool,
+}
+
+/// Decoded `maxp` table fields (profile limits only in version 1.0)
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MaxpSummary {
+    /// "0.5" for CFF fonts, "1.0" for TrueType outlines
+    pub version: String,
+    pub num_glyphs: u16,
+    pub max_points: Option<u16>,
+    pub max_contours: Option<u16>,
+    pub max_component_depth: Option<u16>,
+}
+
+/// One decoded `name` table record
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NameEntry {
+    pub name_id: u16,
+    pub platform_id: u16,
+    pub encoding_id: u16,
+    pub language_id: u16,
+    pub value: String,
+}
+
+impl NameEntry {
+    /// Name ID description, e.g. "Family" for ID 1
+    pub fn name_label(&self) -> String {
+        let label = match self.name_id {
+            0 => "Copyright",
+            1 => "Family",
+            2 => "Subfamily",
+            3 => "Unique ID",
+            4 => "Full name",
+            5 => "Version",
+            6 => "PostScript name",
+            7 => "Trademark",
+            8 => "Manufacturer",
+            9 => "Designer",
+            10 => "Description",
+            11 => "Vendor URL",
+            12 => "Designer URL",
+            13 => "License",
+            14 => "License URL",
+            16 => "Typographic family",
+            17 => "Typographic subfamily",
+            18 => "Compatible full name",
+            19 => "Sample text",
+            20 => "PostScript CID name",
+            21 => "WWS family",
+            22 => "WWS subfamily",
+            23 => "Light background palette",
+            24 => "Dark background palette",
+            25 => "Variations PostScript prefix",
+            id => return format!("Name {id}"),
+        };
+        label.to_string()
+    }
+
+    pub fn platform_label(&self) -> &'static str {
+        match self.platform_id {
+            0 => "Unicode",
+            1 => "Macintosh",
+            2 => "ISO",
+            3 => "Windows",
+            4 => "Custom",
+            _ => "Unknown",
+        }
+    }
+
+    /// Language of the record; common Windows and Macintosh codes are named, others shown in hex
+    pub fn language_label(&self) -> String {
+        let known = match (self.platform_id, self.language_id) {
+            (3, 0x0409) => Some("English (United States)"),
+            (3, 0x0809) => Some("English (United Kingdom)"),
+            (3, 0x0407) => Some("German"),
+            (3, 0x040C) => Some("French"),
+            (3, 0x0410) => Some("Italian"),
+            (3, 0x0C0A) => Some("Spanish"),
+            (3, 0x0413) => Some("Dutch"),
+            (3, 0x0416) => Some("Portuguese (Brazil)"),
+            (3, 0x0419) => Some("Russian"),
+            (3, 0x0415) => Some("Polish"),
+            (3, 0x041D) => Some("Swedish"),
+            (3, 0x0408) => Some("Greek"),
+            (3, 0x041F) => Some("Turkish"),
+            (3, 0x0411) => Some("Japanese"),
+            (3, 0x0412) => Some("Korean"),
+            (3, 0x0804) => Some("Chinese (PRC)"),
+            (3, 0x0404) => Some("Chinese (Taiwan)"),
+            (1, 0) => Some("English"),
+            (1, 1) => Some("French"),
+            (1, 2) => Some("German"),
+            (1, 3) => Some("Italian"),
+            (1, 4) => Some("Dutch"),
+            (1, 6) => Some("Spanish"),
+            (1, 11) => Some("Japanese"),
+            (1, 23) => Some("Korean"),
+            (0, _) => Some("Any"),
+            _ => None,
+        };
+        known
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("0x{:04X}", self.language_id))
+    }
+}
+
+/// Table directory plus decoded summaries of the common tables; missing tables are `None`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FontTables {
+    pub directory: Vec<TableRecord>,
+    pub head: Option<HeadSummary>,
+    pub hhea: Option<HheaSummary>,
+    pub os2: Option<Os2Summary>,
+    pub post: Option<PostSummary>,
+    pub maxp: Option<MaxpSummary>,
+    pub names: Vec<NameEntry>,
+}
+
+/// Read the table directory and decode head, hhea, OS/2, post, maxp and name from one face of a font file.
+pub fn read_font_tables(data: &[u8], face_index: u32) -> TestypfResult<FontTables> {
+    let font = FontRef::from_index(data, face_index)
+        .map_err(|e| TestypfError::InvalidFont(format!("Failed to parse font: {}", e)))?;
+
+    let directory = font
+        .table_directory
+        .table_records()
+        .iter()
+        .map(|record| TableRecord {
+            tag: record.tag().to_string(),
+            checksum: record.checksum(),
+            offset: record.offset(),
+            length: record.length(),
+        })
+        .collect();
+
+    let head = font.head().ok().map(|head| HeadSummary {
+        font_revision: head.font_revision().to_f64(),
+        units_per_em: head.units_per_em(),
+        flags: head.flags().bits(),
+        mac_style: head.mac_style().bits(),
+        created: head.created().as_secs() - MAC_EPOCH_OFFSET,
+        modified: head.modified().as_secs() - MAC_EPOCH_OFFSET,
+        x_min: head.x_min(),
+        y_min: head.y_min(),
+        x_max: head.x_max(),
+        y_max: head.y_max(),
+        lowest_rec_ppem: head.lowest_rec_ppem(),
+        index_to_loc_format: head.index_to_loc_format(),
+    });
+
+    let hhea = font.hhea().ok().map(|hhea| HheaSummary {
+        ascender: hhea.ascender().to_i16(),
+        descender: hhea.descender().to_i16(),
+        line_gap: hhea.line_gap().to_i16(),
+        advance_width_max: hhea.advance_width_max().to_u16(),
+        caret_slope_rise: hhea.caret_slope_rise(),
+        caret_slope_run: hhea.caret_slope_run(),
+        number_of_h_metrics: hhea.number_of_h_metrics(),
+    });
+
+    let os2 = font.os2().ok().map(|os2| Os2Summary {
+        version: os2.version(),
+        weight_class: os2.us_weight_class(),
+        width_class: os2.us_width_class(),
+        fs_type: os2.fs_type(),
+        fs_selection: os2.fs_selection().bits(),
+        vendor_id: os2.ach_vend_id().to_string(),
+        unicode_ranges: u128::from(os2.ul_unicode_range_1())
+            | u128::from(os2.ul_unicode_range_2()) << 32
+            | u128::from(os2.ul_unicode_range_3()) << 64
+            | u128::from(os2.ul_unicode_range_4()) << 96,
+    });
+
+    let post = font.post().ok().map(|post| {
+        let (major, minor) = post.version().to_major_minor();
+        PostSummary {
+            version: format!("{major}.{minor}"),
+            italic_angle: post.italic_angle().to_f64(),
+            underline_position: post.underline_position().to_i16(),
+            underline_thickness: post.underline_thickness().to_i16(),
+            is_fixed_pitch: post.is_fixed_pitch() != 0,
+        }
+    });
+
+    let maxp = font.maxp().ok().map(|maxp| {
+        let (major, minor) = maxp.version().to_major_minor();
+        MaxpSummary {
+            version: format!("{major}.{minor}"),
+            num_glyphs: maxp.num_glyphs(),
+            max_points: maxp.max_points(),
+            max_contours: maxp.max_contours(),
+            max_component_depth: maxp.max_component_depth(),
+        }
+    });
+
+    let names = font
+        .name()
+        .map(|name| {
+            let strings = name.string_data();
+            name.name_record()
+                .iter()
+                .filter_map(|record| {
+                    let value = record.string(strings).ok()?.to_string();
+                    Some(NameEntry {
+                        name_id: record.name_id().to_u16(),
+                        platform_id: record.platform_id(),
+                        encoding_id: record.encoding_id(),
+                        language_id: record.language_id(),
+                        value,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(FontTables {
+        directory,
+        head,
+        hhea,
+        os2,
+        post,
+        maxp,
+        names,
+    })
+}
@@ -1,6 +1,6 @@
 use super::*;
 use crate::font::FontListManager;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::sync::{Arc, Mutex};
 
 #[derive(Default)]
@@ -94,6 +94,7 @@ fn sample_font_info(path: PathBuf) -> TestypfFontInfo {
         style: "Regular".to_string(),
         is_installed: false,
         variation_axes: Vec::new(),
+        vertical_metrics: None,
     }
 }
 
@@ -166,3 +167,1866 @@ fn render_settings_variation_coords_default_to_empty() {
     let settings = RenderSettings::default();
     assert!(settings.variation_coords.is_empty());
 }
+
+const SAMPLE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10" viewBox="0 0 20 10"><path d="M0 0H10V10H0Z" fill="black"/></svg>"#;
+
+#[test]
+fn svg_output_is_detected_and_sized_from_canvas() {
+    assert!(vector::looks_like_svg(SAMPLE_SVG));
+    assert!(!vector::looks_like_svg(r#"{"glyphs": []}"#));
+
+    let result = vector::svg_result(SAMPLE_SVG.to_string()).expect("valid svg");
+    assert!(result.is_vector());
+    assert_eq!((result.width, result.height), (20, 10));
+    assert_eq!(result.svg_markup(), Some(SAMPLE_SVG));
+}
+
+#[test]
+fn svg_rasterizes_to_straight_rgba() {
+    let result = vector::svg_result(SAMPLE_SVG.to_string()).expect("valid svg");
+    let raster = vector::rasterize(&result, 2.0).expect("rasterize");
+
+    assert_eq!((raster.width, raster.height), (40, 20));
+    assert_eq!(raster.data.len(), 40 * 20 * 4);
+    assert!(!raster.is_vector());
+    // Left half is filled black, right half stays transparent
+    assert_eq!(&raster.data[0..4], &[0, 0, 0, 255]);
+    let right = ((raster.width - 1) * 4) as usize;
+    assert_eq!(raster.data[right + 3], 0);
+}
+
+#[test]
+fn exported_png_round_trips_render_provenance() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let font_path = dir.path().join("font.ttf");
+    std::fs::write(&font_path, b"font bytes").unwrap();
+    let image_path = dir.path().join("render.png");
+
+    let mut settings = RenderSettings {
+        font_size: 42.0,
+        background_color: Some((255, 255, 255, 255)),
+        ..RenderSettings::default()
+    };
+    settings.variation_coords.insert("wght".into(), 650.0);
+    let provenance = export::RenderProvenance::capture(&font_path, &settings, "harfbuzz");
+
+    export::write_png(&image_path, 1, 1, &[0, 0, 0, 255], Some(&provenance)).expect("write");
+
+    let restored = export::read_provenance(&image_path).expect("read provenance");
+    assert_eq!(restored, provenance);
+    assert_eq!(restored.settings, settings);
+    assert_eq!(restored.font_matches(), Some(true));
+    assert_eq!(
+        export::read_png_text(&image_path, "testypf:size").unwrap(),
+        Some("42".to_string())
+    );
+
+    std::fs::write(&font_path, b"edited font").unwrap();
+    assert_eq!(restored.font_matches(), Some(false));
+}
+
+#[test]
+fn provenance_falls_back_to_json_sidecar() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let image_path = dir.path().join("plain.png");
+    export::write_png(&image_path, 1, 1, &[0, 0, 0, 0], None).expect("write");
+    assert!(export::read_provenance(&image_path).is_err());
+
+    let provenance = export::RenderProvenance::capture(
+        &dir.path().join("missing.ttf"),
+        &RenderSettings::default(),
+        "harfbuzz",
+    );
+    let sidecar = export::write_sidecar(&image_path, &provenance).expect("sidecar");
+    assert_eq!(sidecar, dir.path().join("plain.json"));
+
+    let restored = export::read_provenance(&image_path).expect("sidecar provenance");
+    assert_eq!(restored.font_sha256, None);
+    assert_eq!(restored.settings, RenderSettings::default());
+}
+
+#[test]
+fn export_name_template_expands_placeholders() {
+    let font = sample_font_info(PathBuf::from("/fonts/Dummy-Regular.ttf"));
+    let mut settings = RenderSettings {
+        font_size: 24.0,
+        ..RenderSettings::default()
+    };
+    settings.variation_coords.insert("wght".into(), 700.0);
+    settings.variation_coords.insert("opsz".into(), 12.0);
+
+    let mut fields = export::NameFields::new(&font, &settings, 3);
+    fields.date = "2024-05-01".to_string();
+
+    let stem = export::render_name_template(
+        "{family}-{style}_{ps}_{size}pt_{backend}_{axes}_{index}_{date}",
+        &fields,
+    )
+    .expect("template");
+    assert_eq!(
+        stem,
+        format!(
+            "Dummy-Regular_DummyPS_24pt_{}_opsz12_wght700_03_2024-05-01",
+            settings.backend
+        )
+    );
+    assert_eq!(
+        export::render_name_template(export::DEFAULT_NAME_TEMPLATE, &fields).unwrap(),
+        "03_dummy_font"
+    );
+
+    assert!(export::validate_name_template("{family}_{weight}").is_err());
+    assert!(export::validate_name_template("{family").is_err());
+    assert!(export::validate_name_template("{ps}_{date}").is_ok());
+}
+
+#[test]
+fn jpeg_export_is_flattened_onto_background() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let rgba = [0, 0, 0, 0, 0, 0, 0, 255];
+
+    let jpeg = dir.path().join("render.jpg");
+    export::write_image(&jpeg, export::ExportFormat::Jpeg, 2, 1, &rgba, None, None).expect("jpeg");
+    let decoded = image::open(&jpeg).expect("decode jpeg").to_rgb8();
+    assert!(
+        decoded.get_pixel(0, 0)[0] > 200,
+        "transparent pixel becomes white"
+    );
+
+    let webp = dir.path().join("render.webp");
+    export::write_image(
+        &webp,
+        export::ExportFormat::WebP,
+        2,
+        1,
+        &rgba,
+        Some((255, 0, 0)),
+        None,
+    )
+    .expect("webp");
+    let decoded = image::open(&webp).expect("decode webp").to_rgba8();
+    assert_eq!(decoded.get_pixel(0, 0).0, [255, 0, 0, 255]);
+    assert_eq!(decoded.get_pixel(1, 0).0, [0, 0, 0, 255]);
+
+    let tiff = dir.path().join("render.tiff");
+    export::write_image(&tiff, export::ExportFormat::Tiff, 2, 1, &rgba, None, None).expect("tiff");
+    let decoded = image::open(&tiff).expect("decode tiff").to_rgba8();
+    assert_eq!(decoded.get_pixel(0, 0).0[3], 0, "TIFF keeps alpha");
+}
+
+#[test]
+fn contact_sheet_tiles_cells_with_captions() {
+    let red = RenderResult {
+        width: 10,
+        height: 4,
+        data: [255, 0, 0, 255].repeat(40).into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+    let cells: Vec<_> = (0..3)
+        .map(|i| contact_sheet::ContactSheetCell {
+            image: &red,
+            caption: format!("Font {i} | Dimensions: 10x4"),
+        })
+        .collect();
+    let options = contact_sheet::ContactSheetOptions {
+        columns: 2,
+        gutter: 5,
+        margin: 3,
+        min_cell_width: 200,
+        ..contact_sheet::ContactSheetOptions::default()
+    };
+
+    let sheet = contact_sheet::compose(&cells, &options).expect("compose");
+    // 2 columns of 200px, 2 rows of 4px image + 4px gap + one 9px caption line
+    assert_eq!(sheet.width, 3 * 2 + 200 * 2 + 5);
+    assert_eq!(sheet.height, 3 * 2 + (4 + 4 + 9) * 2 + 5);
+    assert_eq!(sheet.data.len(), (sheet.width * sheet.height * 4) as usize);
+
+    let pixel = |x: u32, y: u32| {
+        let offset = ((y * sheet.width + x) * 4) as usize;
+        &sheet.data[offset..offset + 4]
+    };
+    assert_eq!(
+        pixel(0, 0),
+        &[255, 255, 255, 255],
+        "margin shows background"
+    );
+    assert_eq!(pixel(3, 3), &[255, 0, 0, 255], "first cell image");
+    assert_eq!(
+        pixel(3 + 200 + 5, 3),
+        &[255, 0, 0, 255],
+        "second column image"
+    );
+    assert_eq!(pixel(3, 3 + 17 + 5), &[255, 0, 0, 255], "second row image");
+
+    let caption_rows = (3 + 4 + 4..3 + 4 + 4 + 9)
+        .flat_map(|y| (3..203).map(move |x| (x, y)))
+        .filter(|&(x, y)| pixel(x, y) == [64, 64, 64, 255])
+        .count();
+    assert!(caption_rows > 0, "caption pixels are drawn");
+
+    let hidden = contact_sheet::compose(
+        &cells,
+        &contact_sheet::ContactSheetOptions {
+            show_captions: false,
+            ..options.clone()
+        },
+    )
+    .expect("compose without captions");
+    assert_eq!(hidden.height, 3 * 2 + 4 * 2 + 5);
+
+    let headed = contact_sheet::compose(
+        &cells,
+        &contact_sheet::ContactSheetOptions {
+            show_captions: false,
+            column_headers: vec!["Opixa".into(), "Skia".into()],
+            ..options
+        },
+    )
+    .expect("compose with column headers");
+    // One 9px header line plus a gutter above the grid
+    assert_eq!(headed.height, hidden.height + 9 + 5);
+    let header_pixels = (3..3 + 9)
+        .flat_map(|y| (3 + 200 + 5..3 + 400 + 5).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            let offset = ((y * headed.width + x) * 4) as usize;
+            headed.data[offset..offset + 4] == [64, 64, 64, 255]
+        })
+        .count();
+    assert!(header_pixels > 0, "second column header is drawn");
+}
+
+#[test]
+fn scale_factor_multiplies_device_size_and_padding() {
+    let settings = RenderSettings {
+        font_size: 24.0,
+        padding: 10,
+        ..RenderSettings::default()
+    };
+    assert_eq!(settings.scale_factor, 1.0);
+    assert_eq!(settings.device_font_size(), 24.0);
+
+    let retina = settings.at_scale(2.0);
+    assert_eq!(retina.device_font_size(), 48.0);
+    assert_eq!(retina.device_padding(), 20);
+    assert_eq!(retina.font_size, 24.0, "logical size is unchanged");
+
+    assert_eq!(settings.at_scale(0.0).device_font_size(), 24.0);
+
+    // Settings saved before scale_factor existed load as 1x
+    let mut json = serde_json::to_value(&settings).unwrap();
+    json.as_object_mut().unwrap().remove("scale_factor");
+    let restored: RenderSettings = serde_json::from_value(json).unwrap();
+    assert_eq!(restored.scale_factor, 1.0);
+}
+
+#[test]
+fn raster_options_are_restricted_to_backend_capabilities() {
+    let options = RasterOptions {
+        antialias: AntialiasMode::Lcd,
+        hinting: HintingMode::Full,
+        gamma: 2.2,
+    };
+
+    let opixa = RendererBackend::Orge.raster_capabilities();
+    let restricted = options.restricted_to(opixa);
+    assert_eq!(restricted.antialias, AntialiasMode::Grayscale);
+    assert_eq!(restricted.hinting, HintingMode::None);
+    assert_eq!(restricted.gamma, 2.2);
+    assert_eq!(
+        options.unsupported_by(opixa),
+        vec!["subpixel LCD", "hinting"]
+    );
+    assert_eq!(opixa.missing(), vec!["subpixel LCD", "hinting"]);
+
+    let svg = RendererBackend::Svg.raster_capabilities();
+    assert_eq!(options.restricted_to(svg), RasterOptions::default());
+    assert!(RasterOptions::default().unsupported_by(svg).is_empty());
+
+    let aliased = RasterOptions {
+        antialias: AntialiasMode::None,
+        ..RasterOptions::default()
+    };
+    assert_eq!(aliased.restricted_to(opixa), aliased);
+    assert_eq!(aliased.unsupported_by(svg), vec!["antialiasing off"]);
+
+    assert!(RendererBackend::Orge.is_raster());
+    assert!(!RendererBackend::Svg.is_raster());
+    assert!(!RendererBackend::Json.is_raster());
+}
+
+#[test]
+fn pixel_formats_convert_to_straight_rgba() {
+    let convert = |format: PixelFormat, width: u32, data: Vec<u8>| {
+        RenderResult {
+            width,
+            height: 1,
+            data: data.into(),
+            format,
+            glyphs: Vec::new(),
+            baseline: None,
+        }
+        .to_rgba8()
+        .expect("convert")
+        .data
+        .to_vec()
+    };
+
+    assert_eq!(
+        convert(PixelFormat::Gray8, 2, vec![0, 200]),
+        [0, 0, 0, 255, 200, 200, 200, 255]
+    );
+    assert_eq!(
+        convert(PixelFormat::GrayAlpha8, 1, vec![90, 128]),
+        [90, 90, 90, 128]
+    );
+    assert_eq!(
+        convert(PixelFormat::Bgra8, 1, vec![1, 2, 3, 4]),
+        [3, 2, 1, 4]
+    );
+    assert_eq!(
+        convert(
+            PixelFormat::Rgba8Premultiplied,
+            2,
+            vec![64, 0, 128, 128, 9, 9, 9, 0]
+        ),
+        [128, 0, 255, 128, 0, 0, 0, 0]
+    );
+    assert_eq!(
+        convert(PixelFormat::Bgra8Premultiplied, 1, vec![0, 0, 255, 255]),
+        [255, 0, 0, 255]
+    );
+    assert_eq!(
+        convert(PixelFormat::LcdBgr8, 1, vec![10, 20, 30]),
+        [30, 20, 10, 255]
+    );
+
+    let short = RenderResult {
+        width: 2,
+        height: 2,
+        data: vec![0; 3].into(),
+        format: PixelFormat::Gray8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+    assert!(short.to_rgba8().is_err());
+}
+
+#[test]
+fn typf_format_names_are_parsed_or_inferred() {
+    assert_eq!(PixelFormat::from_typf("RGBA8", 1, 1, 4), PixelFormat::Rgba8);
+    assert_eq!(
+        PixelFormat::from_typf("bgra8_premultiplied", 1, 1, 4),
+        PixelFormat::Bgra8Premultiplied
+    );
+    assert_eq!(PixelFormat::from_typf("Gray8", 1, 1, 1), PixelFormat::Gray8);
+    assert_eq!(
+        PixelFormat::from_typf("gray_alpha8", 1, 1, 2),
+        PixelFormat::GrayAlpha8
+    );
+    assert_eq!(
+        PixelFormat::from_typf("lcd-bgr", 1, 1, 3),
+        PixelFormat::LcdBgr8
+    );
+    assert_eq!(
+        PixelFormat::from_typf("unknown", 4, 2, 8),
+        PixelFormat::Gray8
+    );
+    assert_eq!(
+        PixelFormat::from_typf("unknown", 4, 2, 24),
+        PixelFormat::LcdRgb8
+    );
+    assert_eq!(
+        PixelFormat::from_typf("unknown", 4, 2, 32),
+        PixelFormat::Rgba8
+    );
+}
+
+#[test]
+fn rgba_conversion_shares_the_pixel_buffer() {
+    let result = RenderResult {
+        width: 1,
+        height: 1,
+        data: vec![1, 2, 3, 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+
+    let rgba = result.to_rgba8().expect("convert");
+    assert!(rgba.data.ptr_eq(&result.data));
+
+    let gray = RenderResult {
+        width: 2,
+        height: 1,
+        data: vec![0, 0].into(),
+        format: PixelFormat::Gray8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+    let converted = gray.to_rgba8().expect("convert");
+    assert!(!converted.data.ptr_eq(&gray.data));
+
+    let buffers = [result.data.clone(), rgba.data, converted.data];
+    assert_eq!(
+        pixels::unique_bytes(&buffers),
+        4 + 8,
+        "shared buffers count once"
+    );
+}
+
+/// What [`MockRenderer`] returns from `render_text`
+#[derive(Debug, Clone, Copy, Default)]
+enum MockOutput {
+    /// A transparent 100x10 RGBA bitmap
+    #[default]
+    Blank,
+    /// typf JSON with one glyph per char (gid = code point - 64, `font_size * 25` wide);
+    /// the backend must be set to Json
+    Shaping,
+    /// One opaque 2-pixel column per codepoint of the text it would shape
+    Codepoints,
+}
+
+/// Stand-in renderer: records every call with its backend and fails for `broken.ttf`
+#[derive(Default)]
+struct MockRenderer {
+    output: MockOutput,
+    backend: Option<RendererBackend>,
+    calls: Mutex<Vec<(PathBuf, RendererBackend)>>,
+}
+
+impl MockRenderer {
+    fn new(output: MockOutput) -> Self {
+        Self {
+            output,
+            ..Self::default()
+        }
+    }
+}
+
+impl TextRenderer for MockRenderer {
+    fn render_text(
+        &self,
+        font_path: &PathBuf,
+        settings: &RenderSettings,
+    ) -> TestypfResult<RenderResult> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push((font_path.clone(), self.backend()));
+        if font_path.ends_with("broken.ttf") {
+            return Err(TestypfError::RenderFailed("broken font".into()));
+        }
+        match self.output {
+            MockOutput::Blank => Ok(RenderResult {
+                width: 100,
+                height: 10,
+                data: vec![0; 100 * 10 * 4].into(),
+                format: PixelFormat::Rgba8,
+                glyphs: Vec::new(),
+                baseline: None,
+            }),
+            MockOutput::Shaping => {
+                assert_eq!(self.backend, Some(RendererBackend::Json));
+                let glyphs: Vec<String> = settings
+                    .sample_text
+                    .char_indices()
+                    .map(|(cluster, c)| {
+                        format!(
+                            r#"{{"glyph_id": {}, "cluster": {cluster}, "x_advance": {}, "y_advance": 0, "x_offset": 0, "y_offset": 0}}"#,
+                            c as u32 - 64,
+                            settings.font_size * 25.0
+                        )
+                    })
+                    .collect();
+                let json = format!(
+                    r#"{{"direction": "ltr", "glyphs": [{}]}}"#,
+                    glyphs.join(",")
+                );
+                Ok(RenderResult {
+                    width: 0,
+                    height: 0,
+                    data: json.into_bytes().into(),
+                    format: PixelFormat::Json,
+                    glyphs: Vec::new(),
+                    baseline: None,
+                })
+            }
+            MockOutput::Codepoints => {
+                let width = settings.text_for_font(font_path).chars().count() as u32;
+                Ok(RenderResult {
+                    width,
+                    height: 2,
+                    data: vec![255; width as usize * 2 * 4].into(),
+                    format: PixelFormat::Rgba8,
+                    glyphs: Vec::new(),
+                    baseline: None,
+                })
+            }
+        }
+    }
+
+    fn get_backends(&self) -> Vec<RendererBackend> {
+        vec![
+            RendererBackend::Orge,
+            RendererBackend::Json,
+            RendererBackend::Svg,
+        ]
+    }
+
+    fn set_backend(&mut self, backend: RendererBackend) -> TestypfResult<()> {
+        self.backend = Some(backend);
+        Ok(())
+    }
+
+    fn backend(&self) -> RendererBackend {
+        self.backend.clone().unwrap_or(RendererBackend::Orge)
+    }
+
+    fn shaper(&self) -> &str {
+        "mock"
+    }
+}
+
+#[test]
+fn benchmark_runs_warmup_and_iterations_per_case() {
+    let mut renderer = MockRenderer {
+        backend: Some(RendererBackend::Json),
+        ..MockRenderer::default()
+    };
+    let case = |name: &str, backend: RendererBackend| bench::BenchmarkCase {
+        font_name: name.to_string(),
+        font_path: PathBuf::from(format!("/fonts/{name}.ttf")),
+        settings: RenderSettings {
+            backend,
+            ..RenderSettings::default()
+        },
+    };
+    let cases = vec![
+        case("good", RendererBackend::Orge),
+        case("good", RendererBackend::Svg),
+        case("broken", RendererBackend::Orge),
+    ];
+    let config = bench::BenchmarkConfig {
+        warmup: 1,
+        iterations: 4,
+    };
+
+    let mut progress = Vec::new();
+    let results = bench::run_benchmark(&mut renderer, &cases, config, |done, total| {
+        progress.push((done, total))
+    });
+
+    assert_eq!(progress, vec![(1, 3), (2, 3), (3, 3)]);
+    let calls = renderer.calls.lock().unwrap();
+    assert_eq!(
+        calls.len(),
+        5 + 5 + 1,
+        "broken case stops at its first failure"
+    );
+    assert_eq!(
+        calls[5].1,
+        RendererBackend::Svg,
+        "backend switched per case"
+    );
+    assert_eq!(
+        renderer.backend(),
+        RendererBackend::Json,
+        "previous backend restored"
+    );
+
+    let ok = &results[0];
+    assert_eq!(ok.iterations, 4);
+    assert!(ok.min_ms <= ok.median_ms && ok.median_ms <= ok.p95_ms);
+    assert!(ok.renders_per_sec > 0.0);
+    assert_eq!(results[1].backend, "Svg");
+    assert_eq!(results[2].iterations, 0);
+    assert!(results[2].error.as_deref().unwrap().contains("broken font"));
+    #[cfg(target_os = "linux")]
+    assert!(ok.peak_rss_bytes.unwrap() > 0);
+
+    let csv = bench::to_csv(&results);
+    let mut lines = csv.lines();
+    assert!(lines.next().unwrap().starts_with("font,font_path,backend,"));
+    assert!(lines
+        .next()
+        .unwrap()
+        .starts_with("good,/fonts/good.ttf,Opixa,16,1,4,"));
+    assert_eq!(csv.lines().count(), 4);
+
+    let json: Vec<bench::BenchmarkStats> =
+        serde_json::from_str(&bench::to_json(&results).unwrap()).unwrap();
+    assert_eq!(json.len(), results.len());
+    assert_eq!(json[2].error, results[2].error);
+    assert_eq!(json[0].iterations, results[0].iterations);
+}
+
+#[test]
+fn benchmark_percentiles_use_nearest_rank() {
+    let samples: Vec<f64> = (1..=20).map(f64::from).collect();
+    assert_eq!(bench::percentile(&samples, 50.0), 10.0);
+    assert_eq!(bench::percentile(&samples, 95.0), 19.0);
+    assert_eq!(bench::percentile(&samples, 100.0), 20.0);
+    assert_eq!(bench::percentile(&[], 95.0), 0.0);
+    assert_eq!(bench::csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+}
+
+#[test]
+fn pixel_diff_reports_changes_bounds_and_scores() {
+    let solid = |w: u32, h: u32, value: u8| RenderResult {
+        width: w,
+        height: h,
+        data: vec![value; (w * h * 4) as usize].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+    let base = solid(16, 16, 200);
+
+    let same = diff::compare(&base, &base.clone(), 0, diff::DiffStyle::Heatmap).unwrap();
+    assert_eq!(same.report.changed_pixels, 0);
+    assert_eq!(same.report.psnr_db, None);
+    assert!((same.report.ssim - 1.0).abs() < 1e-9);
+    assert_eq!(same.report.bounds, None);
+
+    let mut bytes = base.data.to_vec();
+    for (x, y, delta) in [(3u32, 4u32, 2u8), (10, 12, 40)] {
+        let offset = ((y * 16 + x) * 4) as usize;
+        bytes[offset] += delta;
+    }
+    let changed = RenderResult {
+        data: bytes.into(),
+        ..base.clone()
+    };
+
+    let strict = diff::compare(&base, &changed, 0, diff::DiffStyle::Heatmap).unwrap();
+    assert_eq!(strict.report.changed_pixels, 2);
+    assert_eq!(strict.report.max_channel_delta, 40);
+    assert_eq!(
+        strict.report.bounds,
+        Some(diff::DiffBounds {
+            x: 3,
+            y: 4,
+            width: 8,
+            height: 9
+        })
+    );
+    assert!(strict.report.psnr_db.unwrap() > 30.0);
+    assert!(strict.report.ssim < 1.0);
+    assert_eq!(strict.image.width, 16);
+    let heat = ((12 * 16 + 10) * 4) as usize;
+    assert_eq!(&strict.image.data[heat..heat + 4], &[255, 40, 0, 255]);
+
+    let lenient = diff::compare(&base, &changed, 2, diff::DiffStyle::Overlay).unwrap();
+    assert_eq!(
+        lenient.report.changed_pixels, 1,
+        "deltas within tolerance are ignored"
+    );
+    assert_eq!(lenient.report.channel_tolerance, 2);
+    assert!(lenient.report.within(&diff::DiffTolerance {
+        channel: 2,
+        max_changed_pixels: 1,
+    }));
+    assert!(
+        !lenient.report.within(&diff::DiffTolerance {
+            channel: 0,
+            max_changed_pixels: 1,
+        }),
+        "a different channel tolerance never matches, and does not panic"
+    );
+
+    let tolerance = diff::DiffTolerance {
+        channel: 2,
+        max_changed_pixels: 1,
+    };
+    assert!(diff::assert_renders_match(&base, &changed, &tolerance).is_ok());
+    let err =
+        diff::assert_renders_match(&base, &changed, &diff::DiffTolerance::default()).unwrap_err();
+    assert!(matches!(err, TestypfError::RenderMismatch(_)));
+    assert!(err.to_string().contains("2 of 256 pixels changed"));
+
+    let larger = diff::compare(&base, &solid(16, 18, 200), 0, diff::DiffStyle::Heatmap).unwrap();
+    assert!(larger.report.size_mismatch);
+    assert_eq!(larger.report.height, 18);
+    assert_eq!(
+        larger.report.changed_pixels, 32,
+        "missing rows count as changed"
+    );
+}
+
+#[test]
+fn snapshot_suite_blesses_checks_and_reports_failures() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let manifest_path = dir.path().join("manifest.json");
+    std::fs::write(
+        &manifest_path,
+        r#"{
+            "tolerance": { "channel": 1, "max_changed_pixels": 0 },
+            "cases": [
+                { "name": "hello", "font": "good.ttf", "text": "Hello", "backend": "Orge" },
+                { "name": "world svg", "font": "good.ttf", "text": "World", "backend": "Svg", "font_size": 32 },
+                { "name": "broken", "font": "broken.ttf", "text": "x", "backend": "Orge" }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let manifest = snapshot::SnapshotManifest::load(&manifest_path).expect("manifest");
+    assert_eq!(manifest.cases[0].font, dir.path().join("good.ttf"));
+    let settings = manifest.cases[1].render_settings();
+    assert_eq!(settings.sample_text, "World");
+    assert_eq!(settings.backend, RendererBackend::Svg);
+    assert_eq!(settings.font_size, 32.0);
+
+    let mut renderer = MockRenderer {
+        backend: Some(RendererBackend::Json),
+        ..MockRenderer::default()
+    };
+    let options = |mode| snapshot::SnapshotOptions {
+        golden_dir: dir.path().join("golden"),
+        report_dir: dir.path().join("report"),
+        mode,
+    };
+
+    let blessed = snapshot::run_snapshots(
+        &mut renderer,
+        &manifest,
+        &options(snapshot::SnapshotMode::Bless),
+    )
+    .expect("bless run");
+    let statuses: Vec<_> = blessed.results.iter().map(|r| r.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            snapshot::SnapshotStatus::Blessed,
+            snapshot::SnapshotStatus::Blessed,
+            snapshot::SnapshotStatus::Error
+        ]
+    );
+    assert!(dir.path().join("golden/world_svg.png").exists());
+    assert_eq!(
+        renderer.backend,
+        Some(RendererBackend::Json),
+        "the backend active before the run is restored"
+    );
+
+    let again = snapshot::run_snapshots(
+        &mut renderer,
+        &manifest,
+        &options(snapshot::SnapshotMode::Bless),
+    )
+    .unwrap();
+    assert_eq!(
+        again.results[0].status,
+        snapshot::SnapshotStatus::Passed,
+        "matching goldens are left alone"
+    );
+
+    std::fs::remove_file(dir.path().join("golden/hello.png")).unwrap();
+    export::write_image(
+        &dir.path().join("golden/world_svg.png"),
+        export::ExportFormat::Png,
+        100,
+        10,
+        &vec![255; 100 * 10 * 4],
+        None,
+        None,
+    )
+    .unwrap();
+
+    let checked = snapshot::run_snapshots(
+        &mut renderer,
+        &manifest,
+        &options(snapshot::SnapshotMode::Check),
+    )
+    .unwrap();
+    assert!(!checked.is_success());
+    assert_eq!(
+        checked.summary(),
+        "0 passed, 1 failed, 1 missing, 0 blessed, 1 errors"
+    );
+    let failed = &checked.results[1];
+    assert_eq!(failed.status, snapshot::SnapshotStatus::Failed);
+    assert_eq!(failed.diff.as_ref().unwrap().changed_pixels, 1000);
+    for image in [
+        &failed.expected_image,
+        &failed.actual_image,
+        &failed.diff_image,
+    ] {
+        assert!(dir
+            .path()
+            .join("report")
+            .join(image.as_ref().unwrap())
+            .exists());
+    }
+    assert!(
+        !dir.path().join("golden/hello.png").exists(),
+        "check mode never writes goldens"
+    );
+
+    let html = std::fs::read_to_string(dir.path().join("report/report.html")).unwrap();
+    assert!(html.contains("diff/world_svg.png"));
+    assert!(html.contains("broken font"));
+    let json: snapshot::SnapshotReport = serde_json::from_str(
+        &std::fs::read_to_string(dir.path().join("report/report.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(json.results.len(), 3);
+
+    let duplicate = snapshot::SnapshotManifest {
+        cases: vec![manifest.cases[0].clone(), manifest.cases[0].clone()],
+        ..manifest
+    };
+    assert!(duplicate.validate().is_err());
+}
+
+#[test]
+fn typf_json_glyph_runs_are_parsed_tolerantly() {
+    let hb_style = r#"[{"g": 36, "cl": 0, "dx": 0, "dy": 0, "ax": 1200.5, "ay": 0},
+                       {"g": 72, "cl": 1, "dx": -20, "dy": 15, "ax": 600, "ay": 0}]"#;
+    let nested = r#"{"font": "x.ttf", "features": [], "result": {"runs": [{"glyphs": [
+                       {"gid": 36, "cluster": 0, "advance": 1200.5},
+                       {"gid": 72, "cluster": 1, "x_offset": -20, "y_offset": 15, "x_advance": 600}]}]}}"#;
+
+    let expected = vec![
+        shaping::ShapedGlyph {
+            glyph_id: 36,
+            cluster: 0,
+            x_advance: 1200.5,
+            ..Default::default()
+        },
+        shaping::ShapedGlyph {
+            glyph_id: 72,
+            cluster: 1,
+            x_advance: 600.0,
+            x_offset: -20.0,
+            y_offset: 15.0,
+            ..Default::default()
+        },
+    ];
+    assert_eq!(shaping::parse_typf_json(hb_style).unwrap(), expected);
+    assert_eq!(shaping::parse_typf_json(nested).unwrap(), expected);
+    let placements = overlay::parse_glyph_placements(hb_style).unwrap();
+    assert_eq!(
+        placements
+            .iter()
+            .map(|p| (p.glyph_id, p.cluster, p.x_advance))
+            .collect::<Vec<_>>(),
+        vec![(36, 0, 1200.5), (72, 1, 600.0)],
+        "overlay reads the same glyph keys as shaping"
+    );
+    assert!(shaping::parse_typf_json(r#"{"width": 3}"#).is_err());
+    assert!(shaping::parse_typf_json("not json").is_err());
+
+    let run = shaping::format_glyph_run(&expected);
+    assert_eq!(run, "[36=0+1200.5|72=1@-20,15+600]");
+    assert_eq!(shaping::parse_glyph_run(&run).unwrap(), expected);
+    assert_eq!(shaping::parse_glyph_run("[]").unwrap(), Vec::new());
+    assert!(shaping::parse_glyph_run("36=0+10").is_err());
+}
+
+#[test]
+fn shaping_tests_report_mismatches_per_string() {
+    let file = "\
+# font;options;text;expected
+good.ttf;--font-size=20;U+0041,U+0042;[1=0+500|2=1+500]
+good.ttf;;AC;[1=0+500|2=1+500]
+good.ttf;--font-size=40 --variations=wght=700;A;[1=0+500]
+broken.ttf;;A;[1=0+500]
+";
+    let dir = std::path::Path::new("/fonts");
+    let tests = shaping::parse_shaping_tests(file, dir).expect("parse");
+    assert_eq!(tests.len(), 4);
+    assert_eq!(tests[0].line, 2);
+    assert_eq!(tests[0].text, "AB");
+    assert_eq!(tests[0].font, dir.join("good.ttf"));
+    assert_eq!(tests[2].variations.get("wght"), Some(&700.0));
+
+    let literal = shaping::parse_shaping_tests(r"good.ttf;;/A \u{42}\\;[]", dir).expect("parse");
+    let settings = literal[0].settings(&RenderSettings {
+        normalization: normalization::Normalization::Nfkc,
+        ..RenderSettings::default()
+    });
+    assert_eq!(settings.normalization, normalization::Normalization::None);
+    assert_eq!(
+        settings.text_for_font(&literal[0].font),
+        r"/A \u{42}\\",
+        "shaping test text bypasses the escape decoder"
+    );
+
+    let err = shaping::parse_shaping_tests("a.ttf;--bogus;A;[]", dir).unwrap_err();
+    assert!(err.to_string().contains("line 1"));
+
+    let mut renderer = MockRenderer::new(MockOutput::Shaping);
+    let base = RenderSettings::default();
+    let report = shaping::run_shaping_tests(&mut renderer, &tests, &base);
+
+    assert!(!report.is_success());
+    assert_eq!(report.passed(), 1);
+    assert!(report.results[0].passed());
+    assert_eq!(
+        report.results[1].mismatches,
+        vec![
+            "glyph 0: expected x advance 500, got 400",
+            "glyph 1: expected gid 2, got 3",
+            "glyph 1: expected x advance 500, got 400",
+        ]
+    );
+    assert_eq!(
+        report.results[2].mismatches,
+        vec!["glyph 0: expected x advance 500, got 1000".to_string()]
+    );
+    assert_eq!(report.results[2].actual.as_deref(), Some("[1=0+1000]"));
+    assert!(report.results[3]
+        .error
+        .as_deref()
+        .unwrap()
+        .contains("broken font"));
+
+    let summary = report.summary();
+    assert!(summary.starts_with("1 of 4 shaping tests passed"));
+    assert!(summary.contains("FAIL line 3"));
+    assert!(summary.contains("glyph 1: expected gid 2, got 3"));
+}
+
+/// Assemble a minimal sfnt (TrueType) file from raw tables, sorted by tag
+fn build_sfnt(tables: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+    let mut tables = tables.to_vec();
+    tables.sort_by_key(|(tag, _)| **tag);
+    let count = tables.len() as u16;
+    let entry_selector = 15 - count.max(1).leading_zeros() as u16;
+    let search_range = (1u16 << entry_selector) * 16;
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+    out.extend_from_slice(&count.to_be_bytes());
+    out.extend_from_slice(&search_range.to_be_bytes());
+    out.extend_from_slice(&entry_selector.to_be_bytes());
+    out.extend_from_slice(&(count * 16 - search_range).to_be_bytes());
+
+    let mut offset = 12 + 16 * tables.len();
+    let mut data = Vec::new();
+    for (tag, table) in &tables {
+        out.extend_from_slice(*tag);
+        out.extend_from_slice(&0u32.to_be_bytes());
+        out.extend_from_slice(&(offset as u32).to_be_bytes());
+        out.extend_from_slice(&(table.len() as u32).to_be_bytes());
+        data.extend_from_slice(table);
+        while data.len() % 4 != 0 {
+            data.push(0);
+        }
+        offset = 12 + 16 * tables.len() + data.len();
+    }
+    out.extend_from_slice(&data);
+    out
+}
+
+/// `post` version 1.0: glyph names come from the standard Macintosh ordering
+fn post_v1_table() -> Vec<u8> {
+    let mut post = 0x0001_0000u32.to_be_bytes().to_vec();
+    post.resize(32, 0);
+    post
+}
+
+#[test]
+fn shaping_results_keep_runs_scripts_directions_and_names() {
+    let json = r#"{"runs": [
+        {"script": "Latn", "direction": "ltr", "glyphs": [{"glyph_id": 36, "cluster": 0, "x_advance": 600}]},
+        {"script": "Arab", "direction": "RightToLeft", "glyphs": [
+            {"glyph_id": 3, "glyph_name": "alef-ar", "cluster": 2, "x_advance": 300},
+            {"glyph_id": 4, "cluster": 1, "x_advance": 250, "y_offset": -40}]}
+    ]}"#;
+    let mut result = shaping::ShapingResult::from_typf_json(json).expect("runs");
+    assert_eq!(result.runs.len(), 2);
+    assert_eq!(result.runs[0].script.as_deref(), Some("Latn"));
+    assert_eq!(
+        result.runs[1].direction,
+        Some(shaping::TextDirection::RightToLeft)
+    );
+    assert_eq!(result.glyph_count(), 3);
+    assert_eq!(
+        result.runs[1].glyphs[0].glyph_name.as_deref(),
+        Some("alef-ar")
+    );
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let font_path = dir.path().join("names.ttf");
+    std::fs::write(&font_path, build_sfnt(&[(b"post", post_v1_table())])).unwrap();
+    result.fill_glyph_names(&font_path);
+    let names: Vec<_> = result.glyphs().map(|g| g.glyph_name.as_deref()).collect();
+    assert_eq!(
+        names,
+        vec![Some("A"), Some("alef-ar"), Some("exclam")],
+        "typf names win, post fills the rest"
+    );
+
+    let single = shaping::ShapingResult::from_typf_json(
+        r#"{"direction": "rtl", "script": "Hebr", "glyphs": [{"g": 5, "cl": 0, "ax": 10}]}"#,
+    )
+    .unwrap();
+    assert_eq!(single.runs.len(), 1);
+    assert_eq!(single.runs[0].script.as_deref(), Some("Hebr"));
+
+    let mut renderer = MockRenderer {
+        backend: Some(RendererBackend::Svg),
+        ..MockRenderer::new(MockOutput::Shaping)
+    };
+    let settings = RenderSettings {
+        sample_text: "AB".into(),
+        ..RenderSettings::default()
+    };
+    let shaped = renderer
+        .shape_text(&font_path, &settings)
+        .expect("shape_text");
+    assert_eq!(shaped.glyph_count(), 2);
+    assert_eq!(
+        renderer.backend,
+        Some(RendererBackend::Svg),
+        "the renderer's previous backend is restored, not settings.backend"
+    );
+    assert!(renderer
+        .shape_text(&PathBuf::from("/fonts/broken.ttf"), &settings)
+        .is_err());
+    assert_eq!(
+        renderer.backend,
+        Some(RendererBackend::Svg),
+        "restored after a failed render too"
+    );
+}
+
+#[test]
+fn glyph_placements_parse_and_draw_as_overlay() {
+    let json = r#"[
+        {"glyph_id": 36, "cluster": 0, "x": 2, "y": 8, "x_advance": 5, "bbox": [3, 2, 6, 8]},
+        {"gid": 3, "cl": 1, "origin_x": 7, "origin_y": 8, "ax": 2, "bounds": {"x": 7, "y": 3, "width": 1, "height": 4}},
+        {"g": 1, "cl": 1, "x": 9, "y": 8}
+    ]"#;
+    let glyphs = overlay::parse_glyph_placements(json).expect("placements");
+    assert_eq!(glyphs.len(), 3);
+    assert_eq!(
+        glyphs[0].ink,
+        Some(overlay::InkBounds {
+            x: 3.0,
+            y: 2.0,
+            width: 3.0,
+            height: 6.0
+        })
+    );
+    assert_eq!(glyphs[1].x, 7.0);
+    assert_eq!(glyphs[1].ink.map(|ink| ink.height), Some(4.0));
+    assert_eq!(glyphs[2].ink, None);
+    assert!(overlay::parse_glyph_placements(r#"[{"cluster": 0}]"#).is_err());
+    assert_eq!(glyphs[0].scaled(2.0).x_advance, 10.0);
+    let overflowing = overlay::parse_glyph_placements(
+        r#"[{"glyph_id": 1, "x": 1e39, "y": 8}, {"glyph_id": 2, "x": 4, "y": 8}]"#,
+    )
+    .unwrap();
+    assert_eq!(overflowing.len(), 1, "non-finite placement skipped");
+    assert_eq!(overflowing[0].glyph_id, 2);
+
+    let render = RenderResult {
+        width: 12,
+        height: 10,
+        data: vec![0u8; 12 * 10 * 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs,
+        baseline: None,
+    };
+    let pixel = |image: &RenderResult, x: usize, y: usize| {
+        let offset = (y * 12 + x) * 4;
+        image.data[offset..offset + 4].to_vec()
+    };
+
+    let none = overlay::draw_overlay(&render, &overlay::OverlayOptions::default()).unwrap();
+    assert_eq!(none.glyphs.len(), 3);
+    assert!(none.data.iter().all(|&b| b == 0));
+
+    let boxes = overlay::OverlayOptions {
+        boxes: true,
+        ..Default::default()
+    };
+    let drawn = overlay::draw_overlay(&render, &boxes).unwrap();
+    assert_eq!(pixel(&drawn, 3, 2), vec![40, 110, 230, 255], "box corner");
+    assert_eq!(
+        pixel(&drawn, 4, 4),
+        vec![0, 0, 0, 0],
+        "box interior untouched"
+    );
+
+    let mut marks = overlay::OverlayOptions::default();
+    marks.set(overlay::OverlayLayer::Origins, true);
+    marks.set(overlay::OverlayLayer::Clusters, true);
+    assert!(marks.get(overlay::OverlayLayer::Origins) && marks.any());
+    let drawn = overlay::draw_overlay(&render, &marks).unwrap();
+    assert_eq!(pixel(&drawn, 2, 8), vec![220, 40, 40, 255], "origin cross");
+    assert_eq!(
+        pixel(&drawn, 7, 0),
+        vec![200, 60, 200, 255],
+        "cluster start"
+    );
+    assert_eq!(
+        pixel(&drawn, 9, 0),
+        vec![0, 0, 0, 0],
+        "same cluster has no boundary"
+    );
+
+    // A far-off advance is clipped to the canvas instead of walked pixel by pixel
+    let far = RenderResult {
+        glyphs: vec![overlay::GlyphPlacement {
+            glyph_id: 5,
+            cluster: 0,
+            x: 1.0,
+            y: 5.0,
+            x_advance: 1.0e12,
+            y_advance: 0.0,
+            ink: None,
+        }],
+        ..render.clone()
+    };
+    let advances = overlay::OverlayOptions {
+        advances: true,
+        ..Default::default()
+    };
+    let drawn = overlay::draw_overlay(&far, &advances).unwrap();
+    assert_eq!(
+        pixel(&drawn, 11, 5),
+        vec![30, 160, 70, 255],
+        "advance reaches the edge"
+    );
+}
+
+/// Write big-endian 16-bit values into a zeroed table at the given offsets
+fn table_with(len: usize, fields: &[(usize, i16)]) -> Vec<u8> {
+    let mut table = vec![0u8; len];
+    for &(offset, value) in fields {
+        table[offset..offset + 2].copy_from_slice(&value.to_be_bytes());
+    }
+    table
+}
+
+/// head (1000 upem), hhea (800/-200/90) and OS/2 v2 (typo 750/-250/0, win 950/300, x 500, cap 700)
+fn vertical_metrics_tables() -> Vec<(&'static [u8; 4], Vec<u8>)> {
+    vec![
+        (b"head", table_with(54, &[(0, 1), (18, 1000)])),
+        (
+            b"hhea",
+            table_with(36, &[(0, 1), (4, 800), (6, -200), (8, 90)]),
+        ),
+        (
+            b"OS/2",
+            table_with(
+                96,
+                &[
+                    (0, 2),
+                    (68, 750),
+                    (70, -250),
+                    (74, 950),
+                    (76, 300),
+                    (86, 500),
+                    (88, 700),
+                ],
+            ),
+        ),
+    ]
+}
+
+#[test]
+fn vertical_metrics_come_from_head_hhea_and_os2() {
+    let data = build_sfnt(&vertical_metrics_tables());
+    let font = read_fonts::FontRef::new(&data).expect("font");
+    let metrics = TestypfVerticalMetrics::from_font(&font).expect("metrics");
+    assert_eq!(metrics.units_per_em, 1000);
+    assert_eq!(
+        (
+            metrics.hhea_ascender,
+            metrics.hhea_descender,
+            metrics.hhea_line_gap
+        ),
+        (800, -200, 90)
+    );
+    assert_eq!((metrics.typo_ascender, metrics.typo_descender), (750, -250));
+    assert_eq!((metrics.win_ascent, metrics.win_descent), (950, 300));
+    assert_eq!(
+        (metrics.x_height, metrics.cap_height),
+        (Some(500), Some(700))
+    );
+
+    let no_head = build_sfnt(&[(b"post", post_v1_table())]);
+    assert!(
+        TestypfVerticalMetrics::from_font(&read_fonts::FontRef::new(&no_head).unwrap()).is_none()
+    );
+
+    let settings = RenderSettings {
+        font_size: 50.0,
+        padding: 10,
+        scale_factor: 2.0,
+        ..RenderSettings::default()
+    };
+    assert_eq!(overlay::estimate_baseline(&metrics, &settings), 100.0);
+
+    let rows = overlay::guide_rows(
+        &metrics,
+        100.0,
+        100.0,
+        &[
+            overlay::GuideLine::Baseline,
+            overlay::GuideLine::XHeight,
+            overlay::GuideLine::WinClip,
+        ],
+    );
+    assert_eq!(
+        rows,
+        vec![
+            (overlay::GuideLine::Baseline, 100.0),
+            (overlay::GuideLine::XHeight, 50.0),
+            (overlay::GuideLine::WinClip, 5.0),
+            (overlay::GuideLine::WinClip, 130.0),
+        ]
+    );
+
+    let blank = RenderResult {
+        width: 4,
+        height: 120,
+        data: vec![0u8; 4 * 120 * 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: Some(100.0),
+    };
+    let drawn = overlay::draw_guides(&blank, &rows).unwrap();
+    let offset = (100 * 4) * 4;
+    assert_eq!(&drawn.data[offset..offset + 4], &[220, 40, 40, 255]);
+    assert_eq!(drawn.baseline, Some(100.0));
+}
+
+/// name table with a Windows English family and a Macintosh subfamily record
+fn name_table() -> Vec<u8> {
+    let family: Vec<u8> = "Demo".encode_utf16().flat_map(u16::to_be_bytes).collect();
+    let subfamily = b"Bold".to_vec();
+    let mut table = Vec::new();
+    for value in [0u16, 2, 6 + 2 * 12] {
+        table.extend_from_slice(&value.to_be_bytes());
+    }
+    let records = [
+        (3u16, 1u16, 0x0409u16, 1u16, family.len() as u16, 0u16),
+        (1, 0, 0, 2, subfamily.len() as u16, family.len() as u16),
+    ];
+    for record in records {
+        for value in [record.0, record.1, record.2, record.3, record.4, record.5] {
+            table.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+    table.extend_from_slice(&family);
+    table.extend_from_slice(&subfamily);
+    table
+}
+
+#[test]
+fn font_tables_decode_directory_and_common_tables() {
+    let mut tables = vertical_metrics_tables();
+    let os2 = &mut tables[2].1;
+    os2[4..6].copy_from_slice(&700u16.to_be_bytes());
+    os2[6..8].copy_from_slice(&5u16.to_be_bytes());
+    os2[8..10].copy_from_slice(&0x0008u16.to_be_bytes());
+    os2[42..46].copy_from_slice(&0x0000_0201u32.to_be_bytes());
+    os2[58..62].copy_from_slice(b"TEST");
+    os2[62..64].copy_from_slice(&0x00A0u16.to_be_bytes());
+    tables.push((b"post", post_v1_table()));
+    tables.push((b"maxp", vec![0, 0, 0x50, 0, 0, 3]));
+    tables.push((b"name", name_table()));
+
+    let decoded = tables::read_font_tables(&build_sfnt(&tables), 0).expect("tables");
+    let tags: Vec<_> = decoded.directory.iter().map(|t| t.tag.as_str()).collect();
+    assert_eq!(tags, vec!["OS/2", "head", "hhea", "maxp", "name", "post"]);
+    assert_eq!(decoded.directory[3].length, 6);
+
+    assert_eq!(decoded.head.as_ref().unwrap().units_per_em, 1000);
+    assert_eq!(decoded.hhea.as_ref().unwrap().ascender, 800);
+
+    let os2 = decoded.os2.as_ref().unwrap();
+    assert_eq!((os2.weight_name(), os2.width_name()), ("Bold", "Normal"));
+    assert_eq!(os2.fs_type_flags(), vec!["Editable"]);
+    assert_eq!(os2.fs_selection_flags(), vec!["BOLD", "USE_TYPO_METRICS"]);
+    assert_eq!(os2.unicode_range_names(), vec!["Basic Latin", "Cyrillic"]);
+    assert_eq!(os2.vendor_id, "TEST");
+
+    let post = decoded.post.as_ref().unwrap();
+    assert_eq!(post.version, "1.0");
+    assert!(!post.is_fixed_pitch);
+    let maxp = decoded.maxp.as_ref().unwrap();
+    assert_eq!((maxp.version.as_str(), maxp.num_glyphs), ("0.5", 3));
+    assert_eq!(maxp.max_points, None);
+
+    assert_eq!(decoded.names.len(), 2);
+    assert_eq!(decoded.names[0].value, "Demo");
+    assert_eq!(decoded.names[0].name_label(), "Family");
+    assert_eq!(decoded.names[0].language_label(), "English (United States)");
+    assert_eq!(
+        (
+            decoded.names[1].platform_label(),
+            decoded.names[1].value.as_str()
+        ),
+        ("Macintosh", "Bold")
+    );
+
+    let bare = tables::read_font_tables(&build_sfnt(&[(b"post", post_v1_table())]), 0).unwrap();
+    assert!(bare.head.is_none() && bare.os2.is_none() && bare.names.is_empty());
+}
+
+/// cmap with a single Windows full-repertoire format 12 subtable built from (first, last, glyph) groups
+fn cmap_format12(groups: &[(u32, u32, u32)]) -> Vec<u8> {
+    let mut table = Vec::new();
+    for value in [0u16, 1, 3, 10] {
+        table.extend_from_slice(&value.to_be_bytes());
+    }
+    table.extend_from_slice(&12u32.to_be_bytes());
+    table.extend_from_slice(&12u16.to_be_bytes());
+    table.extend_from_slice(&0u16.to_be_bytes());
+    let length = 16 + 12 * groups.len() as u32;
+    for value in [length, 0, groups.len() as u32] {
+        table.extend_from_slice(&value.to_be_bytes());
+    }
+    for &(first, last, glyph) in groups {
+        for value in [first, last, glyph] {
+            table.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+    table
+}
+
+#[test]
+fn coverage_summarizes_cmap_by_block_and_script() {
+    let cmap = cmap_format12(&[
+        (0x41, 0x43, 1),
+        (0x44, 0x44, 0),
+        (0x0378, 0x0378, 5),
+        (0x0416, 0x0416, 4),
+        (0xE000, 0xE000, 5),
+    ]);
+    let maxp = vec![0, 0, 0x50, 0, 0, 6];
+    let coverage =
+        coverage::FontCoverage::read(&build_sfnt(&[(b"cmap", cmap), (b"maxp", maxp)]), 0)
+            .expect("coverage");
+
+    assert_eq!(coverage.len(), 6);
+    assert!(coverage.contains('A') && coverage.contains('Ж'));
+    assert!(!coverage.contains('D'), ".notdef mappings are not coverage");
+
+    let blocks = coverage.blocks();
+    let names: Vec<_> = blocks.iter().map(|b| b.name.as_str()).collect();
+    assert_eq!(names, vec!["Basic Latin", "Cyrillic", "Private Use Area"]);
+    assert_eq!((blocks[0].covered, blocks[0].total), (3, 128));
+    assert_eq!((blocks[2].covered, blocks[2].total), (1, 6400));
+    assert!((blocks[1].percent() - 100.0 / 256.0).abs() < 1e-9);
+    assert!(
+        blocks.iter().all(|b| b.covered <= b.total),
+        "unassigned U+0378 is not Greek coverage"
+    );
+    let empty = coverage::CoverageSummary {
+        name: "Empty".into(),
+        covered: 0,
+        total: 0,
+    };
+    assert_eq!(empty.percent(), 100.0);
+
+    let scripts = coverage.scripts();
+    assert_eq!(scripts[0].name, "Latin");
+    assert_eq!(scripts[0].covered, 3);
+    assert!(scripts[0].total > 1000);
+    assert_eq!(scripts[1].name, "Cyrillic");
+    assert_eq!(scripts.len(), 2, "private use has no script");
+
+    assert_eq!(coverage::unicode_block(0x1F600), Some("Emoticons"));
+    assert_eq!(coverage::unicode_block(0x2FE0), None);
+}
+
+#[test]
+fn missing_characters_skip_invisibles_and_notdef_cells_are_tinted() {
+    let coverage = coverage::FontCoverage {
+        codepoints: "Hel wrd".chars().map(u32::from).collect(),
+    };
+    assert_eq!(
+        coverage.missing("Hello\tworld\u{200D}Жж Ж!"),
+        vec!['o', 'Ж', 'ж', '!'],
+        "distinct, in order, without tabs or joiners"
+    );
+    assert!(coverage.missing("Held\n").is_empty());
+
+    let glyph = |glyph_id: u32, x: f32, ink: Option<overlay::InkBounds>| overlay::GlyphPlacement {
+        glyph_id,
+        cluster: 0,
+        x,
+        y: 8.0,
+        x_advance: 3.0,
+        y_advance: 0.0,
+        ink,
+    };
+    let render = RenderResult {
+        width: 12,
+        height: 10,
+        data: vec![0u8; 12 * 10 * 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: vec![
+            glyph(5, 0.0, None),
+            glyph(0, 3.0, None),
+            glyph(
+                0,
+                6.0,
+                Some(overlay::InkBounds {
+                    x: 7.0,
+                    y: 2.0,
+                    width: 2.0,
+                    height: 3.0,
+                }),
+            ),
+        ],
+        baseline: None,
+    };
+    let pixel = |image: &RenderResult, x: usize, y: usize| {
+        let offset = (y * 12 + x) * 4;
+        image.data[offset..offset + 4].to_vec()
+    };
+    let mut options = overlay::OverlayOptions::default();
+    options.set(overlay::OverlayLayer::Notdef, true);
+    let drawn = overlay::draw_overlay(&render, &options).unwrap();
+
+    let tinted = vec![96, 20, 12, 102];
+    assert_eq!(
+        pixel(&drawn, 0, 5),
+        vec![0, 0, 0, 0],
+        "mapped glyph untouched"
+    );
+    assert_eq!(pixel(&drawn, 3, 0), tinted, "advance cell, full height");
+    assert_eq!(pixel(&drawn, 5, 9), tinted);
+    assert_eq!(pixel(&drawn, 8, 4), tinted, "inside ink box");
+    assert_eq!(pixel(&drawn, 8, 6), vec![0, 0, 0, 0], "below ink box");
+}
+
+#[test]
+fn languages_are_detected_from_exemplar_coverage() {
+    let german = languages::LANGUAGES
+        .iter()
+        .find(|language| language.code == "de")
+        .expect("German is bundled");
+    let exemplars = german.exemplars();
+    assert!(exemplars.contains(&"ä".to_string()) && exemplars.contains(&"Ä".to_string()));
+    assert!(exemplars.contains(&"SS".to_string()), "ß uppercases to SS");
+
+    let letters = "abcdefghijklmnopqrstuvwxyzäöüß";
+    let coverage = coverage::FontCoverage {
+        codepoints: letters
+            .chars()
+            .chain(letters.to_uppercase().chars())
+            .map(u32::from)
+            .collect(),
+    };
+    let supported = coverage.languages();
+    let full: Vec<_> = supported
+        .iter()
+        .filter(|s| s.is_full())
+        .map(|s| s.code)
+        .collect();
+    assert!(full.contains(&"de") && full.contains(&"en") && full.contains(&"id"));
+    assert!(!full.contains(&"sv"));
+
+    let first_partial = supported.iter().position(|s| !s.is_full()).unwrap();
+    assert!(supported[first_partial..].iter().all(|s| !s.is_full()));
+    let swedish = supported.iter().find(|s| s.code == "sv").expect("partial");
+    assert_eq!((swedish.covered, swedish.total), (56, 62));
+    assert_eq!(swedish.missing, vec!["à", "À", "é", "É", "å", "Å"]);
+    assert!(
+        supported.iter().all(|s| s.code != "pl"),
+        "Polish is below the partial threshold"
+    );
+
+    let yoruba = languages::LANGUAGES
+        .iter()
+        .find(|language| language.code == "yo")
+        .unwrap();
+    let support = languages::language_support(&coverage, yoruba);
+    assert!(support.missing.contains(&"ẹ\u{301}".to_string()));
+
+    let find = |code: &str| {
+        languages::LANGUAGES
+            .iter()
+            .find(|language| language.code == code)
+            .unwrap()
+    };
+    let turkish = find("tr").exemplars();
+    assert!(turkish.contains(&"İ".to_string()) && turkish.contains(&"I".to_string()));
+    assert!(find("az").exemplars().contains(&"İ".to_string()));
+    assert!(!find("en").exemplars().contains(&"İ".to_string()));
+    let turkish_letters = "abcçdefgğhıijklmnoöprsştuüvyz";
+    let without_dotted_capital = coverage::FontCoverage {
+        codepoints: turkish_letters
+            .chars()
+            .chain("ABCÇDEFGĞHIJKLMNOÖPRSŞTUÜVYZ".chars())
+            .map(u32::from)
+            .collect(),
+    };
+    let support = languages::language_support(&without_dotted_capital, find("tr"));
+    assert!(
+        !support.is_full(),
+        "a font without İ does not fully support Turkish"
+    );
+    assert_eq!(support.missing, vec!["İ"]);
+
+    // Greek dialytika-tonos letters need no decomposed capitals
+    let greek = find("el").exemplars();
+    assert!(greek.contains(&"ΐ".to_string()) && greek.iter().all(|e| e.chars().count() == 1));
+}
+
+#[test]
+fn charsets_parse_and_build_coverage_matrices() {
+    let named = |name: &str| {
+        charsets::CHARSETS
+            .iter()
+            .find(|charset| charset.name == name)
+            .unwrap()
+            .codepoints()
+    };
+    assert_eq!(named("Latin-1").len(), 191);
+    let adobe: Vec<_> = (1..=5)
+        .map(|n| named(&format!("Adobe Latin {n}")))
+        .collect();
+    assert!(adobe.windows(2).all(|pair| pair[0].is_subset(&pair[1])));
+    assert!(named("WGL4").contains(&0x0416) && named("GF Latin Core").contains(&0x0301));
+
+    let custom = charsets::parse_charset("AB, U+0416 U+0100-0102 u+2000..2001").unwrap();
+    assert_eq!(
+        custom.into_iter().collect::<Vec<_>>(),
+        vec![0x41, 0x42, 0x100, 0x101, 0x102, 0x416, 0x2000, 0x2001]
+    );
+    assert!(charsets::parse_charset("U+XYZ").is_err());
+    assert!(charsets::parse_charset("U+0102-0100").is_err());
+
+    let font = |text: &str| coverage::FontCoverage {
+        codepoints: text.chars().map(u32::from).collect(),
+    };
+    let (regular, bold) = (font("ABCЖ"), font("AB"));
+    let fonts = vec![
+        ("Regular".to_string(), &regular),
+        ("Bold, Italic".to_string(), &bold),
+    ];
+
+    let blocks = coverage::CoverageMatrix::by_blocks(&fonts);
+    let labels: Vec<_> = blocks.rows.iter().map(|row| row.label.as_str()).collect();
+    assert_eq!(labels, vec!["Basic Latin", "Cyrillic"]);
+    assert_eq!(blocks.rows[1].cells[1].covered, 0);
+    assert_eq!(blocks.rows[1].cells[0].total, 256);
+
+    let charset: BTreeSet<u32> = "ABCDЖ".chars().map(u32::from).collect();
+    let matrix = coverage::CoverageMatrix::for_charset("Demo", &charset, &fonts);
+    let labels: Vec<_> = matrix.rows.iter().map(|row| row.label.as_str()).collect();
+    assert_eq!(labels, vec!["Demo (all)", "Basic Latin", "Cyrillic"]);
+    assert_eq!(matrix.rows[0].cells[0].missing, vec![0x44]);
+    assert_eq!(matrix.rows[0].cells[1].missing, vec![0x43, 0x44, 0x416]);
+    assert_eq!(matrix.rows[0].cells[1].percent(), 40.0);
+
+    let csv = matrix.to_csv();
+    let lines: Vec<_> = csv.lines().collect();
+    assert_eq!(lines[0], "row,font,covered,total,percent,missing");
+    assert_eq!(
+        lines[2],
+        "Demo (all),\"Bold, Italic\",2,5,40.0,U+0043 U+0044 U+0416"
+    );
+    assert_eq!(lines.len(), 1 + 3 * 2);
+    let round_trip: coverage::CoverageMatrix =
+        serde_json::from_str(&matrix.to_json().unwrap()).unwrap();
+    assert_eq!(round_trip, matrix);
+}
+
+#[test]
+fn sample_library_suggests_entries_the_font_renders() {
+    let library = samples::builtin_samples();
+    assert_eq!(library[0].text, RenderSettings::default().sample_text);
+    for category in samples::SampleCategory::ALL {
+        if category != samples::SampleCategory::Custom {
+            assert!(
+                library.iter().any(|sample| sample.category == category),
+                "{category} has built-in entries"
+            );
+        }
+    }
+    assert_eq!(library[0].to_string(), "Pangram: English");
+
+    // ASCII plus Cyrillic: entries needing accented Latin or other scripts are left out
+    let coverage = coverage::FontCoverage {
+        codepoints: (0x20..=0x7E).chain(0x400..=0x4FF).collect(),
+    };
+    let suggested = samples::suggest_samples(&library, &coverage);
+    let names: Vec<&str> = suggested.iter().map(|s| s.name.as_str()).collect();
+    assert!(names.contains(&"English") && names.contains(&"Russian"));
+    assert!(!names.contains(&"German") && !names.contains(&"Greek"));
+    assert!(names.contains(&"Ukrainian"));
+    assert!(
+        !names.contains(&"Latin mixed case"),
+        "curly quotes are not ASCII"
+    );
+    // Cyrillic has more covered codepoints than Latin, so its entries lead
+    let first_latin = suggested
+        .iter()
+        .position(|s| s.script.as_deref() == Some("Latin"))
+        .unwrap();
+    assert!(suggested[..first_latin]
+        .iter()
+        .all(|s| s.script.as_deref() == Some("Cyrillic")));
+
+    let custom = samples::SampleText::custom("Mine", "Привет, мир! 123");
+    assert_eq!(custom.category, samples::SampleCategory::Custom);
+    assert_eq!(custom.script.as_deref(), Some("Cyrillic"));
+    assert!(custom.renders_with(&coverage));
+    assert_eq!(samples::SampleText::custom("Digits", "0123").script, None);
+
+    let json = serde_json::to_string(&custom).unwrap();
+    let parsed: samples::SampleText = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, custom);
+}
+
+#[test]
+fn sample_text_escapes_decode_codepoints_and_glyph_names() {
+    let decoded = escapes::decode("e\\u0301 a\\u{200D}b \\u{1F600} \\\\ \\/A", None);
+    assert_eq!(decoded.text, "e\u{301} a\u{200D}b \u{1F600} \\ /A");
+    assert!(decoded.issues.is_empty());
+
+    let broken = escapes::decode("\\u{110000} \\u12 \\u{41", None);
+    assert_eq!(broken.text, "\\u{110000} \\u12 \\u{41");
+    assert_eq!(broken.issues.len(), 3);
+
+    assert!(!escapes::has_escapes("1/2 and/or ½"));
+    assert!(escapes::has_escapes("/A"));
+    assert!(escapes::has_escapes("x \\u0301"));
+    assert_eq!(escapes::decode("1/2 and/or", None).text, "1/2 and/or");
+
+    // post 1.0 names: A is glyph 36, B 37, V 57; B is left unencoded
+    let cmap = cmap_format12(&[
+        (0x20, 0x20, 3),
+        (0x41, 0x41, 36),
+        (0x56, 0x56, 57),
+        (0x61, 0x61, 36),
+    ]);
+    let maxp = vec![0, 0, 0x50, 0, 0x01, 0x02];
+    let font = build_sfnt(&[(b"cmap", cmap), (b"maxp", maxp), (b"post", post_v1_table())]);
+    let names = escapes::GlyphNames::read(&font, 0).expect("glyph names");
+    assert_eq!(names.len(), 258);
+    assert_eq!(names.resolve("A"), Ok('A'), "lowest codepoint wins");
+    assert!(names
+        .resolve("B")
+        .unwrap_err()
+        .contains("no Unicode mapping"));
+    assert_eq!(names.resolve("uni0416"), Ok('Ж'));
+    assert_eq!(names.resolve("u1F600"), Ok('\u{1F600}'));
+    assert!(names.resolve("uni0416.alt").is_err());
+
+    let decoded = escapes::decode("/A/V /space/B x/A", Some(&names));
+    assert_eq!(decoded.text, "AV  /B x/A");
+    assert_eq!(decoded.issues.len(), 1);
+
+    let path = temp_font_path("escapes.ttf");
+    std::fs::write(&path, &font).unwrap();
+    assert_eq!(
+        escapes::decode_for_font("/V\\u0300", &path).text,
+        "V\u{300}"
+    );
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(
+        escapes::visible_form("a\u{200D}e\u{301}\u{E000}b"),
+        "a⟨U+200D⟩e⟨U+0301⟩⟨U+E000⟩b"
+    );
+}
+
+#[test]
+fn normalization_forms_apply_after_escapes_and_compare_nfc_with_nfd() {
+    use normalization::Normalization;
+
+    assert_eq!(Normalization::default(), Normalization::None);
+    assert_eq!(Normalization::Nfc.apply("e\u{301}"), "é");
+    assert_eq!(Normalization::Nfd.apply("é"), "e\u{301}");
+    assert_eq!(Normalization::Nfkc.apply("ﬁ ²"), "fi 2");
+    assert_eq!(Normalization::Nfkd.apply("ǆ̌"), "dz\u{30C}\u{30C}");
+    assert_eq!(Normalization::None.apply("e\u{301}"), "e\u{301}");
+
+    let path = PathBuf::from("/nonexistent/font.ttf");
+    let settings = RenderSettings {
+        sample_text: "Cafe\\u0301".to_string(),
+        normalization: Normalization::Nfc,
+        ..RenderSettings::default()
+    };
+    assert_eq!(settings.text_for_font(&path), "Café");
+
+    let legacy: RenderSettings = serde_json::from_str(
+        r#"{"sample_text":"x","font_size":12.0,"foreground_color":[0,0,0,255],"background_color":null,"backend":"Orge","padding":0}"#,
+    )
+    .unwrap();
+    assert_eq!(legacy.normalization, Normalization::None);
+
+    let comparison = normalization::compare_nfc_nfd(
+        &MockRenderer::new(MockOutput::Codepoints),
+        &path,
+        &settings,
+        0,
+        diff::DiffStyle::Heatmap,
+    )
+    .unwrap();
+    assert!(comparison.forms_differ());
+    assert_eq!((comparison.nfc.width, comparison.nfd.width), (4, 5));
+    assert!(comparison.diff.report.size_mismatch);
+    assert_eq!(comparison.diff.report.changed_pixels, 2);
+
+    let plain = RenderSettings {
+        sample_text: "Cafe".to_string(),
+        ..RenderSettings::default()
+    };
+    let comparison = normalization::compare_nfc_nfd(
+        &MockRenderer::new(MockOutput::Codepoints),
+        &path,
+        &plain,
+        0,
+        diff::DiffStyle::Heatmap,
+    )
+    .unwrap();
+    assert!(!comparison.forms_differ());
+    assert_eq!(comparison.diff.report.changed_pixels, 0);
+}
+
+/// Big-endian 16-bit words, negative values as two's complement
+fn words(values: &[i32]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|&v| (v as u16).to_be_bytes())
+        .collect()
+}
+
+/// Pair adjustment format 1 with x-advance values, one pair set per first glyph
+fn pair_pos_format1(pairs: &[(u16, &[(u16, i16)])]) -> Vec<u8> {
+    let header_len = 10 + 2 * pairs.len();
+    let mut coverage = vec![1, pairs.len() as i32];
+    coverage.extend(pairs.iter().map(|&(first, _)| i32::from(first)));
+    let coverage = words(&coverage);
+    let mut offsets = Vec::new();
+    let mut sets = Vec::new();
+    for (_, records) in pairs {
+        offsets.push((header_len + coverage.len() + sets.len()) as i32);
+        let mut set = vec![records.len() as i32];
+        for &(second, value) in records.iter() {
+            set.extend([i32::from(second), i32::from(value)]);
+        }
+        sets.extend(words(&set));
+    }
+    let mut header = vec![1, header_len as i32, 0x0004, 0, pairs.len() as i32];
+    header.extend(offsets);
+    [words(&header), coverage, sets].concat()
+}
+
+/// GPOS whose `kern` feature uses lookup 0; every lookup is a pair adjustment lookup
+fn gpos_table(lookups: &[Vec<Vec<u8>>]) -> Vec<u8> {
+    let feature_list = [words(&[1]), b"kern".to_vec(), words(&[8, 0, 1, 0])].concat();
+    let lookups: Vec<Vec<u8>> = lookups
+        .iter()
+        .map(|subtables| {
+            let mut header = vec![2, 0, subtables.len() as i32];
+            let mut offset = 6 + 2 * subtables.len();
+            for subtable in subtables {
+                header.push(offset as i32);
+                offset += subtable.len();
+            }
+            [words(&header), subtables.concat()].concat()
+        })
+        .collect();
+    let mut lookup_list = vec![lookups.len() as i32];
+    let mut offset = 2 + 2 * lookups.len();
+    for lookup in &lookups {
+        lookup_list.push(offset as i32);
+        offset += lookup.len();
+    }
+    let lookup_list = [words(&lookup_list), lookups.concat()].concat();
+    let header = words(&[1, 0, 0, 10, 10 + feature_list.len() as i32]);
+    [header, feature_list, lookup_list].concat()
+}
+
+#[test]
+fn spacing_proofs_list_controls_and_kern_pairs_by_magnitude() {
+    // post 1.0 names: slash 18, A 36, B 37 (unencoded), H 43, O 50, T 55, V 57, n 81, o 82
+    let cmap = cmap_format12(&[
+        (0x2F, 0x2F, 18),
+        (0x41, 0x41, 36),
+        (0x48, 0x48, 43),
+        (0x4F, 0x4F, 50),
+        (0x54, 0x54, 55),
+        (0x56, 0x56, 57),
+        (0x6E, 0x6F, 81),
+        (0x0416, 0x0416, 100),
+        (0x041D, 0x041E, 101),
+        (0x05D0, 0x05D0, 103),
+        (0x0628, 0x0628, 104),
+    ]);
+    let maxp = vec![0, 0, 0x50, 0, 0x01, 0x02];
+
+    let glyph_pairs =
+        pair_pos_format1(&[(36, &[(37, -20), (57, -80)]), (55, &[(18, 10), (82, -60)])]);
+    // A is class 0 of the first definition; A and B are class 1 of the second, O class 2
+    let mut class_pairs = words(&[2, 28, 0x0004, 0, 36, 44, 2, 3, -5, 0, -30, 0, -50, 15]);
+    class_pairs.extend(words(&[1, 2, 36, 57]));
+    class_pairs.extend(words(&[1, 57, 1, 1]));
+    class_pairs.extend(words(&[2, 2, 36, 37, 1, 50, 50, 2]));
+    // A second lookup outside the kern feature is ignored
+    let other = pair_pos_format1(&[(57, &[(36, -999)])]);
+    let gpos = gpos_table(&[vec![glyph_pairs, class_pairs], vec![other]]);
+
+    let font = build_sfnt(&[
+        (b"cmap", cmap),
+        (b"maxp", maxp),
+        (b"post", post_v1_table()),
+        (b"GPOS", gpos),
+    ]);
+    let proof = proofs::SpacingProof::read(&font, 0).expect("proof");
+
+    let controls: Vec<&str> = proof.controls.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(
+        &controls[..7],
+        &["HOHAHOH", "HOHHHOH", "HOHOHOH", "HOHTHOH", "HOHVHOH", "nonnnon", "nononon"]
+    );
+    assert!(
+        controls.contains(&"НОНЖНОН"),
+        "Cyrillic letters use Cyrillic controls"
+    );
+    assert!(
+        controls.contains(&"nonאnon") && controls.contains(&"nonبnon"),
+        "caseless letters fall back to Latin lowercase controls"
+    );
+    assert_eq!(proof.letters_without_controls, 0);
+    assert!(proof.controls.iter().all(|c| c.letter != '/'));
+
+    let kern: Vec<String> = proof.kern_pairs.iter().map(|p| p.to_string()).collect();
+    assert_eq!(
+        kern,
+        vec!["AV -80", "To -60", "\\u{0041}/B -20", "T\\/ +10"]
+    );
+    assert_eq!(proof.kern_pairs[0].classes, None);
+    let class: Vec<String> = proof.class_pairs.iter().map(|p| p.to_string()).collect();
+    assert_eq!(class, vec!["VA -50", "AO -30", "VO +15"]);
+    assert_eq!(proof.class_pairs[0].classes, Some((1, 1)));
+    assert_eq!(proof.unreachable_pairs, 0);
+
+    // Proof text survives escape decoding as the intended characters
+    let text = proof.text(proofs::ProofSection::KernPairs, Some(3));
+    assert_eq!(text, "AV To \\u{0041}/B");
+    let names = escapes::GlyphNames::read(&font, 0).unwrap();
+    let decoded = escapes::decode(
+        &proof.text(proofs::ProofSection::KernPairs, None),
+        Some(&names),
+    );
+    assert_eq!(decoded.issues.len(), 1, "/B has no codepoint to shape");
+    assert!(decoded.text.ends_with("T/"));
+    assert_eq!(proof.count(proofs::ProofSection::ClassPairs), 3);
+    assert_eq!(
+        proof.lines(proofs::ProofSection::Controls).len(),
+        proof.controls.len()
+    );
+
+    let bare = proofs::SpacingProof::read(&build_sfnt(&[(b"post", post_v1_table())]), 0).unwrap();
+    assert_eq!(bare, proofs::SpacingProof::default());
+}
@@ -0,0 +1,80 @@
+use super::*;
+use resvg::{tiny_skia, usvg};
+
+/// Largest edge (in pixels) used when rasterizing SVG thumbnails
+pub const MAX_THUMBNAIL_EDGE: u32 = 4096;
+
+/// Check whether a typf string result is SVG markup rather than JSON
+pub fn looks_like_svg(output: &str) -> bool {
+    let trimmed = output.trim_start();
+    trimmed.starts_with("<svg") || (trimmed.starts_with("<?xml") && trimmed.contains("<svg"))
+}
+
+/// Wrap SVG markup from typf in a RenderResult, taking dimensions from the SVG canvas
+pub fn svg_result(markup: String) -> TestypfResult<RenderResult> {
+    let tree = parse_tree(&markup)?;
+    let size = tree.size();
+
+    Ok(RenderResult {
+        width: size.width().ceil() as u32,
+        height: size.height().ceil() as u32,
+        data: markup.into_bytes().into(),
+        format: PixelFormat::Svg,
+        glyphs: Vec::new(),
+        baseline: None,
+    })
+}
+
+/// Rasterize a vector RenderResult into straight-alpha RGBA8 at the given scale
+pub fn rasterize(result: &RenderResult, scale: f32) -> TestypfResult<RenderResult> {
+    let markup = result.svg_markup().ok_or_else(|| {
+        TestypfError::RenderFailed(format!(
+            "Cannot rasterize non-vector render result ({})",
+            result.format
+        ))
+    })?;
+
+    let tree = parse_tree(markup)?;
+    let size = tree.size();
+    let longest = size.width().max(size.height()) * scale;
+    let scale = if longest > MAX_THUMBNAIL_EDGE as f32 {
+        scale * MAX_THUMBNAIL_EDGE as f32 / longest
+    } else {
+        scale
+    };
+
+    let width = ((size.width() * scale).ceil() as u32).max(1);
+    let height = ((size.height() * scale).ceil() as u32).max(1);
+    let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or_else(|| {
+        TestypfError::RenderFailed(format!("Invalid SVG raster size {width}x{height}"))
+    })?;
+
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+
+    let data: Vec<u8> = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+
+    Ok(RenderResult {
+        width,
+        height,
+        data: data.into(),
+        format: PixelFormat::Rgba8,
+        glyphs: result.glyphs.iter().map(|g| g.scaled(scale)).collect(),
+        baseline: result.baseline.map(|b| b * scale),
+    })
+}
+
+fn parse_tree(markup: &str) -> TestypfResult<usvg::Tree> {
+    usvg::Tree::from_str(markup, &usvg::Options::default())
+        .map_err(|e| TestypfError::RenderFailed(format!("Invalid SVG output from typf: {e}")))
+}
@@ -2,7 +2,10 @@
 
 use crate::helpers;
 use crate::message::Message;
-use crate::types::{InstallScope, LayoutMode, RenderPreview, ScanStats};
+use crate::types::{
+    AppConfig, ComparisonRow, CoverageAnalysis, InstallScope, LayoutMode, MatrixRows,
+    NormalizationCompare, PreviewDiff, RenderPreview, ScaleMode, ScanStats, ShapingInspection,
+};
 use crate::update;
 use crate::view;
 
@@ -11,7 +14,18 @@ use iced::{
     multi_window::{self, Application},
     window, Command, Element, Event, Settings, Subscription, Theme,
 };
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use testypf_core::bench::{BenchmarkConfig, BenchmarkStats};
+use testypf_core::coverage::{CoverageMatrix, FontCoverage};
+use testypf_core::diff::DiffStyle;
+use testypf_core::escapes::DecodedText;
+use testypf_core::export::{ExportFormat, DEFAULT_NAME_TEMPLATE};
+use testypf_core::languages::LanguageSupport;
+use testypf_core::overlay::{GuideLine, OverlayOptions};
+use testypf_core::proofs::ProofSection;
+use testypf_core::samples::SampleText;
+use testypf_core::tables::FontTables;
 use testypf_core::{RenderSettings, RendererBackend, TestypfEngine, TestypfFontInfo};
 
 /// Main application state.
@@ -39,6 +53,65 @@ pub struct TestypfApp {
     pub selected_font: Option<usize>,
     pub font_filter: String,
     pub render_selected_only: bool,
+    pub export_sidecar: bool,
+    pub export_template: String,
+    pub export_format: ExportFormat,
+    pub export_flatten_enabled: bool,
+    pub export_flatten_input: String,
+    pub scale_mode: ScaleMode,
+    pub detected_scale_factor: Option<f32>,
+    pub window_logical_size: Option<iced::Size>,
+    pub export_scales: Vec<u32>,
+    pub gamma_input: String,
+    pub benchmark_warmup_input: String,
+    pub benchmark_iterations_input: String,
+    pub benchmark_results: Vec<BenchmarkStats>,
+    /// A benchmark is rendering in a background command
+    pub benchmark_running: bool,
+    pub comparison_backends: Vec<RendererBackend>,
+    pub comparison_rows: Vec<ComparisonRow>,
+    /// A comparison is rendering in a background command
+    pub comparison_running: bool,
+    /// Positions in `render_previews` ticked for diffing (at most two)
+    pub diff_selection: Vec<usize>,
+    pub diff_style: DiffStyle,
+    pub diff_tolerance_input: String,
+    pub preview_diff: Option<Result<PreviewDiff, String>>,
+    pub shaping_inspection: Option<ShapingInspection>,
+    /// Latest NFC vs NFD comparison for the selected font
+    pub normalization_compare: Option<Result<NormalizationCompare, String>>,
+    pub overlay_options: OverlayOptions,
+    /// Vertical metric guides drawn over previews
+    pub guide_lines: Vec<GuideLine>,
+    /// Whether the metadata panel lists the font's tables
+    pub show_font_tables: bool,
+    /// Decoded tables of the selected font, keyed by its path
+    pub font_tables: Option<(PathBuf, Result<FontTables, String>)>,
+    /// cmap coverage per loaded font path, filled on demand
+    pub coverage: HashMap<PathBuf, Result<FontCoverage, String>>,
+    /// Fully and partially supported languages per analyzed font path
+    pub font_languages: HashMap<PathBuf, Vec<LanguageSupport>>,
+    /// Font paths whose coverage is being analyzed in the background
+    pub coverage_pending: HashSet<PathBuf>,
+    /// Report the analysis in the status bar once `coverage_pending` drains
+    pub coverage_report_requested: bool,
+    /// Character typed into the "which fonts cover this" lookup
+    pub coverage_query: String,
+    /// Sample texts the user added; built-in entries come from `testypf_core::samples`
+    pub sample_library: Vec<SampleText>,
+    /// Offer only library entries the selected font renders in full
+    pub sample_suggestions_only: bool,
+    pub sample_entry_name: String,
+    /// Spacing proof section "Load Spacing Proof" puts into the sample text
+    pub proof_section: ProofSection,
+    /// Sample text after escape decoding for the selected (or first) font
+    pub decoded_sample: DecodedText,
+    pub matrix_rows: MatrixRows,
+    /// Characters and `U+` ranges used when `matrix_rows` is Custom
+    pub matrix_custom_charset: String,
+    pub coverage_matrix: Option<Result<CoverageMatrix, String>>,
+    /// (row, font column) of the matrix cell whose missing codepoints are listed
+    pub matrix_drill: Option<(usize, usize)>,
 }
 
 impl multi_window::Application for TestypfApp {
@@ -62,8 +135,17 @@ impl multi_window::Application for TestypfApp {
 
         let font_ops_available = TestypfEngine::font_ops_available();
 
+        let mut export_template = DEFAULT_NAME_TEMPLATE.to_string();
+        let mut export_format = ExportFormat::default();
+        let mut sample_library = Vec::new();
+        let mut scale_mode = ScaleMode::Auto;
+
         // Load persisted configuration if present
         if let Ok(config) = helpers::load_config() {
+            export_template = config.export_template;
+            export_format = config.export_format;
+            sample_library = config.sample_library;
+            scale_mode = config.scale_mode;
             if available_backends.iter().any(|b| b == &config.backend) {
                 let _ = engine.text_renderer().set_backend(config.backend.clone());
                 render_settings.backend = config.backend;
@@ -74,7 +156,7 @@ impl multi_window::Application for TestypfApp {
         let install_scope = InstallScope::User;
         engine.set_font_install_scope(install_scope.to_font_scope());
 
-        let app = Self {
+        let mut app = Self {
             engine,
             fonts: Vec::new(),
             render_settings,
@@ -82,7 +164,7 @@ impl multi_window::Application for TestypfApp {
             render_previews: Vec::new(),
             is_dragging: false,
             hovered_file: None,
-            available_backends,
+            available_backends: available_backends.clone(),
             last_scan_stats: None,
             pending_drop_paths: Vec::new(),
             drop_processing_scheduled: false,
@@ -98,9 +180,63 @@ impl multi_window::Application for TestypfApp {
             selected_font: None,
             font_filter: String::new(),
             render_selected_only: false,
+            export_sidecar: false,
+            export_template,
+            export_format,
+            export_flatten_enabled: false,
+            export_flatten_input: "#FFFFFF".to_string(),
+            scale_mode,
+            detected_scale_factor: None,
+            window_logical_size: None,
+            export_scales: vec![1],
+            gamma_input: "1.0".to_string(),
+            benchmark_warmup_input: BenchmarkConfig::default().warmup.to_string(),
+            benchmark_iterations_input: BenchmarkConfig::default().iterations.to_string(),
+            benchmark_results: Vec::new(),
+            benchmark_running: false,
+            comparison_backends: available_backends
+                .iter()
+                .filter(|backend| backend.is_raster())
+                .cloned()
+                .collect(),
+            comparison_rows: Vec::new(),
+            comparison_running: false,
+            diff_selection: Vec::new(),
+            diff_style: DiffStyle::default(),
+            diff_tolerance_input: "0".to_string(),
+            preview_diff: None,
+            overlay_options: OverlayOptions::default(),
+            guide_lines: Vec::new(),
+            show_font_tables: false,
+            font_tables: None,
+            coverage: HashMap::new(),
+            font_languages: HashMap::new(),
+            coverage_pending: HashSet::new(),
+            coverage_report_requested: false,
+            coverage_query: String::new(),
+            sample_library,
+            sample_suggestions_only: true,
+            sample_entry_name: String::new(),
+            proof_section: ProofSection::default(),
+            decoded_sample: DecodedText::default(),
+            matrix_rows: MatrixRows::default(),
+            matrix_custom_charset: String::new(),
+            coverage_matrix: None,
+            matrix_drill: None,
+            shaping_inspection: None,
+            normalization_compare: None,
         };
 
-        (app, Command::none())
+        app.apply_scale_factor();
+
+        // iced 0.12 reports neither the window's scale factor nor changes to it, so Auto mode
+        // falls back to measuring it once from a screenshot; a saved fixed scale skips that
+        let command = if app.scale_mode == ScaleMode::Auto {
+            window::fetch_size(window::Id::MAIN, Message::WindowSizeFetched)
+        } else {
+            Command::none()
+        };
+        (app, command)
     }
 
     fn subscription(&self) -> Subscription<Message> {
@@ -168,6 +304,179 @@ impl TestypfApp {
         settings
     }
 
+    /// Persist backend, scale and export preferences and user sample texts.
+    pub fn save_config(&self) {
+        if let Err(e) = helpers::save_config(&AppConfig {
+            backend: self.render_settings.backend.clone(),
+            export_template: self.export_template.clone(),
+            export_format: self.export_format,
+            sample_library: self.sample_library.clone(),
+            scale_mode: self.scale_mode,
+        }) {
+            eprintln!("Failed to persist configuration: {}", e);
+        }
+    }
+
+    /// Scale factor renders should use: the manual override, else the detected window scale.
+    pub fn effective_scale_factor(&self) -> f32 {
+        self.scale_mode
+            .factor()
+            .or(self.detected_scale_factor)
+            .unwrap_or(1.0)
+    }
+
+    /// Push the effective scale factor into the render settings.
+    pub fn apply_scale_factor(&mut self) {
+        let scale = self.effective_scale_factor();
+        if self.render_settings.scale_factor != scale {
+            self.render_settings.scale_factor = scale;
+            self.invalidate_render_cache();
+        }
+    }
+
+    /// Drop all previews along with any diff between them.
+    pub fn clear_render_previews(&mut self) {
+        self.render_previews.clear();
+        self.diff_selection.clear();
+        self.preview_diff = None;
+        self.shaping_inspection = None;
+    }
+
+    /// Recompute the diff card from the current selection, style and tolerance.
+    pub fn refresh_preview_diff(&mut self) {
+        self.preview_diff = match self.diff_selection.as_slice() {
+            &[first, second] => Some(helpers::diff_previews(
+                &self.render_previews,
+                first,
+                second,
+                &self.diff_tolerance_input,
+                self.diff_style,
+            )),
+            _ => None,
+        };
+    }
+
+    /// Redraw glyph overlays and guide lines on every preview.
+    pub fn refresh_preview_overlays(&mut self) {
+        let options = self.overlay_options;
+        let mut failure = None;
+        for preview in &mut self.render_previews {
+            let rows = helpers::preview_guide_rows(
+                preview,
+                self.fonts.get(preview.font_index),
+                &self.render_settings,
+                &self.guide_lines,
+            );
+            preview.overlay = match helpers::preview_overlay_handle(preview, &options, &rows) {
+                Ok(handle) => handle,
+                Err(e) => {
+                    failure = Some(e);
+                    None
+                }
+            };
+        }
+        if let Some(e) = failure {
+            self.status = format!("Glyph overlay failed: {}", e);
+        }
+    }
+
+    /// Decode the selected font's tables when the table view is open and they are not cached yet.
+    pub fn refresh_font_tables(&mut self) {
+        if !self.show_font_tables {
+            return;
+        }
+        let Some(font) = self.selected_font.and_then(|i| self.fonts.get(i)) else {
+            return;
+        };
+        if self
+            .font_tables
+            .as_ref()
+            .is_some_and(|(path, _)| path == font.path())
+        {
+            return;
+        }
+        let tables = font.font_tables().map_err(|e| e.to_string());
+        self.font_tables = Some((font.path().clone(), tables));
+    }
+
+    /// Analyze one font's cmap and language support unless they are cached already.
+    pub fn ensure_coverage(&mut self, font_index: usize) {
+        let Some(font) = self.fonts.get(font_index) else {
+            return;
+        };
+        if self.coverage.contains_key(font.path()) {
+            return;
+        }
+        let analysis = CoverageAnalysis::of(font);
+        self.store_coverage(vec![analysis]);
+    }
+
+    /// Analyze every font without cached coverage in a background command.
+    pub fn analyze_coverage_in_background(&mut self) -> Command<Message> {
+        let fonts: Vec<TestypfFontInfo> = self
+            .fonts
+            .iter()
+            .filter(|font| {
+                !self.coverage.contains_key(font.path())
+                    && !self.coverage_pending.contains(font.path())
+            })
+            .cloned()
+            .collect();
+        if fonts.is_empty() {
+            return Command::none();
+        }
+        self.coverage_pending
+            .extend(fonts.iter().map(|font| font.path().clone()));
+        Command::perform(
+            async move { fonts.iter().map(CoverageAnalysis::of).collect() },
+            Message::CoverageAnalyzed,
+        )
+    }
+
+    /// Cache analyzed coverage, keeping entries that were filled in the meantime.
+    pub fn store_coverage(&mut self, analyses: Vec<CoverageAnalysis>) {
+        for analysis in analyses {
+            self.coverage_pending.remove(&analysis.path);
+            self.font_languages
+                .entry(analysis.path.clone())
+                .or_insert(analysis.languages);
+            self.coverage
+                .entry(analysis.path)
+                .or_insert(analysis.coverage);
+        }
+    }
+
+    /// Decode sample-text escapes against the selected font, or the first loaded one, and normalize.
+    pub fn refresh_decoded_sample(&mut self) {
+        self.decoded_sample = helpers::decode_sample(
+            &self.render_settings,
+            self.fonts.get(self.selected_font.unwrap_or(0)),
+        );
+    }
+
+    /// Coverage of the selected font, when it has been analyzed and its cmap was readable.
+    pub fn selected_font_coverage(&self) -> Option<&FontCoverage> {
+        let font = self.fonts.get(self.selected_font?)?;
+        self.coverage.get(font.path())?.as_ref().ok()
+    }
+
+    /// Sample-text characters the font at `font_index` cannot map after escape decoding,
+    /// analyzing its coverage if needed.
+    ///
+    /// Fonts whose cmap cannot be read report nothing rather than every character.
+    pub fn missing_characters(&mut self, font_index: usize) -> Vec<char> {
+        self.ensure_coverage(font_index);
+        let Some(font) = self.fonts.get(font_index) else {
+            return Vec::new();
+        };
+        match self.coverage.get(font.path()) {
+            Some(Ok(coverage)) => {
+                coverage.missing(&self.render_settings.text_for_font(font.path()))
+            }
+            _ => Vec::new(),
+        }
+    }
+
     /// Invalidate render cache.
     pub fn invalidate_render_cache(&mut self) {
         self.last_render_settings = None;
@@ -201,6 +510,10 @@ impl TestypfApp {
         ];
 
         fields.iter().any(|field| field.contains(&needle))
+            || self
+                .font_languages
+                .get(font.path())
+                .is_some_and(|languages| helpers::supports_language(languages, &needle))
     }
 
     /// Get indices of fonts matching the current filter.
@@ -224,7 +537,7 @@ impl TestypfApp {
     }
 
     /// Get backend capabilities text.
-    pub fn backend_capabilities(&self, backend: &RendererBackend) -> &'static str {
+    pub fn backend_capabilities(&self, backend: &RendererBackend) -> String {
         helpers::backend_capabilities(backend)
     }
 
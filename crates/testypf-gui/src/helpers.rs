@@ -2,7 +2,9 @@
 
 use crate::message::Message;
 use crate::types::{
-    AppConfig, DropPathKind, InstallScope, LayoutMode, RenderPreview, ScanStats, FONT_EXTENSIONS,
+    AppConfig, ComparisonCell, ComparisonRow, DropPathKind, InstallScope, LayoutMode, MatrixRows,
+    NormalizationCompare, PreviewDiff, RenderPreview, RenderedImage, ScaleMode, ScanStats,
+    FONT_EXTENSIONS,
 };
 use iced::widget::image::Handle;
 use iced::{keyboard, Element};
@@ -10,8 +12,24 @@ use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
+use testypf_core::bench::{BenchmarkCase, BenchmarkConfig, BenchmarkStats};
+use testypf_core::charsets;
+use testypf_core::contact_sheet::{ContactSheetCell, ContactSheetOptions};
+use testypf_core::coverage::{CoverageMatrix, CoverageSummary, FontCoverage, MatrixCell};
+use testypf_core::diff::DiffStyle;
+use testypf_core::escapes::{self, DecodedText};
+use testypf_core::export::{ExportFormat, RenderProvenance};
+use testypf_core::languages::LanguageSupport;
+use testypf_core::normalization::NormalizationComparison;
+use testypf_core::overlay::{GuideLine, OverlayOptions};
+use testypf_core::proofs::{ProofSection, SpacingProof};
+use testypf_core::samples::{self, SampleText};
+use testypf_core::shaping::ShapingRun;
+use testypf_core::tables::FontTables;
 use testypf_core::{
-    RenderResult, RenderSettings, RendererBackend, TestypfFontInfo, TestypfVariationAxis,
+    PixelFormat, RasterOptions, RenderResult, RenderSettings, RendererBackend, TestypfFontInfo,
+    TestypfVariationAxis, TextRenderer,
 };
 
 // =============================================================================
@@ -160,10 +178,466 @@ pub fn font_metadata_lines(font: &TestypfFontInfo, file_size_bytes: Option<u64>)
         lines.push(format!("File size: {}", format_file_size(size)));
     }
 
+    if let Some(m) = &font.vertical_metrics {
+        let optional = |value: Option<i16>| value.map_or("—".to_string(), |v| v.to_string());
+        lines.push(format!("Units per em: {}", m.units_per_em));
+        lines.push(format!(
+            "hhea ascender {}, descender {}, line gap {}",
+            m.hhea_ascender, m.hhea_descender, m.hhea_line_gap
+        ));
+        lines.push(format!(
+            "Typo ascender {}, descender {}, line gap {}",
+            m.typo_ascender, m.typo_descender, m.typo_line_gap
+        ));
+        lines.push(format!(
+            "Win ascent {}, descent {}",
+            m.win_ascent, m.win_descent
+        ));
+        lines.push(format!(
+            "x-height {}, cap height {}",
+            optional(m.x_height),
+            optional(m.cap_height)
+        ));
+    }
+
     lines.push(format!("Path: {}", font.path().display()));
     lines
 }
 
+/// Titled sections describing a font's table directory and decoded tables.
+pub fn font_table_sections(tables: &FontTables) -> Vec<(String, Vec<String>)> {
+    let mut sections = vec![(
+        format!("Table directory ({} tables)", tables.directory.len()),
+        tables
+            .directory
+            .iter()
+            .map(|t| {
+                format!(
+                    "{:<4}  {:>9} bytes  checksum 0x{:08X}",
+                    t.tag, t.length, t.checksum
+                )
+            })
+            .collect(),
+    )];
+
+    if let Some(head) = &tables.head {
+        sections.push((
+            "head".to_string(),
+            vec![
+                format!(
+                    "Revision {:.3}, {} units per em",
+                    head.font_revision, head.units_per_em
+                ),
+                format!(
+                    "Flags 0x{:04X}, macStyle 0x{:04X}, lowest rec. PPEM {}",
+                    head.flags, head.mac_style, head.lowest_rec_ppem
+                ),
+                format!(
+                    "Bounds ({}, {}) – ({}, {})",
+                    head.x_min, head.y_min, head.x_max, head.y_max
+                ),
+                format!(
+                    "Created {}, modified {}",
+                    head.created_date(),
+                    head.modified_date()
+                ),
+            ],
+        ));
+    }
+    if let Some(hhea) = &tables.hhea {
+        sections.push((
+            "hhea".to_string(),
+            vec![
+                format!(
+                    "Ascender {}, descender {}, line gap {}",
+                    hhea.ascender, hhea.descender, hhea.line_gap
+                ),
+                format!(
+                    "Advance width max {}, {} hMetrics, caret slope {}/{}",
+                    hhea.advance_width_max,
+                    hhea.number_of_h_metrics,
+                    hhea.caret_slope_rise,
+                    hhea.caret_slope_run
+                ),
+            ],
+        ));
+    }
+    if let Some(os2) = &tables.os2 {
+        let ranges = os2.unicode_range_names();
+        sections.push((
+            format!("OS/2 (version {})", os2.version),
+            vec![
+                format!(
+                    "Weight class {} ({}), width class {} ({})",
+                    os2.weight_class,
+                    os2.weight_name(),
+                    os2.width_class,
+                    os2.width_name()
+                ),
+                format!(
+                    "fsSelection 0x{:04X}: {}",
+                    os2.fs_selection,
+                    list_or_none(&os2.fs_selection_flags())
+                ),
+                format!(
+                    "fsType 0x{:04X}: {}",
+                    os2.fs_type,
+                    list_or_none(&os2.fs_type_flags())
+                ),
+                format!("Vendor ID: {}", os2.vendor_id),
+                format!(
+                    "Unicode ranges ({}): {}",
+                    ranges.len(),
+                    list_or_none(&ranges)
+                ),
+            ],
+        ));
+    }
+    if let Some(post) = &tables.post {
+        sections.push((
+            format!("post (version {})", post.version),
+            vec![
+                format!(
+                    "Italic angle {:.2}, {}",
+                    post.italic_angle,
+                    if post.is_fixed_pitch {
+                        "fixed pitch"
+                    } else {
+                        "proportional"
+                    }
+                ),
+                format!(
+                    "Underline position {}, thickness {}",
+                    post.underline_position, post.underline_thickness
+                ),
+            ],
+        ));
+    }
+    if let Some(maxp) = &tables.maxp {
+        let mut lines = vec![format!("{} glyphs", maxp.num_glyphs)];
+        if let (Some(points), Some(contours)) = (maxp.max_points, maxp.max_contours) {
+            lines.push(format!(
+                "Max points {}, max contours {}, max component depth {}",
+                points,
+                contours,
+                maxp.max_component_depth.unwrap_or(0)
+            ));
+        }
+        sections.push((format!("maxp (version {})", maxp.version), lines));
+    }
+
+    sections.push((
+        format!("name ({} records)", tables.names.len()),
+        tables
+            .names
+            .iter()
+            .map(|n| {
+                format!(
+                    "{} [{}] {}/{}: {}",
+                    n.name_label(),
+                    n.name_id,
+                    n.platform_label(),
+                    n.language_label(),
+                    n.value
+                )
+            })
+            .collect(),
+    ));
+    sections
+}
+
+fn list_or_none(items: &[&str]) -> String {
+    if items.is_empty() {
+        "none".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+// =============================================================================
+// Character Coverage
+// =============================================================================
+
+/// Parse a coverage lookup: a single character, or a codepoint as `U+0416`, `0x416` or bare hex.
+pub fn parse_coverage_query(query: &str) -> Option<char> {
+    let query = query.trim();
+    let mut chars = query.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(ch);
+    }
+    let hex = ["U+", "u+", "0x", "0X"]
+        .iter()
+        .find_map(|prefix| query.strip_prefix(prefix))
+        .unwrap_or(query);
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+/// Indices of loaded fonts whose analyzed coverage includes `ch`.
+pub fn fonts_covering(
+    fonts: &[TestypfFontInfo],
+    coverage: &HashMap<PathBuf, Result<FontCoverage, String>>,
+    ch: char,
+) -> Vec<usize> {
+    fonts
+        .iter()
+        .enumerate()
+        .filter(|(_, font)| matches!(coverage.get(font.path()), Some(Ok(c)) if c.contains(ch)))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Whether a fully supported language's name contains, or its code equals, a lowercase needle.
+pub fn supports_language(languages: &[LanguageSupport], needle: &str) -> bool {
+    languages.iter().any(|language| {
+        language.is_full()
+            && (language.code == needle || language.name.to_lowercase().contains(needle))
+    })
+}
+
+/// Metadata lines naming fully and partially supported languages.
+pub fn language_support_lines(languages: &[LanguageSupport]) -> Vec<String> {
+    let full: Vec<&str> = languages
+        .iter()
+        .filter(|l| l.is_full())
+        .map(|l| l.name)
+        .collect();
+    let partial: Vec<String> = languages
+        .iter()
+        .filter(|l| !l.is_full())
+        .map(|l| format!("{} ({:.0}%)", l.name, l.percent()))
+        .collect();
+    let mut lines = vec![format!(
+        "Supports: {}",
+        if full.is_empty() {
+            "no bundled language fully".to_string()
+        } else {
+            full.join(", ")
+        }
+    )];
+    if !partial.is_empty() {
+        lines.push(format!("Partial: {}", partial.join(", ")));
+    }
+    lines
+}
+
+/// One block or script row, e.g. `Basic Latin  95/95  100.0%`.
+pub fn coverage_summary_line(summary: &CoverageSummary) -> String {
+    format!(
+        "{}  {}/{}  {:.1}%",
+        summary.name,
+        summary.covered,
+        summary.total,
+        summary.percent()
+    )
+}
+
+/// Build the coverage matrix over every font whose cmap could be read, in font list order.
+pub fn build_coverage_matrix(
+    rows: MatrixRows,
+    custom_charset: &str,
+    fonts: &[TestypfFontInfo],
+    coverage: &HashMap<PathBuf, Result<FontCoverage, String>>,
+) -> Result<CoverageMatrix, String> {
+    let columns: Vec<(String, &FontCoverage)> = fonts
+        .iter()
+        .filter_map(|font| match coverage.get(font.path()) {
+            Some(Ok(c)) => Some((font.full_name.clone(), c)),
+            _ => None,
+        })
+        .collect();
+    if columns.is_empty() {
+        return Err("No analyzed fonts to compare".to_string());
+    }
+    match rows {
+        MatrixRows::Blocks => Ok(CoverageMatrix::by_blocks(&columns)),
+        MatrixRows::Charset(charset) => Ok(CoverageMatrix::for_charset(
+            charset.name,
+            &charset.codepoints(),
+            &columns,
+        )),
+        MatrixRows::Custom => {
+            let codepoints = charsets::parse_charset(custom_charset).map_err(|e| e.to_string())?;
+            if codepoints.is_empty() {
+                return Err("Enter characters or U+ ranges for the custom charset".to_string());
+            }
+            Ok(CoverageMatrix::for_charset("Custom", &codepoints, &columns))
+        }
+    }
+}
+
+/// Cell fill: green when complete, then yellow, orange and red as coverage drops.
+pub fn coverage_cell_color(percent: f64) -> iced::Color {
+    if percent >= 100.0 {
+        iced::Color::from_rgb(0.6, 0.87, 0.6)
+    } else if percent >= 75.0 {
+        iced::Color::from_rgb(0.93, 0.9, 0.55)
+    } else if percent > 0.0 {
+        iced::Color::from_rgb(0.96, 0.72, 0.5)
+    } else {
+        iced::Color::from_rgb(0.93, 0.55, 0.55)
+    }
+}
+
+/// Most missing codepoints listed in a matrix drill-down.
+const MATRIX_DRILL_LISTED: usize = 256;
+
+/// Heading plus the missing codepoints of one matrix cell, eight per line.
+pub fn matrix_drill_lines(font: &str, row: &str, cell: &MatrixCell) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{} – {}: {}/{} covered, {} missing",
+        font,
+        row,
+        cell.covered,
+        cell.total,
+        cell.missing.len()
+    )];
+    let listed: Vec<String> = cell
+        .missing
+        .iter()
+        .take(MATRIX_DRILL_LISTED)
+        .map(
+            |&cp| match char::from_u32(cp).filter(|ch| !ch.is_control()) {
+                Some(ch) => format!("U+{:04X} {}", cp, ch),
+                None => format!("U+{:04X}", cp),
+            },
+        )
+        .collect();
+    lines.extend(listed.chunks(8).map(|chunk| chunk.join("  ")));
+    if cell.missing.len() > MATRIX_DRILL_LISTED {
+        lines.push(format!(
+            "… and {} more (export the matrix for the full list)",
+            cell.missing.len() - MATRIX_DRILL_LISTED
+        ));
+    }
+    lines
+}
+
+// =============================================================================
+// Sample Text Library
+// =============================================================================
+
+/// Built-in entries followed by the user's, narrowed to what `coverage` renders when suggesting.
+pub fn sample_choices(
+    user_entries: &[SampleText],
+    coverage: Option<&FontCoverage>,
+    suggestions_only: bool,
+) -> Vec<SampleText> {
+    let mut library = samples::builtin_samples();
+    library.extend(user_entries.iter().cloned());
+    match coverage {
+        Some(coverage) if suggestions_only => samples::suggest_samples(&library, coverage)
+            .into_iter()
+            .cloned()
+            .collect(),
+        _ => library,
+    }
+}
+
+/// Add `text` to the user library under `name`, replacing an entry of the same name.
+pub fn save_sample_entry(
+    user_entries: &mut Vec<SampleText>,
+    name: &str,
+    text: &str,
+) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Enter a name for the sample text".to_string());
+    }
+    if text.trim().is_empty() {
+        return Err("Sample text is empty".to_string());
+    }
+    let entry = SampleText::custom(name, text);
+    match user_entries
+        .iter_mut()
+        .find(|existing| existing.name == name)
+    {
+        Some(existing) => {
+            *existing = entry;
+            Ok(format!("Updated sample \"{}\"", name))
+        }
+        None => {
+            user_entries.push(entry);
+            Ok(format!("Saved sample \"{}\" to the library", name))
+        }
+    }
+}
+
+/// Sample text of `settings` decoded against `font` (without glyph names when `None`) and normalized.
+pub fn decode_sample(settings: &RenderSettings, font: Option<&TestypfFontInfo>) -> DecodedText {
+    let sample = &settings.sample_text;
+    let mut decoded = match font {
+        Some(font) => escapes::decode_for_font(sample, font.path()),
+        None => escapes::decode(sample, None),
+    };
+    decoded.text = settings.normalization.apply(&decoded.text);
+    decoded
+}
+
+/// What the shaper receives for `sample`: the decoded text with invisibles spelled out, then
+/// any escapes that could not be decoded. Empty when the text has nothing to reveal.
+pub fn decoded_sample_lines(sample: &str, decoded: &DecodedText) -> Vec<String> {
+    let visible = escapes::visible_form(&decoded.text);
+    if !escapes::has_escapes(sample) && visible == decoded.text {
+        return Vec::new();
+    }
+    let mut lines = vec![format!("Shaped as: {}", visible)];
+    lines.extend(
+        decoded
+            .issues
+            .iter()
+            .map(|issue| format!("Escape kept as typed: {}", issue)),
+    );
+    lines
+}
+
+/// Index of the user entry whose text is the current sample text.
+pub fn current_user_sample(user_entries: &[SampleText], sample_text: &str) -> Option<usize> {
+    user_entries
+        .iter()
+        .position(|entry| entry.text == sample_text)
+}
+
+/// Most spacing proof strings loaded into the sample text at once
+pub const SPACING_PROOF_LIMIT: usize = 200;
+
+/// Sample text for one section of a font's spacing proof, with a status line.
+pub fn spacing_proof_sample(
+    font_name: &str,
+    proof: &SpacingProof,
+    section: ProofSection,
+) -> Result<(String, String), String> {
+    let total = proof.count(section);
+    if total == 0 {
+        return Err(match section {
+            ProofSection::Controls => format!("{}: no letters with controls", font_name),
+            _ => format!(
+                "{}: no {} in the GPOS kern feature",
+                font_name,
+                section.label().to_lowercase()
+            ),
+        });
+    }
+    let shown = total.min(SPACING_PROOF_LIMIT);
+    let mut status = if shown < total {
+        format!("{}: {}, first {} of {}", font_name, section, shown, total)
+    } else {
+        format!("{}: {}, {}", font_name, section, total)
+    };
+    if section == ProofSection::Controls && proof.letters_without_controls > 0 {
+        status.push_str(&format!(
+            " ({} letters skipped: no controls in the font)",
+            proof.letters_without_controls
+        ));
+    }
+    if section != ProofSection::Controls && proof.unreachable_pairs > 0 {
+        status.push_str(&format!(
+            " ({} pairs skipped: glyphs without a codepoint or name)",
+            proof.unreachable_pairs
+        ));
+    }
+    Ok((proof.text(section, Some(SPACING_PROOF_LIMIT)), status))
+}
+
 // =============================================================================
 // Variable Fonts
 // =============================================================================
@@ -233,55 +707,115 @@ pub fn format_file_size(bytes: u64) -> String {
 // Rendering
 // =============================================================================
 
-/// Convert Typf RenderResult (RGBA8) into an iced image handle.
+/// Convert a Typf RenderResult into an iced image handle, sharing RGBA8 buffers.
 pub fn image_handle_from_render(render_result: &RenderResult) -> Result<Handle, String> {
-    let expected_len = (render_result.width as usize)
-        .saturating_mul(render_result.height as usize)
-        .saturating_mul(4);
-    if expected_len == 0 {
-        return Err("Render result contains no pixel data".to_string());
-    }
+    let rgba = render_result.to_rgba8().map_err(|e| e.to_string())?;
 
-    if render_result.data.len() != expected_len {
-        return Err(format!(
-            "Pixel data length mismatch (expected {}, got {})",
-            expected_len,
-            render_result.data.len()
-        ));
-    }
-
-    if !render_result.format.to_lowercase().contains("rgba") {
-        return Err(format!(
-            "Unsupported render format for preview: {}",
-            render_result.format
-        ));
-    }
-
-    Ok(Handle::from_pixels(
-        render_result.width,
-        render_result.height,
-        render_result.data.clone(),
-    ))
+    Ok(Handle::from_pixels(rgba.width, rgba.height, rgba.data))
 }
 
 /// Build a render preview with an iced image handle.
+///
+/// Vector results are rasterized into a thumbnail for display while the
+/// original SVG markup is kept for export.
 pub fn build_render_preview(
     font_index: usize,
     render_result: RenderResult,
     duration_ms: u128,
+    scale_factor: f32,
 ) -> Result<RenderPreview, String> {
-    let handle = image_handle_from_render(&render_result)?;
+    let svg = render_result.svg_markup().map(str::to_string);
+    // typf already sized the SVG canvas in device pixels
+    let raster = if render_result.is_vector() {
+        testypf_core::vector::rasterize(&render_result, 1.0)
+    } else {
+        render_result.to_rgba8()
+    }
+    .map_err(|e| e.to_string())?;
+
+    let handle = image_handle_from_render(&raster)?;
     Ok(RenderPreview {
+        glyphs: raster.glyphs,
+        baseline: raster.baseline,
+        overlay: None,
+        missing_chars: Vec::new(),
         font_index,
-        width: render_result.width,
-        height: render_result.height,
-        format: render_result.format.clone(),
-        pixels: render_result.data.clone(),
+        width: raster.width,
+        height: raster.height,
+        format: render_result.format,
+        pixels: raster.data,
         handle,
         duration_ms,
+        scale_factor,
+        svg,
     })
 }
 
+/// Bytes held by preview pixel buffers and SVG markup, counting shared buffers once.
+pub fn preview_memory_bytes(previews: &[RenderPreview]) -> usize {
+    let pixels = testypf_core::pixels::unique_bytes(previews.iter().map(|p| &p.pixels));
+    let svg: usize = previews
+        .iter()
+        .filter_map(|p| p.svg.as_ref())
+        .map(String::len)
+        .sum();
+    pixels + svg
+}
+
+/// Status-area summary of memory held by the current previews.
+pub fn preview_memory_summary(previews: &[RenderPreview]) -> String {
+    format!(
+        "Preview memory: {} across {} preview(s)",
+        format_file_size(preview_memory_bytes(previews) as u64),
+        previews.len()
+    )
+}
+
+/// On-screen size of a preview in logical pixels (device pixels divided by its scale).
+pub fn preview_logical_size(preview: &RenderPreview) -> (f32, f32) {
+    let scale = if preview.scale_factor > 0.0 {
+        preview.scale_factor
+    } else {
+        1.0
+    };
+    (preview.width as f32 / scale, preview.height as f32 / scale)
+}
+
+/// Derive a window scale factor from its physical and logical widths, snapped to quarter steps.
+pub fn scale_factor_from_sizes(physical_width: u32, logical_width: f32) -> Option<f32> {
+    if physical_width == 0 || logical_width <= 0.0 {
+        return None;
+    }
+    let scale = (physical_width as f32 / logical_width * 4.0).round() / 4.0;
+    (scale >= 1.0).then_some(scale.min(4.0))
+}
+
+/// File name suffix for an export scale variant (`@2x`, `@3x`; none for 1×).
+pub fn export_scale_suffix(scale: u32) -> String {
+    if scale <= 1 {
+        String::new()
+    } else {
+        format!("@{scale}x")
+    }
+}
+
+/// First of `stem.ext`, `stem_1.ext`, ... in `folder` whose image, `.svg` copy and JSON sidecar
+/// are all unused.
+pub fn unused_export_path(folder: &Path, stem: &str, ext: &str) -> PathBuf {
+    let taken = |path: &Path| {
+        path.exists()
+            || path.with_extension("svg").exists()
+            || testypf_core::export::sidecar_path(path).exists()
+    };
+    let mut candidate = folder.join(format!("{stem}.{ext}"));
+    let mut suffix = 1;
+    while taken(&candidate) {
+        candidate = folder.join(format!("{stem}_{suffix}.{ext}"));
+        suffix += 1;
+    }
+    candidate
+}
+
 /// Human-readable metadata string for a render preview.
 pub fn preview_metadata_text(
     preview: &RenderPreview,
@@ -291,22 +825,482 @@ pub fn preview_metadata_text(
     let variation_text = variation_summary(settings)
         .map(|s| format!(" | Variations: {}", s))
         .unwrap_or_default();
+    let missing_text = missing_characters_summary(&preview.missing_chars)
+        .map(|s| format!(" | {}", s))
+        .unwrap_or_default();
+    let scale_text = if preview.scale_factor != 1.0 {
+        format!(" | Scale: {}x", preview.scale_factor)
+    } else {
+        String::new()
+    };
 
     format!(
-        "Dimensions: {}x{} | Format: {} | Backend: {} | Style: {} | Family: {} | Render time: {} ms{}",
+        "Dimensions: {}x{}{} | Format: {} | Backend: {} | Style: {} | Family: {} | Render time: {} ms{}{}",
         preview.width,
         preview.height,
-        preview.format,
+        scale_text,
+        if preview.svg.is_some() {
+            "SVG (rasterized thumbnail)".to_string()
+        } else {
+            preview.format.to_string()
+        },
         settings.backend,
         font.style,
         font.family_name,
         preview.duration_ms,
-        variation_text
+        variation_text,
+        missing_text
+    )
+}
+
+/// Most missing characters listed by name before the rest are only counted.
+const MISSING_CHARS_LISTED: usize = 8;
+
+/// "Missing 2 characters: U+0416 Ж, U+4E2D 中", or `None` when the font covers the sample.
+pub fn missing_characters_summary(missing: &[char]) -> Option<String> {
+    if missing.is_empty() {
+        return None;
+    }
+    let mut listed: Vec<String> = missing
+        .iter()
+        .take(MISSING_CHARS_LISTED)
+        .map(|&ch| format!("U+{:04X} {}", u32::from(ch), ch))
+        .collect();
+    if missing.len() > MISSING_CHARS_LISTED {
+        listed.push(format!("and {} more", missing.len() - MISSING_CHARS_LISTED));
+    }
+    Some(format!(
+        "Missing {} character{}: {}",
+        missing.len(),
+        if missing.len() == 1 { "" } else { "s" },
+        listed.join(", ")
+    ))
+}
+
+/// Composite previews into a captioned contact sheet, one caption per preview.
+pub fn compose_contact_sheet(
+    previews: &[RenderPreview],
+    fonts: &[TestypfFontInfo],
+    settings: &RenderSettings,
+    options: &ContactSheetOptions,
+) -> Result<RenderResult, String> {
+    let images: Vec<RenderResult> = previews
+        .iter()
+        .map(|preview| RenderResult {
+            width: preview.width,
+            height: preview.height,
+            data: preview.pixels.clone(),
+            format: PixelFormat::Rgba8,
+            glyphs: Vec::new(),
+            baseline: None,
+        })
+        .collect();
+
+    let cells: Vec<ContactSheetCell<'_>> = previews
+        .iter()
+        .zip(&images)
+        .map(|(preview, image)| ContactSheetCell {
+            image,
+            caption: fonts
+                .get(preview.font_index)
+                .map(|font| {
+                    format!(
+                        "{} | {}",
+                        font.full_name,
+                        preview_metadata_text(preview, font, settings)
+                    )
+                })
+                .unwrap_or_else(|| format!("Font #{}", preview.font_index + 1)),
+        })
+        .collect();
+
+    testypf_core::contact_sheet::compose(&cells, options).map_err(|e| e.to_string())
+}
+
+// =============================================================================
+// Preview Diff
+// =============================================================================
+
+/// Tick or untick a preview for diffing; ticking a third replaces the oldest pick.
+pub fn toggle_diff_selection(selection: &[usize], position: usize, enabled: bool) -> Vec<usize> {
+    let mut next: Vec<usize> = selection
+        .iter()
+        .copied()
+        .filter(|&p| p != position)
+        .collect();
+    if enabled {
+        next.push(position);
+        if next.len() > 2 {
+            next.remove(0);
+        }
+    }
+    next
+}
+
+/// Rows of the enabled guide lines for a preview, using the reported baseline or an estimate from the font metrics.
+pub fn preview_guide_rows(
+    preview: &RenderPreview,
+    font: Option<&TestypfFontInfo>,
+    settings: &RenderSettings,
+    guides: &[GuideLine],
+) -> Vec<(GuideLine, f32)> {
+    let Some(metrics) = font.and_then(|f| f.vertical_metrics) else {
+        return Vec::new();
+    };
+    let settings = settings.at_scale(preview.scale_factor);
+    let baseline = preview
+        .baseline
+        .unwrap_or_else(|| testypf_core::overlay::estimate_baseline(&metrics, &settings));
+    testypf_core::overlay::guide_rows(&metrics, baseline, settings.device_font_size(), guides)
+}
+
+/// Draw guide lines and the enabled glyph overlay layers over a preview; `None` when there is nothing to draw.
+pub fn preview_overlay_handle(
+    preview: &RenderPreview,
+    options: &OverlayOptions,
+    guide_rows: &[(GuideLine, f32)],
+) -> Result<Option<Handle>, String> {
+    let draw_glyphs = options.any() && !preview.glyphs.is_empty();
+    if !draw_glyphs && guide_rows.is_empty() {
+        return Ok(None);
+    }
+    let image = RenderResult {
+        width: preview.width,
+        height: preview.height,
+        data: preview.pixels.clone(),
+        format: PixelFormat::Rgba8,
+        glyphs: preview.glyphs.clone(),
+        baseline: preview.baseline,
+    };
+    let guided =
+        testypf_core::overlay::draw_guides(&image, guide_rows).map_err(|e| e.to_string())?;
+    let overlaid =
+        testypf_core::overlay::draw_overlay(&guided, options).map_err(|e| e.to_string())?;
+    image_handle_from_render(&overlaid).map(Some)
+}
+
+/// Image to show for a preview: the overlaid copy when one exists.
+pub fn preview_display_handle(preview: &RenderPreview) -> Handle {
+    preview
+        .overlay
+        .clone()
+        .unwrap_or_else(|| preview.handle.clone())
+}
+
+/// Diff two previews by position, parsing the per-channel tolerance from text.
+pub fn diff_previews(
+    previews: &[RenderPreview],
+    first: usize,
+    second: usize,
+    tolerance: &str,
+    style: DiffStyle,
+) -> Result<PreviewDiff, String> {
+    let tolerance = tolerance
+        .trim()
+        .parse::<u8>()
+        .map_err(|_| "Tolerance must be a whole number from 0 to 255".to_string())?;
+    let as_result = |position: usize| {
+        previews
+            .get(position)
+            .map(|preview| RenderResult {
+                width: preview.width,
+                height: preview.height,
+                data: preview.pixels.clone(),
+                format: PixelFormat::Rgba8,
+                glyphs: Vec::new(),
+                baseline: None,
+            })
+            .ok_or_else(|| "Selected preview no longer exists".to_string())
+    };
+
+    let diff =
+        testypf_core::diff::compare(&as_result(first)?, &as_result(second)?, tolerance, style)
+            .map_err(|e| e.to_string())?;
+    let handle = image_handle_from_render(&diff.image)?;
+
+    Ok(PreviewDiff {
+        first,
+        second,
+        width: diff.image.width,
+        height: diff.image.height,
+        scale_factor: previews[first].scale_factor,
+        report: diff.report,
+        handle,
+    })
+}
+
+/// Rasterize (vector output) or convert a render for display.
+pub fn rendered_image(render_result: &RenderResult) -> Result<RenderedImage, String> {
+    let raster = if render_result.is_vector() {
+        testypf_core::vector::rasterize(render_result, 1.0)
+    } else {
+        render_result.to_rgba8()
+    }
+    .map_err(|e| e.to_string())?;
+    Ok(RenderedImage {
+        handle: image_handle_from_render(&raster)?,
+        width: raster.width,
+        height: raster.height,
+    })
+}
+
+/// Prepare an NFC vs NFD comparison for display.
+pub fn normalization_compare(
+    font_index: usize,
+    comparison: &NormalizationComparison,
+    scale_factor: f32,
+) -> Result<NormalizationCompare, String> {
+    Ok(NormalizationCompare {
+        font_index,
+        nfc_text: comparison.nfc_text.clone(),
+        nfd_text: comparison.nfd_text.clone(),
+        report: comparison.diff.report.clone(),
+        nfc: rendered_image(&comparison.nfc)?,
+        nfd: rendered_image(&comparison.nfd)?,
+        diff: rendered_image(&comparison.diff.image)?,
+        scale_factor,
+    })
+}
+
+/// One-line outcome of an NFC vs NFD comparison.
+pub fn normalization_verdict(compare: &NormalizationCompare) -> String {
+    let lengths = format!(
+        "{} vs {} codepoints",
+        compare.nfc_text.chars().count(),
+        compare.nfd_text.chars().count()
+    );
+    if compare.nfc_text == compare.nfd_text {
+        "NFC and NFD are the same codepoints for this text - nothing to compare".to_string()
+    } else if compare.report.changed_pixels == 0 {
+        format!("NFC and NFD render identically ({})", lengths)
+    } else {
+        format!(
+            "NFC and NFD render differently: {} of {} pixels ({})",
+            compare.report.changed_pixels,
+            compare.report.total_pixels(),
+            lengths
+        )
+    }
+}
+
+// =============================================================================
+// Shaping Inspector
+// =============================================================================
+
+/// Column headings of the shaping table.
+pub const SHAPING_COLUMNS: [&str; 8] = [
+    "#", "Glyph ID", "Name", "Cluster", "X adv", "Y adv", "X off", "Y off",
+];
+
+/// Heading for one shaped run, e.g. "Run 1: Arab, RTL, 4 glyph(s)".
+pub fn shaping_run_label(index: usize, run: &ShapingRun) -> String {
+    format!(
+        "Run {}: {}, {}, {} glyph(s)",
+        index + 1,
+        run.script.as_deref().unwrap_or("unknown script"),
+        run.direction
+            .map(|d| d.to_string())
+            .unwrap_or_else(|| "unknown direction".to_string()),
+        run.glyphs.len()
     )
 }
 
-/// Save a render preview to a PNG file on disk.
-pub fn export_preview_to_path(preview: &RenderPreview, path: &Path) -> Result<(), String> {
+/// Table cells for each glyph of a run, in `SHAPING_COLUMNS` order.
+pub fn shaping_table_rows(run: &ShapingRun) -> Vec<[String; 8]> {
+    let number = |value: f32| {
+        if value.fract() == 0.0 {
+            format!("{}", value as i64)
+        } else {
+            format!("{:.2}", value)
+        }
+    };
+    run.glyphs
+        .iter()
+        .enumerate()
+        .map(|(index, glyph)| {
+            [
+                index.to_string(),
+                glyph.glyph_id.to_string(),
+                glyph.glyph_name.clone().unwrap_or_else(|| "—".to_string()),
+                glyph.cluster.to_string(),
+                number(glyph.x_advance),
+                number(glyph.y_advance),
+                number(glyph.x_offset),
+                number(glyph.y_offset),
+            ]
+        })
+        .collect()
+}
+
+// =============================================================================
+// Backend Comparison
+// =============================================================================
+
+/// Render each `(font index, path)` once per backend with otherwise identical `settings`.
+pub fn render_comparison(
+    renderer: &mut dyn TextRenderer,
+    fonts: &[(usize, PathBuf)],
+    backends: &[RendererBackend],
+    settings: &RenderSettings,
+) -> Vec<ComparisonRow> {
+    fonts
+        .iter()
+        .map(|(font_index, path)| {
+            let cells = backends
+                .iter()
+                .map(|backend| {
+                    let settings = RenderSettings {
+                        backend: backend.clone(),
+                        ..settings.clone()
+                    };
+                    let started = Instant::now();
+                    let outcome = renderer
+                        .set_backend(backend.clone())
+                        .and_then(|_| renderer.render_text(path, &settings))
+                        .map_err(|e| e.to_string())
+                        .and_then(|result| {
+                            build_render_preview(
+                                *font_index,
+                                result,
+                                started.elapsed().as_millis(),
+                                settings.scale_factor,
+                            )
+                        });
+                    ComparisonCell {
+                        backend: backend.clone(),
+                        outcome,
+                    }
+                })
+                .collect();
+            ComparisonRow {
+                font_index: *font_index,
+                cells,
+            }
+        })
+        .collect()
+}
+
+/// Add or remove a backend from the comparison, keeping the available-backend order.
+pub fn toggle_comparison_backend(
+    available: &[RendererBackend],
+    selected: &[RendererBackend],
+    backend: &RendererBackend,
+    enabled: bool,
+) -> Vec<RendererBackend> {
+    available
+        .iter()
+        .filter(|b| {
+            if *b == backend {
+                enabled
+            } else {
+                selected.contains(b)
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+/// Caption for one comparison cell: timing on success, the error otherwise.
+pub fn comparison_cell_text(cell: &ComparisonCell) -> String {
+    match &cell.outcome {
+        Ok(preview) => format!(
+            "{} ms | {}x{}",
+            preview.duration_ms, preview.width, preview.height
+        ),
+        Err(e) => format!("failed: {}", e),
+    }
+}
+
+/// Tile a comparison matrix into one image: one column per backend, one row per font.
+///
+/// Backend names head the columns and every cell is captioned with its font and timing.
+/// Failed cells keep their slot as an empty tile so columns stay aligned.
+pub fn compose_comparison_matrix(
+    rows: &[ComparisonRow],
+    fonts: &[TestypfFontInfo],
+    options: &ContactSheetOptions,
+) -> Result<RenderResult, String> {
+    let backends: Vec<String> = rows
+        .first()
+        .map(|row| row.cells.iter().map(|c| c.backend.to_string()).collect())
+        .unwrap_or_default();
+
+    let images: Vec<RenderResult> = rows
+        .iter()
+        .flat_map(|row| &row.cells)
+        .map(|cell| match &cell.outcome {
+            Ok(preview) => RenderResult {
+                width: preview.width,
+                height: preview.height,
+                data: preview.pixels.clone(),
+                format: PixelFormat::Rgba8,
+                glyphs: Vec::new(),
+                baseline: None,
+            },
+            Err(_) => RenderResult {
+                width: 1,
+                height: 1,
+                data: vec![0; 4].into(),
+                format: PixelFormat::Rgba8,
+                glyphs: Vec::new(),
+                baseline: None,
+            },
+        })
+        .collect();
+
+    let cells: Vec<ContactSheetCell<'_>> = rows
+        .iter()
+        .flat_map(|row| row.cells.iter().map(move |cell| (row.font_index, cell)))
+        .zip(&images)
+        .map(|((font_index, cell), image)| ContactSheetCell {
+            image,
+            caption: format!(
+                "{} | {}",
+                fonts
+                    .get(font_index)
+                    .map(|font| font.full_name.clone())
+                    .unwrap_or_else(|| format!("Font #{}", font_index + 1)),
+                comparison_cell_text(cell)
+            ),
+        })
+        .collect();
+
+    testypf_core::contact_sheet::compose(
+        &cells,
+        &ContactSheetOptions {
+            columns: backends.len().max(1),
+            column_headers: backends,
+            ..options.clone()
+        },
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Settings and scale mode for re-rendering an exported image.
+///
+/// `glyph_positions` stays as the overlay controls want it, and the scale becomes the fixed mode
+/// closest to the exported factor so window scale detection does not replace it.
+pub fn reproduced_settings(
+    exported: RenderSettings,
+    glyph_positions: bool,
+) -> (RenderSettings, ScaleMode) {
+    let scale_mode = ScaleMode::nearest_fixed(exported.scale_factor);
+    let settings = RenderSettings {
+        glyph_positions,
+        scale_factor: scale_mode.factor().unwrap_or(1.0),
+        ..exported
+    };
+    (settings, scale_mode)
+}
+
+/// Save a render preview in the given image format, optionally flattened onto a solid color.
+pub fn export_preview_as(
+    preview: &RenderPreview,
+    path: &Path,
+    format: ExportFormat,
+    flatten: Option<(u8, u8, u8)>,
+    provenance: Option<&RenderProvenance>,
+) -> Result<(), String> {
     if preview.width == 0 || preview.height == 0 {
         return Err("Preview has zero dimensions".to_string());
     }
@@ -319,33 +1313,105 @@ pub fn export_preview_to_path(preview: &RenderPreview, path: &Path) -> Result<()
         return Err("Preview pixel data length is invalid".to_string());
     }
 
-    ::image::save_buffer_with_format(
+    testypf_core::export::write_image(
         path,
-        &preview.pixels,
+        format,
         preview.width,
         preview.height,
-        ::image::ColorType::Rgba8,
-        ::image::ImageFormat::Png,
+        &preview.pixels,
+        flatten,
+        provenance,
     )
-    .map_err(|e| format!("Failed to write preview PNG: {}", e))
+    .map_err(|e| format!("Failed to write preview {}: {}", format, e))
 }
 
-/// Make a filesystem-safe filename stem from user-facing font names.
-pub fn sanitized_file_stem(name: &str) -> String {
-    let mut stem: String = name
-        .chars()
-        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
-        .collect();
+/// Path and format for a single-image export: a known image extension picks the format,
+/// anything else is replaced by the selected format's extension.
+pub fn export_target(path: &Path, selected: ExportFormat) -> (PathBuf, ExportFormat) {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(ExportFormat::from_extension)
+    {
+        Some(format) => (path.to_path_buf(), format),
+        None => (path.with_extension(selected.extension()), selected),
+    }
+}
+
+/// Save the SVG markup of a vector render preview to disk.
+pub fn export_preview_svg_to_path(preview: &RenderPreview, path: &Path) -> Result<(), String> {
+    let markup = preview
+        .svg
+        .as_ref()
+        .ok_or_else(|| "Preview has no vector output".to_string())?;
+
+    fs::write(path, markup).map_err(|e| format!("Failed to write preview SVG: {}", e))
+}
 
-    while stem.contains("__") {
-        stem = stem.replace("__", "_");
+// =============================================================================
+// Benchmarks
+// =============================================================================
+
+/// Parse warm-up and iteration counts from the benchmark inputs.
+pub fn parse_benchmark_config(warmup: &str, iterations: &str) -> Result<BenchmarkConfig, String> {
+    let warmup = warmup
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| "Warm-up must be a whole number".to_string())?;
+    let iterations = iterations
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| "Iterations must be a positive whole number".to_string())?;
+
+    Ok(BenchmarkConfig { warmup, iterations })
+}
+
+/// Every (font, backend) combination for the given fonts, using `settings` otherwise.
+pub fn benchmark_cases(
+    fonts: &[TestypfFontInfo],
+    font_indices: &[usize],
+    backends: &[RendererBackend],
+    settings: &RenderSettings,
+) -> Vec<BenchmarkCase> {
+    font_indices
+        .iter()
+        .filter_map(|&i| fonts.get(i))
+        .flat_map(|font| {
+            backends.iter().map(move |backend| BenchmarkCase {
+                font_name: font.full_name.clone(),
+                font_path: font.path().clone(),
+                settings: RenderSettings {
+                    backend: backend.clone(),
+                    ..settings.clone()
+                },
+            })
+        })
+        .collect()
+}
+
+/// Bar length for a benchmark median, scaled so the slowest result fills `full_width`.
+pub fn benchmark_bar_width(median_ms: f64, slowest_ms: f64, full_width: f32) -> f32 {
+    if slowest_ms <= 0.0 || median_ms <= 0.0 {
+        return 2.0;
     }
+    ((median_ms / slowest_ms) as f32 * full_width).max(2.0)
+}
 
-    stem.trim_matches('_')
-        .chars()
-        .take(40)
-        .collect::<String>()
-        .to_lowercase()
+/// One-line summary for a benchmark result.
+pub fn benchmark_summary(stats: &BenchmarkStats) -> String {
+    if let Some(error) = &stats.error {
+        return format!("failed: {}", error);
+    }
+    let rss = stats
+        .peak_rss_bytes
+        .map(|b| format!(" · peak RSS {}", format_file_size(b)))
+        .unwrap_or_default();
+    format!(
+        "median {:.2} ms · min {:.2} · p95 {:.2} · {:.1} renders/s{}",
+        stats.median_ms, stats.min_ms, stats.p95_ms, stats.renders_per_sec, rss
+    )
 }
 
 // =============================================================================
@@ -374,6 +1440,11 @@ pub fn parse_rgba_hex(value: &str) -> Option<(u8, u8, u8, u8)> {
     Some((r, g, b, a))
 }
 
+/// Format an RGBA tuple as #RRGGBBAA for the color inputs.
+pub fn format_rgba_hex((r, g, b, a): (u8, u8, u8, u8)) -> String {
+    format!("#{r:02X}{g:02X}{b:02X}{a:02X}")
+}
+
 // =============================================================================
 // Cache Logic
 // =============================================================================
@@ -465,6 +1536,7 @@ pub fn get_backend_description(backend: &RendererBackend) -> String {
     match backend {
         RendererBackend::Orge => "Opixa (High quality raster)".to_string(),
         RendererBackend::Json => "JSON (debug output)".to_string(),
+        RendererBackend::Svg => "SVG (vector outlines)".to_string(),
         #[cfg(target_os = "macos")]
         RendererBackend::CoreGraphics => "CoreGraphics (macOS native)".to_string(),
         #[cfg(feature = "render-skia")]
@@ -475,19 +1547,41 @@ pub fn get_backend_description(backend: &RendererBackend) -> String {
 }
 
 /// Provide backend-specific capabilities for quick reference.
-pub fn backend_capabilities(backend: &RendererBackend) -> &'static str {
-    match backend {
+pub fn backend_capabilities(backend: &RendererBackend) -> String {
+    let summary = match backend {
         RendererBackend::Orge => "Opixa: fast raster previews; best default choice",
         RendererBackend::Json => "JSON backend outputs metadata only (hidden in picker)",
+        RendererBackend::Svg => {
+            "Svg: scalable outlines; previews show a rasterized thumbnail, export writes .svg"
+        }
         #[cfg(target_os = "macos")]
         RendererBackend::CoreGraphics => "CoreGraphics: macOS native, color-managed previews",
         #[cfg(feature = "render-skia")]
         RendererBackend::Skia => "Skia: GPU-accelerated bitmaps; enable render-skia feature",
         #[cfg(feature = "render-zeno")]
         RendererBackend::Zeno => "Zeno: experimental vector backend",
+    };
+
+    let missing = backend.raster_capabilities().missing();
+    if missing.is_empty() {
+        summary.to_string()
+    } else {
+        format!("{summary}. Ignores: {}", missing.join(", "))
     }
 }
 
+/// Warning for rasterization options the backend will not honor, if any.
+pub fn raster_support_note(backend: &RendererBackend, raster: &RasterOptions) -> Option<String> {
+    let unsupported = raster.unsupported_by(backend.raster_capabilities());
+    (!unsupported.is_empty()).then(|| {
+        format!(
+            "{} does not support {}; rendering with its defaults",
+            backend,
+            unsupported.join(", ")
+        )
+    })
+}
+
 // =============================================================================
 // Error Messages
 // =============================================================================
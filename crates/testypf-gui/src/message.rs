@@ -1,9 +1,20 @@
 //! Message types for testypf GUI application.
 
-use crate::types::{InstallScope, LayoutMode};
+use crate::types::{
+    ComparisonRow, CoverageAnalysis, InstallScope, LayoutMode, MatrixRows, ScaleMode,
+};
 use iced::window;
+use iced::window::screenshot::Screenshot;
 use std::path::PathBuf;
-use testypf_core::RendererBackend;
+use testypf_core::bench::BenchmarkStats;
+use testypf_core::coverage::CoverageMatrix;
+use testypf_core::diff::DiffStyle;
+use testypf_core::export::ExportFormat;
+use testypf_core::normalization::Normalization;
+use testypf_core::overlay::{GuideLine, OverlayLayer};
+use testypf_core::proofs::ProofSection;
+use testypf_core::samples::SampleText;
+use testypf_core::{AntialiasMode, HintingMode, RendererBackend};
 
 /// All possible messages the application can receive.
 #[derive(Debug, Clone)]
@@ -21,6 +32,13 @@ pub enum Message {
 
     // Rendering controls
     SampleTextChanged(String),
+    SamplePicked(SampleText),
+    SampleSuggestionsToggled(bool),
+    SampleEntryNameChanged(String),
+    SaveSampleEntry,
+    RemoveSampleEntry,
+    ProofSectionChanged(ProofSection),
+    LoadSpacingProof,
     FontSizeChanged(String),
     BackendChanged(RendererBackend),
     TestBackend,
@@ -30,8 +48,56 @@ pub enum Message {
     VariationAxisChanged(String, f32),
     LayoutChanged(LayoutMode),
     RenderSelectedOnlyToggled(bool),
+    ScaleModeChanged(ScaleMode),
+    AntialiasChanged(AntialiasMode),
+    HintingChanged(HintingMode),
+    GammaChanged(String),
+    DetectScaleFactor,
+    WindowSizeFetched(iced::Size),
+    ScaleScreenshotTaken(Screenshot),
     ExportPreviews,
+    InspectShaping,
+    NormalizationChanged(Normalization),
+    CompareNormalization,
+    CloseNormalizationCompare,
+    DiffPreviewToggled(usize, bool),
+    DiffStyleChanged(DiffStyle),
+    DiffToleranceChanged(String),
+    OverlayLayerToggled(OverlayLayer, bool),
+    GuideLineToggled(GuideLine, bool),
+    FontTablesToggled(bool),
+    AnalyzeCoverage,
+    CoverageAnalyzed(Vec<CoverageAnalysis>),
+    CoverageQueryChanged(String),
+    MatrixRowsChanged(MatrixRows),
+    MatrixCustomCharsetChanged(String),
+    BuildCoverageMatrix,
+    CoverageMatrixBuilt(Vec<CoverageAnalysis>, Result<CoverageMatrix, String>),
+    MatrixCellSelected(usize, usize),
+    ExportCoverageMatrix,
+    CoverageMatrixDestinationChosen(Option<PathBuf>),
+    ComparisonBackendToggled(RendererBackend, bool),
+    RunComparison,
+    ComparisonFinished(Result<Vec<ComparisonRow>, String>, u128),
+    ExportComparison,
+    ComparisonDestinationChosen(Option<PathBuf>),
+    BenchmarkWarmupChanged(String),
+    BenchmarkIterationsChanged(String),
+    RunBenchmark,
+    BenchmarkFinished(Result<Vec<BenchmarkStats>, String>),
+    SaveBenchmark,
+    BenchmarkDestinationChosen(Option<PathBuf>),
     ExportDestinationChosen(Option<PathBuf>),
+    ExportSidecarToggled(bool),
+    ExportScaleToggled(u32, bool),
+    ExportContactSheet,
+    ContactSheetDestinationChosen(Option<PathBuf>),
+    ExportTemplateChanged(String),
+    ExportFormatChanged(ExportFormat),
+    ExportFlattenToggled(bool),
+    ExportFlattenColorChanged(String),
+    ReproduceFromImage,
+    ReproduceImageChosen(Option<PathBuf>),
 
     // UI actions
     RenderPreviews,
@@ -1,6 +1,9 @@
 //! Custom styles for testypf GUI application.
 
-use iced::{widget::container, Theme};
+use iced::{
+    widget::{button, container},
+    Theme,
+};
 
 /// Custom container style for active drag state.
 pub struct DragActiveStyle;
@@ -27,3 +30,51 @@ impl container::StyleSheet for DragActiveStyle {
         }
     }
 }
+
+/// Solid fill used for benchmark chart bars.
+pub struct BarStyle(pub iced::Color);
+
+impl container::StyleSheet for BarStyle {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            background: Some(iced::Background::Color(self.0)),
+            border: iced::border::Border {
+                radius: 3.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Flat fill for coverage matrix cells, colored by coverage.
+pub struct CoverageCellStyle(pub iced::Color);
+
+impl button::StyleSheet for CoverageCellStyle {
+    type Style = Theme;
+
+    fn active(&self, _style: &Self::Style) -> button::Appearance {
+        button::Appearance {
+            background: Some(iced::Background::Color(self.0)),
+            text_color: iced::Color::from_rgb(0.1, 0.1, 0.1),
+            border: iced::border::Border {
+                radius: 3.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> button::Appearance {
+        button::Appearance {
+            border: iced::border::Border {
+                color: iced::Color::from_rgb(0.2, 0.2, 0.2),
+                width: 1.0,
+                radius: 3.0.into(),
+            },
+            ..self.active(style)
+        }
+    }
+}
@@ -3,19 +3,56 @@
 use crate::app::TestypfApp;
 use crate::helpers;
 use crate::message::Message;
-use crate::types::{AppConfig, DropPathKind, LayoutMode, RenderPreview};
+use crate::types::{
+    AppConfig, ComparisonCell, ComparisonRow, DropPathKind, LayoutMode, MatrixRows, RenderPreview,
+    ScaleMode,
+};
 
 use iced::keyboard;
 use iced::widget::image::Handle;
 use iced::window;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
+use testypf_core::contact_sheet::ContactSheetOptions;
+use testypf_core::coverage::{CoverageSummary, FontCoverage};
+use testypf_core::diff::DiffStyle;
+use testypf_core::escapes;
+use testypf_core::export::{ExportFormat, DEFAULT_NAME_TEMPLATE};
+use testypf_core::languages::LanguageSupport;
+use testypf_core::normalization::{Normalization, NormalizationComparison};
+use testypf_core::overlay::{GuideLine, OverlayLayer, OverlayOptions};
+use testypf_core::proofs::{ControlString, KernPair, ProofSection, SpacingProof};
+use testypf_core::samples::{SampleCategory, SampleText};
+use testypf_core::shaping::ShapingResult;
+use testypf_core::tables::{FontTables, HeadSummary, NameEntry, Os2Summary, TableRecord};
 use testypf_core::{
-    FontliftFontSource, RenderResult, RenderSettings, RendererBackend, TestypfFontInfo,
+    FontliftFontSource, PixelFormat, RenderResult, RenderSettings, RendererBackend, TestypfError,
+    TestypfFontInfo, TestypfResult, TestypfVerticalMetrics, TextRenderer,
 };
 
+/// RGBA8 preview of the first font with every byte set to `fill`
+fn preview_fixture(width: u32, height: u32, fill: u8, duration_ms: u128) -> RenderPreview {
+    let pixels = vec![fill; (width * height * 4) as usize];
+    RenderPreview {
+        font_index: 0,
+        width,
+        height,
+        format: PixelFormat::Rgba8,
+        pixels: pixels.clone().into(),
+        handle: Handle::from_pixels(width, height, pixels),
+        duration_ms,
+        scale_factor: 1.0,
+        svg: None,
+        glyphs: Vec::new(),
+        baseline: None,
+        overlay: None,
+        missing_chars: Vec::new(),
+    }
+}
+
 #[test]
 fn render_window_settings_are_transparent() {
     let settings = TestypfApp::render_window_settings();
@@ -45,8 +82,10 @@ fn image_handle_from_render_accepts_valid_rgba() {
     let render_result = RenderResult {
         width: 2,
         height: 2,
-        data: vec![255; 16],
-        format: "Rgba8".to_string(),
+        data: vec![255; 16].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
     };
 
     let handle =
@@ -66,6 +105,7 @@ fn set_install_state_updates_flag() {
         style: "Regular".into(),
         is_installed: false,
         variation_axes: Vec::new(),
+        vertical_metrics: None,
     }];
 
     let updated = helpers::set_install_state(&mut fonts, 0, true);
@@ -91,8 +131,10 @@ fn image_handle_from_render_rejects_length_mismatch() {
     let render_result = RenderResult {
         width: 2,
         height: 2,
-        data: vec![0; 12],
-        format: "Rgba8".to_string(),
+        data: vec![0; 12].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
     };
 
     let err = helpers::image_handle_from_render(&render_result)
@@ -108,19 +150,10 @@ fn export_preview_writes_png() {
         .unwrap()
         .as_millis();
     let path = env::temp_dir().join(format!("testypf_preview_export_{ts}.png"));
-    let pixels = vec![255u8; 16];
-
-    let preview = RenderPreview {
-        font_index: 0,
-        width: 2,
-        height: 2,
-        format: "Rgba8".to_string(),
-        pixels: pixels.clone(),
-        handle: Handle::from_pixels(2, 2, pixels),
-        duration_ms: 0,
-    };
+    let preview = preview_fixture(2, 2, 255, 0);
 
-    helpers::export_preview_to_path(&preview, &path).expect("export should succeed");
+    helpers::export_preview_as(&preview, &path, ExportFormat::Png, None, None)
+        .expect("export should succeed");
 
     let bytes = fs::read(&path).expect("png should be written");
     assert!(
@@ -131,6 +164,125 @@ fn export_preview_writes_png() {
     fs::remove_file(&path).ok();
 }
 
+#[test]
+fn export_names_skip_stems_taken_by_any_sidecar() {
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let folder = env::temp_dir().join(format!("testypf_export_names_{ts}"));
+    fs::create_dir_all(&folder).unwrap();
+
+    assert_eq!(
+        helpers::unused_export_path(&folder, "demo", "png"),
+        folder.join("demo.png")
+    );
+    fs::write(folder.join("demo.json"), "{}").unwrap();
+    fs::write(folder.join("demo_1.svg"), "<svg/>").unwrap();
+    assert_eq!(
+        helpers::unused_export_path(&folder, "demo", "png"),
+        folder.join("demo_2.png")
+    );
+
+    fs::remove_dir_all(&folder).ok();
+}
+
+#[test]
+fn vector_render_preview_keeps_svg_and_exports_it() {
+    let markup = r#"<svg xmlns="http://www.w3.org/2000/svg" width="8" height="4"><rect width="4" height="4"/></svg>"#;
+    let render_result =
+        testypf_core::vector::svg_result(markup.to_string()).expect("svg should parse");
+
+    let preview = helpers::build_render_preview(0, render_result, 3, 1.0)
+        .expect("vector preview should build");
+
+    assert_eq!((preview.width, preview.height), (8, 4));
+    assert_eq!(preview.pixels.len(), 8 * 4 * 4, "thumbnail should be RGBA");
+    assert_eq!(preview.svg.as_deref(), Some(markup));
+
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let path = env::temp_dir().join(format!("testypf_preview_export_{ts}.svg"));
+    helpers::export_preview_svg_to_path(&preview, &path).expect("svg export should succeed");
+    assert_eq!(fs::read_to_string(&path).expect("svg written"), markup);
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn export_preview_embeds_reproducible_settings() {
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let path = env::temp_dir().join(format!("testypf_preview_provenance_{ts}.png"));
+    let preview = preview_fixture(2, 2, 255, 0);
+    let settings = RenderSettings {
+        sample_text: "Reproduce me".to_string(),
+        ..RenderSettings::default()
+    };
+    let provenance = testypf_core::export::RenderProvenance::capture(
+        &PathBuf::from("demo.ttf"),
+        &settings,
+        "harfbuzz",
+    );
+
+    helpers::export_preview_as(&preview, &path, ExportFormat::Png, None, Some(&provenance))
+        .expect("export should succeed");
+
+    let restored = testypf_core::export::read_provenance(&path).expect("provenance embedded");
+    assert_eq!(restored.settings, settings);
+    assert_eq!(restored.font_path, PathBuf::from("demo.ttf"));
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn reproduced_settings_keep_overlay_state_and_pin_the_scale() {
+    let exported = RenderSettings {
+        sample_text: "A\\u{301}".to_string(),
+        normalization: Normalization::Nfc,
+        glyph_positions: true,
+        scale_factor: 2.0,
+        ..RenderSettings::default()
+    };
+
+    let (settings, mode) = helpers::reproduced_settings(exported.clone(), false);
+    assert!(
+        !settings.glyph_positions,
+        "overlay controls decide glyph positions"
+    );
+    assert_eq!(mode, ScaleMode::X2);
+    assert_eq!(settings.scale_factor, 2.0);
+    assert_eq!(settings.sample_text, exported.sample_text);
+
+    let (settings, mode) = helpers::reproduced_settings(
+        RenderSettings {
+            scale_factor: 1.25,
+            ..exported.clone()
+        },
+        true,
+    );
+    assert!(settings.glyph_positions);
+    assert_eq!((mode, settings.scale_factor), (ScaleMode::X1, 1.0));
+    assert_eq!(ScaleMode::nearest_fixed(f32::NAN), ScaleMode::X1);
+    assert_eq!(ScaleMode::nearest_fixed(3.5), ScaleMode::X3);
+
+    // The decoded sample shown under the text field follows the reproduced settings
+    let decoded = helpers::decode_sample(&settings, None);
+    assert_eq!(decoded.text, "\u{C1}");
+}
+
+#[test]
+fn format_rgba_hex_round_trips_through_parser() {
+    let color = (0x12, 0xAB, 0x00, 0x7F);
+    let hex = helpers::format_rgba_hex(color);
+    assert_eq!(hex, "#12AB007F");
+    assert_eq!(helpers::parse_rgba_hex(&hex), Some(color));
+}
+
 #[test]
 fn scan_folder_for_fonts_collects_stats() {
     let ts = SystemTime::now()
@@ -166,6 +318,10 @@ fn config_round_trip_to_custom_path() {
 
     let cfg = AppConfig {
         backend: RendererBackend::Orge,
+        export_template: "{family}_{size}".to_string(),
+        export_format: ExportFormat::WebP,
+        sample_library: vec![SampleText::custom("Kerning", "AVATAR Type")],
+        scale_mode: ScaleMode::X2,
     };
 
     helpers::save_config_to(&path, &cfg).expect("save config");
@@ -176,6 +332,16 @@ fn config_round_trip_to_custom_path() {
     fs::remove_file(&path).ok();
 }
 
+#[test]
+fn legacy_config_gets_default_export_settings() {
+    let cfg: AppConfig = serde_json::from_str(r#"{"backend":"Orge"}"#).expect("parse config");
+
+    assert_eq!(cfg.export_template, DEFAULT_NAME_TEMPLATE);
+    assert_eq!(cfg.export_format, ExportFormat::Png);
+    assert!(cfg.sample_library.is_empty());
+    assert_eq!(cfg.scale_mode, ScaleMode::Auto);
+}
+
 #[test]
 fn detects_font_extensions() {
     assert!(helpers::is_font_file(&PathBuf::from("font.TTF")));
@@ -321,17 +487,10 @@ fn preview_metadata_text_includes_duration_and_backend() {
         style: "Regular".into(),
         is_installed: true,
         variation_axes: Vec::new(),
+        vertical_metrics: None,
     };
 
-    let preview = RenderPreview {
-        font_index: 0,
-        width: 32,
-        height: 16,
-        format: "Rgba8".to_string(),
-        pixels: vec![255; 32 * 16 * 4],
-        handle: Handle::from_pixels(32, 16, vec![255; 32 * 16 * 4]),
-        duration_ms: 12,
-    };
+    let preview = preview_fixture(32, 16, 255, 12);
 
     let text = helpers::preview_metadata_text(&preview, &font, &RenderSettings::default());
 
@@ -340,6 +499,46 @@ fn preview_metadata_text_includes_duration_and_backend() {
         text.contains(&RenderSettings::default().backend.to_string()),
         "backend label should be present"
     );
+    assert!(!text.contains("Missing"), "full coverage adds nothing");
+}
+
+#[test]
+fn missing_characters_are_summarized_on_previews() {
+    assert_eq!(helpers::missing_characters_summary(&[]), None);
+    assert_eq!(
+        helpers::missing_characters_summary(&['Ж']).as_deref(),
+        Some("Missing 1 character: U+0416 Ж")
+    );
+    let many: Vec<char> = ('α'..='κ').collect();
+    let summary = helpers::missing_characters_summary(&many).unwrap();
+    assert!(summary.starts_with("Missing 10 characters: U+03B1 α, U+03B2 β"));
+    assert!(summary.ends_with("U+03B8 θ, and 2 more"));
+
+    let font = TestypfFontInfo {
+        source: FontliftFontSource::new(PathBuf::from("latin.ttf")),
+        postscript_name: "Latin".into(),
+        full_name: "Latin".into(),
+        family_name: "Latin".into(),
+        style: "Regular".into(),
+        is_installed: false,
+        variation_axes: Vec::new(),
+        vertical_metrics: None,
+    };
+    let render = RenderResult {
+        width: 4,
+        height: 4,
+        data: vec![0u8; 4 * 4 * 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+    let mut preview = helpers::build_render_preview(0, render, 3, 1.0).expect("preview");
+    preview.missing_chars = vec!['中'];
+    let text = helpers::preview_metadata_text(&preview, &font, &RenderSettings::default());
+    assert!(
+        text.ends_with(" | Missing 1 character: U+4E2D 中"),
+        "{text}"
+    );
 }
 
 #[test]
@@ -419,17 +618,10 @@ fn preview_metadata_includes_variations_when_present() {
         style: "Regular".into(),
         is_installed: true,
         variation_axes: Vec::new(),
+        vertical_metrics: None,
     };
 
-    let preview = RenderPreview {
-        font_index: 0,
-        width: 10,
-        height: 10,
-        format: "Rgba8".to_string(),
-        pixels: vec![255; 10 * 10 * 4],
-        handle: Handle::from_pixels(10, 10, vec![255; 10 * 10 * 4]),
-        duration_ms: 5,
-    };
+    let preview = preview_fixture(10, 10, 255, 5);
 
     let mut settings = RenderSettings::default();
     settings.variation_coords.insert("wght".into(), 500.0_f32);
@@ -452,6 +644,7 @@ fn font_metadata_lines_include_path_and_install_state() {
         style: "Bold".into(),
         is_installed: false,
         variation_axes: Vec::new(),
+        vertical_metrics: None,
     };
 
     let lines = helpers::font_metadata_lines(&font, Some(2048));
@@ -528,3 +721,936 @@ fn shortcut_mapping_covers_core_actions() {
         "shortcuts should require command modifier"
     );
 }
+
+#[test]
+fn contact_sheet_follows_layout_columns() {
+    let font = TestypfFontInfo {
+        source: FontliftFontSource::new(PathBuf::from("demo.ttf")),
+        postscript_name: "DemoPS".into(),
+        full_name: "Demo Font".into(),
+        family_name: "Demo".into(),
+        style: "Regular".into(),
+        is_installed: false,
+        variation_axes: Vec::new(),
+        vertical_metrics: None,
+    };
+    let previews: Vec<RenderPreview> = (0..3).map(|_| preview_fixture(8, 8, 0, 1)).collect();
+    let options = |mode: LayoutMode| ContactSheetOptions {
+        columns: mode.columns(),
+        ..ContactSheetOptions::default()
+    };
+
+    let single = helpers::compose_contact_sheet(
+        &previews,
+        std::slice::from_ref(&font),
+        &RenderSettings::default(),
+        &options(LayoutMode::Single),
+    )
+    .expect("single column sheet");
+    let side_by_side = helpers::compose_contact_sheet(
+        &previews,
+        std::slice::from_ref(&font),
+        &RenderSettings::default(),
+        &options(LayoutMode::SideBySide),
+    )
+    .expect("two column sheet");
+
+    assert!(side_by_side.width > single.width);
+    assert!(side_by_side.height < single.height);
+    assert!(helpers::compose_contact_sheet(
+        &[],
+        &[font],
+        &RenderSettings::default(),
+        &options(LayoutMode::Single)
+    )
+    .is_err());
+}
+
+#[test]
+fn single_image_exports_take_the_format_from_the_extension() {
+    let target = |path: &str| helpers::export_target(&PathBuf::from(path), ExportFormat::WebP);
+    assert_eq!(
+        target("/out/sheet.png"),
+        (PathBuf::from("/out/sheet.png"), ExportFormat::Png)
+    );
+    assert_eq!(
+        target("/out/sheet.JPEG"),
+        (PathBuf::from("/out/sheet.JPEG"), ExportFormat::Jpeg)
+    );
+    assert_eq!(target("/out/sheet.tif").1, ExportFormat::Tiff);
+    assert_eq!(
+        target("/out/sheet"),
+        (PathBuf::from("/out/sheet.webp"), ExportFormat::WebP)
+    );
+    assert_eq!(
+        target("/out/sheet.v2"),
+        (PathBuf::from("/out/sheet.webp"), ExportFormat::WebP)
+    );
+}
+
+#[test]
+fn scale_factor_detection_snaps_to_quarter_steps() {
+    assert_eq!(helpers::scale_factor_from_sizes(2048, 1024.0), Some(2.0));
+    assert_eq!(helpers::scale_factor_from_sizes(1536, 1024.0), Some(1.5));
+    assert_eq!(helpers::scale_factor_from_sizes(1025, 1024.0), Some(1.0));
+    assert_eq!(helpers::scale_factor_from_sizes(0, 1024.0), None);
+    assert_eq!(helpers::scale_factor_from_sizes(800, 0.0), None);
+
+    assert_eq!(helpers::export_scale_suffix(1), "");
+    assert_eq!(helpers::export_scale_suffix(2), "@2x");
+    assert_eq!(helpers::export_scale_suffix(3), "@3x");
+}
+
+#[test]
+fn hidpi_previews_display_at_logical_size() {
+    let font = TestypfFontInfo {
+        source: FontliftFontSource::new(PathBuf::from("demo.ttf")),
+        postscript_name: "DemoPS".into(),
+        full_name: "Demo Font".into(),
+        family_name: "Demo".into(),
+        style: "Regular".into(),
+        is_installed: false,
+        variation_axes: Vec::new(),
+        vertical_metrics: None,
+    };
+    let render_result = RenderResult {
+        width: 64,
+        height: 32,
+        data: vec![0; 64 * 32 * 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+
+    let preview = helpers::build_render_preview(0, render_result, 5, 2.0).expect("preview");
+    assert_eq!((preview.width, preview.height), (64, 32));
+    assert_eq!(helpers::preview_logical_size(&preview), (32.0, 16.0));
+
+    let metadata = helpers::preview_metadata_text(&preview, &font, &RenderSettings::default());
+    assert!(metadata.contains("Dimensions: 64x32 | Scale: 2x"));
+}
+
+#[test]
+fn backend_capabilities_list_ignored_raster_options() {
+    let caps = helpers::backend_capabilities(&RendererBackend::Svg);
+    assert!(caps.starts_with("Svg: scalable outlines"));
+    assert!(caps.contains("Ignores: antialiasing off, subpixel LCD, hinting, gamma"));
+
+    let lcd = testypf_core::RasterOptions {
+        antialias: testypf_core::AntialiasMode::Lcd,
+        ..Default::default()
+    };
+    let note = helpers::raster_support_note(&RendererBackend::Orge, &lcd).expect("warning");
+    assert!(note.contains("Opixa does not support subpixel LCD"));
+    assert!(helpers::raster_support_note(&RendererBackend::Orge, &Default::default()).is_none());
+}
+
+#[test]
+fn bgra_premultiplied_renders_become_straight_rgba_previews() {
+    let render_result = RenderResult {
+        width: 1,
+        height: 2,
+        data: vec![0, 0, 128, 128, 10, 20, 30, 255].into(),
+        format: PixelFormat::Bgra8Premultiplied,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+
+    let preview = helpers::build_render_preview(0, render_result, 1, 1.0).expect("preview");
+    assert_eq!(preview.format, PixelFormat::Bgra8Premultiplied);
+    assert_eq!(
+        preview.pixels.to_vec(),
+        vec![255, 0, 0, 128, 30, 20, 10, 255]
+    );
+
+    let gray = RenderResult {
+        width: 2,
+        height: 1,
+        data: vec![0, 255].into(),
+        format: PixelFormat::Gray8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+    assert!(helpers::image_handle_from_render(&gray).is_ok());
+}
+
+#[test]
+fn rgba_previews_share_one_pixel_buffer() {
+    let render_result = RenderResult {
+        width: 4,
+        height: 4,
+        data: vec![7; 4 * 4 * 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+    let source = render_result.data.clone();
+
+    let preview = helpers::build_render_preview(0, render_result, 1, 1.0).expect("preview");
+    assert!(
+        preview.pixels.ptr_eq(&source),
+        "preview reuses the render buffer"
+    );
+
+    let copy = preview.clone();
+    let previews = vec![preview, copy];
+    assert_eq!(helpers::preview_memory_bytes(&previews), 4 * 4 * 4);
+    assert_eq!(
+        helpers::preview_memory_summary(&previews),
+        "Preview memory: 64 B across 2 preview(s)"
+    );
+}
+
+#[test]
+fn benchmark_helpers_parse_config_and_scale_bars() {
+    let config = helpers::parse_benchmark_config(" 1 ", "5").expect("valid config");
+    assert_eq!((config.warmup, config.iterations), (1, 5));
+    assert!(helpers::parse_benchmark_config("x", "5").is_err());
+    assert!(helpers::parse_benchmark_config("0", "0").is_err());
+
+    assert_eq!(helpers::benchmark_bar_width(10.0, 10.0, 300.0), 300.0);
+    assert_eq!(helpers::benchmark_bar_width(5.0, 10.0, 300.0), 150.0);
+    assert_eq!(
+        helpers::benchmark_bar_width(0.0, 10.0, 300.0),
+        2.0,
+        "bars never vanish"
+    );
+}
+
+#[test]
+fn benchmark_cases_cover_every_font_and_backend() {
+    let font = TestypfFontInfo {
+        source: FontliftFontSource::new(PathBuf::from("demo.ttf")),
+        postscript_name: "DemoPS".into(),
+        full_name: "Demo Font".into(),
+        family_name: "Demo".into(),
+        style: "Regular".into(),
+        is_installed: false,
+        variation_axes: Vec::new(),
+        vertical_metrics: None,
+    };
+    let fonts = vec![font.clone(), font];
+    let backends = [RendererBackend::Orge, RendererBackend::Json];
+    let settings = RenderSettings::default();
+
+    let cases = helpers::benchmark_cases(&fonts, &[0, 1, 9], &backends, &settings);
+
+    assert_eq!(cases.len(), 4, "unknown indices are skipped");
+    assert_eq!(cases[0].settings.backend, RendererBackend::Orge);
+    assert_eq!(cases[1].settings.backend, RendererBackend::Json);
+    assert_eq!(cases[1].settings.sample_text, settings.sample_text);
+}
+
+/// Renders a 4x2 opaque bitmap on Orge and fails on every other backend
+struct OrgeOnlyRenderer {
+    backend: RendererBackend,
+}
+
+impl TextRenderer for OrgeOnlyRenderer {
+    fn render_text(
+        &self,
+        _font_path: &PathBuf,
+        settings: &RenderSettings,
+    ) -> TestypfResult<RenderResult> {
+        assert_eq!(
+            settings.backend, self.backend,
+            "settings follow the backend"
+        );
+        if self.backend != RendererBackend::Orge {
+            return Err(TestypfError::RenderFailed("unsupported".into()));
+        }
+        Ok(RenderResult {
+            width: 4,
+            height: 2,
+            data: vec![255; 4 * 2 * 4].into(),
+            format: PixelFormat::Rgba8,
+            glyphs: Vec::new(),
+            baseline: None,
+        })
+    }
+
+    fn get_backends(&self) -> Vec<RendererBackend> {
+        vec![RendererBackend::Orge, RendererBackend::Svg]
+    }
+
+    fn set_backend(&mut self, backend: RendererBackend) -> TestypfResult<()> {
+        self.backend = backend;
+        Ok(())
+    }
+
+    fn backend(&self) -> RendererBackend {
+        self.backend.clone()
+    }
+
+    fn shaper(&self) -> &str {
+        "stub"
+    }
+}
+
+#[test]
+fn render_comparison_renders_each_font_once_per_backend() {
+    let mut renderer = OrgeOnlyRenderer {
+        backend: RendererBackend::Svg,
+    };
+    let fonts = vec![(2, PathBuf::from("a.ttf")), (5, PathBuf::from("b.ttf"))];
+    let backends = [RendererBackend::Orge, RendererBackend::Svg];
+
+    let rows =
+        helpers::render_comparison(&mut renderer, &fonts, &backends, &RenderSettings::default());
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1].font_index, 5);
+    let cells = &rows[1].cells;
+    assert_eq!(cells.len(), 2);
+    assert_eq!(cells[0].backend, RendererBackend::Orge);
+    assert_eq!(cells[0].outcome.as_ref().unwrap().width, 4);
+    assert_eq!(cells[1].backend, RendererBackend::Svg);
+    assert!(cells[1]
+        .outcome
+        .as_ref()
+        .unwrap_err()
+        .contains("unsupported"));
+}
+
+#[test]
+fn comparison_matrix_has_one_column_per_backend() {
+    let available = [
+        RendererBackend::Orge,
+        RendererBackend::Json,
+        RendererBackend::Svg,
+    ];
+    let selected = helpers::toggle_comparison_backend(
+        &available,
+        &[RendererBackend::Svg],
+        &RendererBackend::Orge,
+        true,
+    );
+    assert_eq!(
+        selected,
+        vec![RendererBackend::Orge, RendererBackend::Svg],
+        "available order is kept"
+    );
+    assert!(
+        helpers::toggle_comparison_backend(&available, &selected, &RendererBackend::Svg, false)
+            == vec![RendererBackend::Orge]
+    );
+
+    let font = TestypfFontInfo {
+        source: FontliftFontSource::new(PathBuf::from("demo.ttf")),
+        postscript_name: "DemoPS".into(),
+        full_name: "Demo Font".into(),
+        family_name: "Demo".into(),
+        style: "Regular".into(),
+        is_installed: false,
+        variation_axes: Vec::new(),
+        vertical_metrics: None,
+    };
+    let render = RenderResult {
+        width: 10,
+        height: 6,
+        data: vec![255; 10 * 6 * 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+    let row = ComparisonRow {
+        font_index: 0,
+        cells: vec![
+            ComparisonCell {
+                backend: RendererBackend::Orge,
+                outcome: helpers::build_render_preview(0, render, 3, 1.0),
+            },
+            ComparisonCell {
+                backend: RendererBackend::Svg,
+                outcome: Err("backend unavailable".into()),
+            },
+        ],
+    };
+    assert_eq!(helpers::comparison_cell_text(&row.cells[0]), "3 ms | 10x6");
+    assert_eq!(
+        helpers::comparison_cell_text(&row.cells[1]),
+        "failed: backend unavailable"
+    );
+
+    let options = ContactSheetOptions {
+        min_cell_width: 100,
+        show_captions: false,
+        ..ContactSheetOptions::default()
+    };
+    let matrix = helpers::compose_comparison_matrix(
+        &[row.clone(), row],
+        std::slice::from_ref(&font),
+        &options,
+    )
+    .expect("matrix");
+    // Two backend columns, two font rows of the 6px render, one header line above
+    assert_eq!(matrix.width, 16 * 2 + 100 * 2 + 16);
+    assert_eq!(matrix.height, 16 * 2 + (9 + 16) + 6 * 2 + 16);
+}
+
+#[test]
+fn diff_selection_keeps_two_previews_and_builds_a_diff() {
+    let selection = helpers::toggle_diff_selection(&[], 0, true);
+    let selection = helpers::toggle_diff_selection(&selection, 2, true);
+    assert_eq!(selection, vec![0, 2]);
+    assert_eq!(
+        helpers::toggle_diff_selection(&selection, 1, true),
+        vec![2, 1],
+        "a third pick replaces the oldest"
+    );
+    assert_eq!(
+        helpers::toggle_diff_selection(&selection, 0, false),
+        vec![2]
+    );
+
+    let preview = |value: u8| {
+        let render = RenderResult {
+            width: 4,
+            height: 4,
+            data: vec![value; 4 * 4 * 4].into(),
+            format: PixelFormat::Rgba8,
+            glyphs: Vec::new(),
+            baseline: None,
+        };
+        helpers::build_render_preview(0, render, 1, 1.0).expect("preview")
+    };
+    let previews = vec![preview(10), preview(10), preview(30)];
+
+    let same = helpers::diff_previews(&previews, 0, 1, "0", Default::default()).expect("diff");
+    assert_eq!(same.report.changed_pixels, 0);
+
+    let changed = helpers::diff_previews(&previews, 0, 2, "0", Default::default()).expect("diff");
+    assert_eq!(changed.report.changed_pixels, 16);
+    assert_eq!((changed.width, changed.height), (4, 4));
+
+    let tolerant =
+        helpers::diff_previews(&previews, 0, 2, " 20 ", Default::default()).expect("diff");
+    assert_eq!(tolerant.report.changed_pixels, 0);
+
+    assert!(helpers::diff_previews(&previews, 0, 2, "300", Default::default()).is_err());
+    assert!(helpers::diff_previews(&previews, 0, 7, "0", Default::default()).is_err());
+}
+
+#[test]
+fn shaping_inspector_formats_runs_and_glyph_rows() {
+    let json = r#"{"runs": [{"script": "Arab", "direction": "rtl", "glyphs": [
+        {"glyph_id": 482, "glyph_name": "lam_alef", "cluster": 0, "x_advance": 1120, "y_offset": -12.5}]},
+        {"glyphs": [{"glyph_id": 3, "cluster": 2, "x_advance": 260}]}]}"#;
+    let result = ShapingResult::from_typf_json(json).expect("shaping json");
+
+    assert_eq!(
+        helpers::shaping_run_label(0, &result.runs[0]),
+        "Run 1: Arab, RTL, 1 glyph(s)"
+    );
+    assert_eq!(
+        helpers::shaping_run_label(1, &result.runs[1]),
+        "Run 2: unknown script, unknown direction, 1 glyph(s)"
+    );
+
+    let rows = helpers::shaping_table_rows(&result.runs[0]);
+    assert_eq!(
+        rows[0],
+        ["0", "482", "lam_alef", "0", "1120", "0", "0", "-12.50"].map(String::from)
+    );
+    assert_eq!(helpers::shaping_table_rows(&result.runs[1])[0][2], "—");
+    assert_eq!(rows[0].len(), helpers::SHAPING_COLUMNS.len());
+}
+
+#[test]
+fn overlay_layers_draw_only_when_positions_exist() {
+    let render = RenderResult {
+        width: 8,
+        height: 6,
+        data: vec![0u8; 8 * 6 * 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: testypf_core::overlay::parse_glyph_placements(
+            r#"[{"glyph_id": 1, "cluster": 0, "x": 1, "y": 4, "x_advance": 5, "bbox": [1, 1, 5, 4]}]"#,
+        )
+        .unwrap(),
+        baseline: Some(4.0),
+    };
+    let preview = helpers::build_render_preview(0, render, 1, 1.0).expect("preview");
+    assert_eq!(preview.glyphs.len(), 1, "placements kept on the preview");
+
+    let mut options = OverlayOptions::default();
+    assert!(helpers::preview_overlay_handle(&preview, &options, &[])
+        .unwrap()
+        .is_none());
+    options.set(OverlayLayer::Boxes, true);
+    assert!(helpers::preview_overlay_handle(&preview, &options, &[])
+        .unwrap()
+        .is_some());
+
+    let bare = RenderPreview {
+        glyphs: Vec::new(),
+        baseline: None,
+        ..preview.clone()
+    };
+    assert!(
+        helpers::preview_overlay_handle(&bare, &options, &[])
+            .unwrap()
+            .is_none(),
+        "nothing to draw without positions"
+    );
+}
+
+#[test]
+fn guide_rows_use_reported_or_estimated_baseline() {
+    let metrics = TestypfVerticalMetrics {
+        units_per_em: 1000,
+        hhea_ascender: 800,
+        hhea_descender: -200,
+        x_height: Some(500),
+        ..TestypfVerticalMetrics::default()
+    };
+    let font = TestypfFontInfo {
+        source: FontliftFontSource::new(PathBuf::from("metrics.ttf")),
+        postscript_name: "Metrics".into(),
+        full_name: "Metrics".into(),
+        family_name: "Metrics".into(),
+        style: "Regular".into(),
+        is_installed: false,
+        variation_axes: Vec::new(),
+        vertical_metrics: Some(metrics),
+    };
+    let render = RenderResult {
+        width: 10,
+        height: 80,
+        data: vec![0u8; 10 * 80 * 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: Some(60.0),
+    };
+    let preview = helpers::build_render_preview(0, render, 1, 2.0).expect("preview");
+    let settings = RenderSettings {
+        font_size: 20.0,
+        padding: 5,
+        ..RenderSettings::default()
+    };
+    let guides = [GuideLine::Baseline, GuideLine::XHeight];
+
+    let rows = helpers::preview_guide_rows(&preview, Some(&font), &settings, &guides);
+    assert_eq!(
+        rows,
+        vec![(GuideLine::Baseline, 60.0), (GuideLine::XHeight, 40.0)],
+        "reported baseline, 40 px em at 2x"
+    );
+
+    let estimated = RenderPreview {
+        baseline: None,
+        ..preview.clone()
+    };
+    let rows = helpers::preview_guide_rows(&estimated, Some(&font), &settings, &guides);
+    assert_eq!(
+        rows[0],
+        (GuideLine::Baseline, 42.0),
+        "padding 10 + ascender 32"
+    );
+
+    assert!(helpers::preview_guide_rows(&preview, None, &settings, &guides).is_empty());
+
+    let lines = helpers::font_metadata_lines(&font, None);
+    assert!(lines.contains(&"hhea ascender 800, descender -200, line gap 0".to_string()));
+    assert!(lines.contains(&"x-height 500, cap height —".to_string()));
+    assert!(
+        helpers::preview_overlay_handle(&preview, &OverlayOptions::default(), &rows)
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[test]
+fn font_table_sections_summarize_each_table() {
+    let tables = FontTables {
+        directory: vec![TableRecord {
+            tag: "head".into(),
+            checksum: 0x1234_ABCD,
+            offset: 12,
+            length: 54,
+        }],
+        head: Some(HeadSummary {
+            font_revision: 1.5,
+            units_per_em: 1000,
+            flags: 0x000B,
+            mac_style: 0,
+            created: 0,
+            modified: 86_400,
+            x_min: -50,
+            y_min: -200,
+            x_max: 900,
+            y_max: 800,
+            lowest_rec_ppem: 8,
+            index_to_loc_format: 0,
+        }),
+        hhea: None,
+        os2: Some(Os2Summary {
+            version: 4,
+            weight_class: 400,
+            width_class: 5,
+            fs_type: 0,
+            fs_selection: 0x0040,
+            vendor_id: "TEST".into(),
+            unicode_ranges: 1,
+        }),
+        post: None,
+        maxp: None,
+        names: vec![NameEntry {
+            name_id: 1,
+            platform_id: 3,
+            encoding_id: 1,
+            language_id: 0x0409,
+            value: "Demo".into(),
+        }],
+    };
+
+    let sections = helpers::font_table_sections(&tables);
+    let titles: Vec<_> = sections.iter().map(|(title, _)| title.as_str()).collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Table directory (1 tables)",
+            "head",
+            "OS/2 (version 4)",
+            "name (1 records)"
+        ]
+    );
+    assert_eq!(
+        sections[0].1[0],
+        "head         54 bytes  checksum 0x1234ABCD"
+    );
+    assert!(sections[1]
+        .1
+        .contains(&"Created 1970-01-01, modified 1970-01-02".to_string()));
+    assert!(sections[2]
+        .1
+        .contains(&"Weight class 400 (Regular), width class 5 (Normal)".to_string()));
+    assert!(sections[2]
+        .1
+        .contains(&"fsType 0x0000: Installable".to_string()));
+    assert!(sections[2]
+        .1
+        .contains(&"Unicode ranges (1): Basic Latin".to_string()));
+    assert_eq!(
+        sections[3].1[0],
+        "Family [1] Windows/English (United States): Demo"
+    );
+}
+
+#[test]
+fn coverage_lookup_finds_fonts_mapping_a_character() {
+    assert_eq!(helpers::parse_coverage_query("Ж"), Some('Ж'));
+    assert_eq!(helpers::parse_coverage_query(" U+0416 "), Some('Ж'));
+    assert_eq!(helpers::parse_coverage_query("0x41"), Some('A'));
+    assert_eq!(helpers::parse_coverage_query("416"), Some('Ж'));
+    assert_eq!(helpers::parse_coverage_query("U+D800"), None);
+    assert_eq!(helpers::parse_coverage_query("hello"), None);
+
+    let font = |name: &str| TestypfFontInfo {
+        source: FontliftFontSource::new(PathBuf::from(format!("{name}.ttf"))),
+        postscript_name: name.into(),
+        full_name: name.into(),
+        family_name: name.into(),
+        style: "Regular".into(),
+        is_installed: false,
+        variation_axes: Vec::new(),
+        vertical_metrics: None,
+    };
+    let fonts = vec![
+        font("Latin"),
+        font("Cyrillic"),
+        font("Broken"),
+        font("Pending"),
+    ];
+    let covering = |codepoints: &[u32]| FontCoverage {
+        codepoints: codepoints.iter().copied().collect(),
+    };
+    let mut coverage = HashMap::new();
+    coverage.insert(PathBuf::from("Latin.ttf"), Ok(covering(&[0x41, 0x42])));
+    coverage.insert(PathBuf::from("Cyrillic.ttf"), Ok(covering(&[0x41, 0x416])));
+    coverage.insert(PathBuf::from("Broken.ttf"), Err("no cmap".to_string()));
+
+    assert_eq!(helpers::fonts_covering(&fonts, &coverage, 'A'), vec![0, 1]);
+    assert_eq!(helpers::fonts_covering(&fonts, &coverage, 'Ж'), vec![1]);
+    assert!(helpers::fonts_covering(&fonts, &coverage, 'Z').is_empty());
+
+    let summary = CoverageSummary {
+        name: "Basic Latin".into(),
+        covered: 95,
+        total: 128,
+    };
+    assert_eq!(
+        helpers::coverage_summary_line(&summary),
+        "Basic Latin  95/128  74.2%"
+    );
+}
+
+#[test]
+fn language_support_lines_and_filter_matching() {
+    let support = |code, name, covered| LanguageSupport {
+        code,
+        name,
+        covered,
+        total: 62,
+        missing: Vec::new(),
+    };
+    let languages = vec![
+        support("en", "English", 62),
+        support("de", "German", 62),
+        support("sv", "Swedish", 56),
+    ];
+    assert_eq!(
+        helpers::language_support_lines(&languages),
+        vec!["Supports: English, German", "Partial: Swedish (90%)"]
+    );
+    assert_eq!(
+        helpers::language_support_lines(&[]),
+        vec!["Supports: no bundled language fully"]
+    );
+
+    assert!(helpers::supports_language(&languages, "germ"));
+    assert!(helpers::supports_language(&languages, "en"));
+    assert!(
+        !helpers::supports_language(&languages, "swedish"),
+        "partial support does not match"
+    );
+    assert!(helpers::supports_language(&languages, "de"));
+    assert!(
+        !helpers::supports_language(&languages, "d"),
+        "codes match exactly"
+    );
+}
+
+#[test]
+fn coverage_matrix_builds_from_analyzed_fonts_and_drills_down() {
+    let options = MatrixRows::options();
+    assert_eq!(options.first(), Some(&MatrixRows::Blocks));
+    assert_eq!(options.last(), Some(&MatrixRows::Custom));
+    let labels: Vec<String> = options.iter().map(ToString::to_string).collect();
+    assert!(labels.contains(&"Adobe Latin 3".to_string()) && labels.contains(&"WGL4".to_string()));
+
+    let font = |name: &str| TestypfFontInfo {
+        source: FontliftFontSource::new(PathBuf::from(format!("{name}.ttf"))),
+        postscript_name: name.into(),
+        full_name: name.into(),
+        family_name: "Family".into(),
+        style: name.into(),
+        is_installed: false,
+        variation_axes: Vec::new(),
+        vertical_metrics: None,
+    };
+    let fonts = vec![font("Regular"), font("Broken"), font("Bold")];
+    let mut coverage = HashMap::new();
+    coverage.insert(
+        PathBuf::from("Regular.ttf"),
+        Ok(FontCoverage {
+            codepoints: (0x20..=0x7E).chain(0xA0..=0xFF).collect(),
+        }),
+    );
+    coverage.insert(PathBuf::from("Broken.ttf"), Err("no cmap".to_string()));
+    coverage.insert(
+        PathBuf::from("Bold.ttf"),
+        Ok(FontCoverage {
+            codepoints: (0x41..=0x5A).collect(),
+        }),
+    );
+
+    let latin1 = MatrixRows::options()
+        .into_iter()
+        .find(|rows| rows.to_string() == "Latin-1")
+        .unwrap();
+    let matrix = helpers::build_coverage_matrix(latin1, "", &fonts, &coverage).unwrap();
+    assert_eq!(
+        matrix.fonts,
+        vec!["Regular", "Bold"],
+        "unreadable fonts are skipped"
+    );
+    assert_eq!(matrix.rows[0].label, "Latin-1 (all)");
+    assert_eq!(matrix.rows[0].cells[0].percent(), 100.0);
+    assert_eq!(matrix.rows[0].cells[1].covered, 26);
+
+    let blocks = helpers::build_coverage_matrix(MatrixRows::Blocks, "", &fonts, &coverage).unwrap();
+    assert_eq!(blocks.rows.len(), 2);
+
+    let custom =
+        helpers::build_coverage_matrix(MatrixRows::Custom, "ÄÖ U+0416", &fonts, &coverage).unwrap();
+    assert_eq!(custom.rows[0].cells[0].missing, vec![0x416]);
+    assert!(helpers::build_coverage_matrix(MatrixRows::Custom, "  ", &fonts, &coverage).is_err());
+    assert!(
+        helpers::build_coverage_matrix(MatrixRows::Blocks, "", &fonts[1..2], &coverage).is_err()
+    );
+
+    let lines = helpers::matrix_drill_lines("Bold", "Latin-1 (all)", &matrix.rows[0].cells[1]);
+    assert_eq!(
+        lines[0],
+        "Bold – Latin-1 (all): 26/191 covered, 165 missing"
+    );
+    assert_eq!(
+        lines[1],
+        "U+0020    U+0021 !  U+0022 \"  U+0023 #  U+0024 $  U+0025 %  U+0026 &  U+0027 '"
+    );
+    assert_eq!(lines.len(), 1 + 165usize.div_ceil(8));
+
+    assert_ne!(
+        helpers::coverage_cell_color(100.0),
+        helpers::coverage_cell_color(99.0)
+    );
+    assert_ne!(
+        helpers::coverage_cell_color(0.0),
+        helpers::coverage_cell_color(1.0)
+    );
+}
+
+#[test]
+fn sample_library_lists_suggestions_and_saves_user_entries() {
+    let mut user_entries = Vec::new();
+    assert!(helpers::save_sample_entry(&mut user_entries, "  ", "Hamburgefonstiv").is_err());
+    assert!(helpers::save_sample_entry(&mut user_entries, "Mine", " ").is_err());
+    helpers::save_sample_entry(&mut user_entries, " Mine ", "Hamburgefonstiv").unwrap();
+    let status = helpers::save_sample_entry(&mut user_entries, "Mine", "Hamburgefonts").unwrap();
+    assert!(status.starts_with("Updated"));
+    assert_eq!(user_entries.len(), 1);
+    assert_eq!(user_entries[0].category, SampleCategory::Custom);
+    assert_eq!(user_entries[0].script.as_deref(), Some("Latin"));
+    assert_eq!(
+        helpers::current_user_sample(&user_entries, "Hamburgefonts"),
+        Some(0)
+    );
+    assert_eq!(
+        helpers::current_user_sample(&user_entries, "Hamburgefonstiv"),
+        None
+    );
+
+    let everything = helpers::sample_choices(&user_entries, None, true);
+    assert_eq!(everything.last(), user_entries.last());
+    assert!(
+        everything.len() > 30,
+        "without coverage the whole library is offered"
+    );
+
+    let ascii = FontCoverage {
+        codepoints: (0x20..=0x7E).collect(),
+    };
+    let suggested = helpers::sample_choices(&user_entries, Some(&ascii), true);
+    assert!(suggested.iter().any(|s| s.name == "English"));
+    assert!(suggested.iter().any(|s| s.name == "Mine"));
+    assert!(suggested
+        .iter()
+        .all(|s| s.script.as_deref() != Some("Cyrillic")));
+    assert_eq!(
+        helpers::sample_choices(&user_entries, Some(&ascii), false).len(),
+        everything.len()
+    );
+}
+
+#[test]
+fn decoded_sample_lines_reveal_escapes_and_invisibles() {
+    let plain = "The quick brown fox";
+    assert!(helpers::decoded_sample_lines(plain, &escapes::decode(plain, None)).is_empty());
+
+    let escaped = "a\\u{200D}b /uni0416 /ampersand";
+    let lines = helpers::decoded_sample_lines(escaped, &escapes::decode(escaped, None));
+    assert_eq!(lines[0], "Shaped as: a⟨U+200D⟩b Ж /ampersand");
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].starts_with("Escape kept as typed: /ampersand"));
+
+    // Typed invisibles are revealed even without escapes
+    let typed = "e\u{301}";
+    assert_eq!(
+        helpers::decoded_sample_lines(typed, &escapes::decode(typed, None)),
+        vec!["Shaped as: e⟨U+0301⟩".to_string()]
+    );
+}
+
+#[test]
+fn normalization_comparison_is_prepared_with_a_verdict() {
+    let solid = |width: u32| RenderResult {
+        width,
+        height: 2,
+        data: vec![255; width as usize * 2 * 4].into(),
+        format: PixelFormat::Rgba8,
+        glyphs: Vec::new(),
+        baseline: None,
+    };
+    let comparison = |nfc: &str, nfd: &str, nfc_width: u32, nfd_width: u32| {
+        let (nfc_render, nfd_render) = (solid(nfc_width), solid(nfd_width));
+        NormalizationComparison {
+            nfc_text: nfc.to_string(),
+            nfd_text: nfd.to_string(),
+            diff: testypf_core::diff::compare(&nfc_render, &nfd_render, 0, DiffStyle::Heatmap)
+                .unwrap(),
+            nfc: nfc_render,
+            nfd: nfd_render,
+        }
+    };
+
+    let differs = helpers::normalization_compare(3, &comparison("é", "e\u{301}", 1, 2), 2.0)
+        .expect("prepared");
+    assert_eq!(differs.font_index, 3);
+    assert_eq!(
+        (differs.nfc.width, differs.nfd.width, differs.diff.width),
+        (1, 2, 2)
+    );
+    assert_eq!(
+        helpers::normalization_verdict(&differs),
+        "NFC and NFD render differently: 2 of 4 pixels (1 vs 2 codepoints)"
+    );
+
+    let identical =
+        helpers::normalization_compare(0, &comparison("é", "e\u{301}", 2, 2), 1.0).unwrap();
+    assert_eq!(
+        helpers::normalization_verdict(&identical),
+        "NFC and NFD render identically (1 vs 2 codepoints)"
+    );
+
+    let same = helpers::normalization_compare(0, &comparison("abc", "abc", 3, 3), 1.0).unwrap();
+    assert!(helpers::normalization_verdict(&same).contains("nothing to compare"));
+}
+
+#[test]
+fn spacing_proof_sections_load_into_the_sample_text() {
+    let pair = |text: &str, value: i32| KernPair {
+        text: text.to_string(),
+        left_glyph: 1,
+        right_glyph: 2,
+        value,
+        classes: None,
+    };
+    let proof = SpacingProof {
+        controls: "AB"
+            .chars()
+            .map(|letter| ControlString {
+                letter,
+                text: format!("HOH{letter}HOH"),
+            })
+            .collect(),
+        kern_pairs: (0..250).map(|i| pair("AV", -250 + i)).collect(),
+        class_pairs: Vec::new(),
+        unreachable_pairs: 3,
+        letters_without_controls: 4,
+    };
+
+    let (text, status) =
+        helpers::spacing_proof_sample("Sans", &proof, ProofSection::Controls).unwrap();
+    assert_eq!(text, "HOHAHOH HOHBHOH");
+    assert_eq!(
+        status,
+        "Sans: Control strings, 2 (4 letters skipped: no controls in the font)"
+    );
+
+    let (text, status) =
+        helpers::spacing_proof_sample("Sans", &proof, ProofSection::KernPairs).unwrap();
+    assert_eq!(
+        text.split(' ').count(),
+        helpers::SPACING_PROOF_LIMIT,
+        "long pair lists are capped"
+    );
+    assert_eq!(
+        status,
+        "Sans: Kern pairs, first 200 of 250 (3 pairs skipped: glyphs without a codepoint or name)"
+    );
+
+    assert_eq!(
+        helpers::spacing_proof_sample("Sans", &proof, ProofSection::ClassPairs).unwrap_err(),
+        "Sans: no class kern pairs in the GPOS kern feature"
+    );
+}
@@ -1,7 +1,16 @@
 //! Type definitions for testypf GUI application.
 
 use iced::widget::image::Handle;
-use testypf_core::{FontScope, RendererBackend};
+use std::path::PathBuf;
+use testypf_core::charsets::{Charset, CHARSETS};
+use testypf_core::coverage::FontCoverage;
+use testypf_core::diff::DiffReport;
+use testypf_core::export::{ExportFormat, DEFAULT_NAME_TEMPLATE};
+use testypf_core::languages::LanguageSupport;
+use testypf_core::overlay::GlyphPlacement;
+use testypf_core::samples::SampleText;
+use testypf_core::shaping::ShapingResult;
+use testypf_core::{FontScope, PixelBuffer, PixelFormat, RendererBackend, TestypfFontInfo};
 
 /// Font installation scope (user vs system).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -45,6 +54,14 @@ impl LayoutMode {
     pub fn options() -> Vec<Self> {
         vec![Self::Single, Self::SideBySide]
     }
+
+    /// Number of preview columns shown on screen for this layout.
+    pub fn columns(self) -> usize {
+        match self {
+            LayoutMode::Single => 1,
+            LayoutMode::SideBySide => 2,
+        }
+    }
 }
 
 impl std::fmt::Display for LayoutMode {
@@ -56,6 +73,168 @@ impl std::fmt::Display for LayoutMode {
     }
 }
 
+/// Diff between two selected previews, ready for display.
+#[derive(Clone)]
+pub struct PreviewDiff {
+    /// Positions of the compared previews in `render_previews`
+    pub first: usize,
+    pub second: usize,
+    pub report: DiffReport,
+    pub width: u32,
+    pub height: u32,
+    /// Device pixels per logical pixel, taken from the first preview
+    pub scale_factor: f32,
+    pub handle: Handle,
+}
+
+/// Shaping output for one font, shown in the Shaping inspector.
+#[derive(Debug, Clone)]
+pub struct ShapingInspection {
+    pub font_index: usize,
+    /// Sample text the font was shaped with
+    pub text: String,
+    pub result: Result<ShapingResult, String>,
+}
+
+/// cmap coverage and language support of one font, analyzed off the UI thread.
+#[derive(Debug, Clone)]
+pub struct CoverageAnalysis {
+    pub path: PathBuf,
+    pub coverage: Result<FontCoverage, String>,
+    pub languages: Vec<LanguageSupport>,
+}
+
+impl CoverageAnalysis {
+    /// Read the font's cmap and match it against the bundled language exemplars.
+    pub fn of(font: &TestypfFontInfo) -> Self {
+        let coverage = font.coverage().map_err(|e| e.to_string());
+        let languages = coverage
+            .as_ref()
+            .map(FontCoverage::languages)
+            .unwrap_or_default();
+        Self {
+            path: font.path().clone(),
+            coverage,
+            languages,
+        }
+    }
+}
+
+/// A render ready to show at its device-pixel size.
+#[derive(Clone)]
+pub struct RenderedImage {
+    pub handle: Handle,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The sample text rendered as NFC and as NFD with one font, and their pixel diff.
+#[derive(Clone)]
+pub struct NormalizationCompare {
+    pub font_index: usize,
+    /// Decoded text in each form, as shaped
+    pub nfc_text: String,
+    pub nfd_text: String,
+    pub report: DiffReport,
+    pub nfc: RenderedImage,
+    pub nfd: RenderedImage,
+    pub diff: RenderedImage,
+    /// Device pixels per logical pixel the forms were rendered at
+    pub scale_factor: f32,
+}
+
+/// One font rendered once per compared backend, in column order.
+#[derive(Debug, Clone)]
+pub struct ComparisonRow {
+    pub font_index: usize,
+    pub cells: Vec<ComparisonCell>,
+}
+
+/// A single backend's render in the comparison matrix.
+#[derive(Debug, Clone)]
+pub struct ComparisonCell {
+    pub backend: RendererBackend,
+    pub outcome: Result<RenderPreview, String>,
+}
+
+/// Render scale selection: follow the window's scale or force a fixed factor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum ScaleMode {
+    #[default]
+    Auto,
+    X1,
+    X2,
+    X3,
+}
+
+impl ScaleMode {
+    pub const OPTIONS: [ScaleMode; 4] =
+        [ScaleMode::Auto, ScaleMode::X1, ScaleMode::X2, ScaleMode::X3];
+
+    /// Fixed mode closest to `factor` (1× for non-finite factors).
+    pub fn nearest_fixed(factor: f32) -> ScaleMode {
+        if !factor.is_finite() || factor < 1.5 {
+            ScaleMode::X1
+        } else if factor < 2.5 {
+            ScaleMode::X2
+        } else {
+            ScaleMode::X3
+        }
+    }
+
+    /// Fixed factor for manual modes, `None` when following the window.
+    pub fn factor(self) -> Option<f32> {
+        match self {
+            ScaleMode::Auto => None,
+            ScaleMode::X1 => Some(1.0),
+            ScaleMode::X2 => Some(2.0),
+            ScaleMode::X3 => Some(3.0),
+        }
+    }
+}
+
+impl std::fmt::Display for ScaleMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaleMode::Auto => write!(f, "Auto (window)"),
+            ScaleMode::X1 => write!(f, "1×"),
+            ScaleMode::X2 => write!(f, "2×"),
+            ScaleMode::X3 => write!(f, "3×"),
+        }
+    }
+}
+
+/// Rows of the coverage matrix: Unicode blocks, a built-in charset or a typed one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatrixRows {
+    #[default]
+    Blocks,
+    Charset(Charset),
+    Custom,
+}
+
+impl MatrixRows {
+    pub fn options() -> Vec<MatrixRows> {
+        std::iter::once(MatrixRows::Blocks)
+            .chain(CHARSETS.iter().copied().map(MatrixRows::Charset))
+            .chain(std::iter::once(MatrixRows::Custom))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for MatrixRows {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatrixRows::Blocks => write!(f, "Unicode blocks"),
+            MatrixRows::Charset(charset) => write!(f, "{}", charset),
+            MatrixRows::Custom => write!(f, "Custom charset"),
+        }
+    }
+}
+
+/// Scale factors offered as export variants.
+pub const EXPORT_SCALES: [u32; 3] = [1, 2, 3];
+
 /// Render availability state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RenderAvailability {
@@ -134,18 +313,48 @@ pub enum DropPathKind {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 pub struct AppConfig {
     pub backend: RendererBackend,
+    /// File name template used by preview exports
+    #[serde(default = "default_export_template")]
+    pub export_template: String,
+    /// Image format used by preview exports
+    #[serde(default)]
+    pub export_format: ExportFormat,
+    /// Sample texts the user added to the library
+    #[serde(default)]
+    pub sample_library: Vec<SampleText>,
+    /// Render scale; a fixed factor means the window is never measured
+    #[serde(default)]
+    pub scale_mode: ScaleMode,
+}
+
+fn default_export_template() -> String {
+    DEFAULT_NAME_TEMPLATE.to_string()
 }
 
 /// Rendered font preview data.
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct RenderPreview {
     pub font_index: usize,
     pub width: u32,
     pub height: u32,
-    pub format: String,
-    pub pixels: Vec<u8>,
+    /// Pixel format typf produced; `pixels` are always straight RGBA8
+    pub format: PixelFormat,
+    /// Straight RGBA8 pixels, shared with `handle` rather than copied
+    pub pixels: PixelBuffer,
     pub handle: Handle,
     pub duration_ms: u128,
+    /// Device pixels per logical pixel the preview was rendered at
+    pub scale_factor: f32,
+    /// Original SVG markup when the backend produced vector output
+    pub svg: Option<String>,
+    /// Glyph positions in the same device pixels as `pixels`; empty unless typf reported them
+    pub glyphs: Vec<GlyphPlacement>,
+    /// Baseline of the first line in device pixels from the top, when the renderer reported it
+    pub baseline: Option<f32>,
+    /// Copy of the image with glyph overlays drawn on, when any overlay layer is enabled
+    pub overlay: Option<Handle>,
+    /// Sample-text characters the font's cmap does not map (rendered as .notdef)
+    pub missing_chars: Vec<char>,
 }
 
 /// Supported font file extensions.
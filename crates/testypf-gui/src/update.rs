@@ -3,22 +3,94 @@
 use crate::app::TestypfApp;
 use crate::helpers;
 use crate::message::Message;
-use crate::types::{AppConfig, DropPathKind, ScanStats};
+use crate::types::{CoverageAnalysis, DropPathKind, ScanStats, ShapingInspection};
 
 use iced::{window, Command};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
-use testypf_core::FontliftFontSource;
+use testypf_core::bench::{self, BenchmarkStats};
+use testypf_core::contact_sheet::ContactSheetOptions;
+use testypf_core::coverage::CoverageMatrix;
+use testypf_core::export::{render_name_template, NameFields, RenderProvenance};
+use testypf_core::{FontliftFontSource, TestypfEngine, TestypfFontInfo};
 
 /// Handle an incoming message and return any resulting command.
 pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message> {
     match message {
         Message::SampleTextChanged(text) => {
             app.render_settings.sample_text = text;
+            app.refresh_decoded_sample();
             app.status = "Sample text updated".to_string();
             app.invalidate_render_cache();
         }
 
+        Message::SamplePicked(sample) => {
+            app.status = format!("Sample text: {}", sample);
+            app.render_settings.sample_text = sample.text;
+            app.refresh_decoded_sample();
+            app.invalidate_render_cache();
+        }
+
+        Message::SampleSuggestionsToggled(enabled) => {
+            app.sample_suggestions_only = enabled;
+        }
+
+        Message::SampleEntryNameChanged(name) => {
+            app.sample_entry_name = name;
+        }
+
+        Message::ProofSectionChanged(section) => {
+            app.proof_section = section;
+        }
+
+        Message::LoadSpacingProof => {
+            let Some(font) = app.selected_font.and_then(|index| app.fonts.get(index)) else {
+                app.status = "Select a font to load its spacing proof".to_string();
+                return Command::none();
+            };
+            let sample = font
+                .spacing_proof()
+                .map_err(|e| e.to_string())
+                .and_then(|proof| {
+                    helpers::spacing_proof_sample(&font.full_name, &proof, app.proof_section)
+                });
+            match sample {
+                Ok((text, status)) => {
+                    app.status = status;
+                    app.render_settings.sample_text = text;
+                    app.refresh_decoded_sample();
+                    app.invalidate_render_cache();
+                }
+                Err(e) => app.status = e,
+            }
+        }
+
+        Message::SaveSampleEntry => {
+            match helpers::save_sample_entry(
+                &mut app.sample_library,
+                &app.sample_entry_name,
+                &app.render_settings.sample_text,
+            ) {
+                Ok(status) => {
+                    app.sample_entry_name.clear();
+                    app.status = status;
+                    app.save_config();
+                }
+                Err(e) => app.status = e,
+            }
+        }
+
+        Message::RemoveSampleEntry => {
+            if let Some(index) =
+                helpers::current_user_sample(&app.sample_library, &app.render_settings.sample_text)
+            {
+                let removed = app.sample_library.remove(index);
+                app.status = format!("Removed sample \"{}\" from the library", removed.name);
+                app.save_config();
+            }
+        }
+
         Message::FontSizeChanged(size_str) => {
             if let Ok(size) = size_str.parse::<f32>() {
                 app.render_settings.font_size = size;
@@ -32,12 +104,8 @@ pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message
         Message::BackendChanged(backend) => {
             let _ = app.engine.text_renderer().set_backend(backend.clone());
             app.render_settings.backend = backend.clone();
-            if let Err(e) = helpers::save_config(&AppConfig {
-                backend: backend.clone(),
-            }) {
-                eprintln!("Failed to persist backend selection: {}", e);
-            }
-            app.status = format!("Backend changed to {}", backend);
+            app.save_config();
+            app.status = raster_status(app, format!("Backend changed to {}", backend));
             app.invalidate_render_cache();
         }
 
@@ -110,6 +178,70 @@ pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message
             }
         }
 
+        Message::AntialiasChanged(mode) => {
+            app.render_settings.raster.antialias = mode;
+            app.invalidate_render_cache();
+            app.status = raster_status(app, format!("Antialiasing: {}", mode));
+        }
+
+        Message::HintingChanged(mode) => {
+            app.render_settings.raster.hinting = mode;
+            app.invalidate_render_cache();
+            app.status = raster_status(app, format!("Hinting: {}", mode));
+        }
+
+        Message::GammaChanged(value) => {
+            app.gamma_input = value.clone();
+            match value.trim().parse::<f32>() {
+                Ok(gamma) if gamma.is_finite() && gamma > 0.0 => {
+                    app.render_settings.raster.gamma = gamma;
+                    app.invalidate_render_cache();
+                    app.status = raster_status(app, format!("Gamma set to {}", gamma));
+                }
+                _ => {
+                    app.status = "Gamma must be a positive number (1.0 = linear)".to_string();
+                }
+            }
+        }
+
+        Message::ScaleModeChanged(mode) => {
+            app.scale_mode = mode;
+            app.apply_scale_factor();
+            app.save_config();
+            app.status = format!(
+                "Rendering at {}× device scale",
+                app.render_settings.scale_factor
+            );
+        }
+
+        Message::DetectScaleFactor => {
+            app.status = "Measuring window scale factor...".to_string();
+            return window::fetch_size(window::Id::MAIN, Message::WindowSizeFetched);
+        }
+
+        Message::WindowSizeFetched(size) => {
+            app.window_logical_size = Some(size);
+            // Fallback for the scale factor iced does not expose: screenshots are in physical
+            // pixels, so comparing with the logical size gives the scale
+            return window::screenshot(window::Id::MAIN, Message::ScaleScreenshotTaken);
+        }
+
+        Message::ScaleScreenshotTaken(screenshot) => {
+            let detected = app.window_logical_size.and_then(|size| {
+                helpers::scale_factor_from_sizes(screenshot.size.width, size.width)
+            });
+            match detected {
+                Some(scale) => {
+                    app.detected_scale_factor = Some(scale);
+                    app.apply_scale_factor();
+                    app.status = format!("Detected {}× window scale factor", scale);
+                }
+                None => {
+                    app.status = "Could not detect window scale factor; using 1×".to_string();
+                }
+            }
+        }
+
         Message::InstallScopeChanged(scope) => {
             app.install_scope = scope;
             app.engine
@@ -221,7 +353,7 @@ pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message
                 }
                 app.status = "Font removed".to_string();
                 app.invalidate_render_cache();
-                app.render_previews.clear();
+                app.clear_render_previews();
             }
         }
 
@@ -232,6 +364,7 @@ pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message
                     app.render_settings.variation_coords.clear();
                     app.status = "Font details hidden; variations reset".to_string();
                     app.invalidate_render_cache();
+                    app.refresh_decoded_sample();
                 } else {
                     app.selected_font = Some(index);
                     helpers::sync_variations_for_axes(
@@ -239,6 +372,9 @@ pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message
                         &app.fonts[index].variation_axes,
                     );
                     app.invalidate_render_cache();
+                    app.refresh_font_tables();
+                    app.ensure_coverage(index);
+                    app.refresh_decoded_sample();
                     app.status = format!("Showing details for {}", app.fonts[index].full_name);
                 }
             }
@@ -270,6 +406,8 @@ pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message
                 app.visible_font_indices().len(),
                 app.fonts.len()
             );
+            // Language names match too, which needs every font's coverage
+            return app.analyze_coverage_in_background();
         }
 
         Message::InstallFont(index) => {
@@ -348,7 +486,7 @@ pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message
                 return Command::none();
             }
 
-            app.status = "Choose a folder to save PNG previews...".to_string();
+            app.status = "Choose a folder to save PNG/SVG previews...".to_string();
             return Command::perform(
                 async { rfd::FileDialog::new().pick_folder() },
                 Message::ExportDestinationChosen,
@@ -358,7 +496,7 @@ pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message
         Message::ExportDestinationChosen(destination) => match destination {
             Some(folder) => match export_previews_to_folder(app, &folder) {
                 Ok(written) => {
-                    app.status = format!("Exported {} preview(s) to {}", written, folder.display());
+                    app.status = format!("Exported {} image(s) to {}", written, folder.display());
                 }
                 Err(e) => {
                     app.status = format!("Export failed: {}", e);
@@ -369,6 +507,503 @@ pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message
             }
         },
 
+        Message::ExportContactSheet => {
+            if app.render_previews.is_empty() {
+                app.status = "Render previews before exporting a contact sheet".to_string();
+                return Command::none();
+            }
+
+            app.status = "Choose where to save the contact sheet...".to_string();
+            let file_name = format!("contact_sheet.{}", app.export_format.extension());
+            return Command::perform(
+                async move { rfd::FileDialog::new().set_file_name(file_name).save_file() },
+                Message::ContactSheetDestinationChosen,
+            );
+        }
+
+        Message::ContactSheetDestinationChosen(destination) => match destination {
+            Some(path) => match export_contact_sheet(app, &path) {
+                Ok((width, height)) => {
+                    app.status = format!(
+                        "Exported {}x{} contact sheet with {} preview(s) to {}",
+                        width,
+                        height,
+                        app.render_previews.len(),
+                        helpers::export_target(&path, app.export_format).0.display()
+                    );
+                }
+                Err(e) => {
+                    app.status = format!("Contact sheet export failed: {}", e);
+                }
+            },
+            None => {
+                app.status = "Contact sheet export cancelled".to_string();
+            }
+        },
+
+        Message::InspectShaping => {
+            let Some(font_index) = app.selected_font else {
+                app.status = "Select a font to inspect its shaping".to_string();
+                return Command::none();
+            };
+            let Some(font) = app.fonts.get(font_index) else {
+                return Command::none();
+            };
+
+            let result = app
+                .engine
+                .text_renderer()
+                .shape_text(font.path(), &app.render_settings)
+                .map_err(|e| e.to_string());
+            app.status = match &result {
+                Ok(shaped) => format!(
+                    "Shaped {} glyph(s) in {} run(s) for {}",
+                    shaped.glyph_count(),
+                    shaped.runs.len(),
+                    font.full_name
+                ),
+                Err(e) => format!("Shaping failed for {}: {}", font.full_name, e),
+            };
+            app.shaping_inspection = Some(ShapingInspection {
+                font_index,
+                text: app.render_settings.sample_text.clone(),
+                result,
+            });
+        }
+
+        Message::NormalizationChanged(normalization) => {
+            app.render_settings.normalization = normalization;
+            app.refresh_decoded_sample();
+            app.status = format!("Normalization: {}", normalization);
+            app.invalidate_render_cache();
+        }
+
+        Message::CompareNormalization => {
+            let Some(font_index) = app.selected_font else {
+                app.status = "Select a font to compare NFC and NFD".to_string();
+                return Command::none();
+            };
+            let Some(font) = app.fonts.get(font_index) else {
+                return Command::none();
+            };
+            let Ok(tolerance) = app.diff_tolerance_input.trim().parse::<u8>() else {
+                app.status = "Tolerance must be a whole number from 0 to 255".to_string();
+                return Command::none();
+            };
+
+            let compare = app
+                .engine
+                .compare_normalization(font.path(), &app.render_settings, tolerance, app.diff_style)
+                .map_err(|e| e.to_string())
+                .and_then(|comparison| {
+                    helpers::normalization_compare(
+                        font_index,
+                        &comparison,
+                        app.render_settings.scale_factor,
+                    )
+                });
+            app.status = match &compare {
+                Ok(compare) => format!(
+                    "{}: {}",
+                    font.full_name,
+                    helpers::normalization_verdict(compare)
+                ),
+                Err(e) => helpers::friendly_render_error(&font.full_name, e),
+            };
+            app.normalization_compare = Some(compare);
+        }
+
+        Message::CloseNormalizationCompare => {
+            app.normalization_compare = None;
+        }
+
+        Message::DiffPreviewToggled(position, enabled) => {
+            app.diff_selection =
+                helpers::toggle_diff_selection(&app.diff_selection, position, enabled);
+            app.refresh_preview_diff();
+            app.status = match &app.preview_diff {
+                Some(Ok(diff)) => format!("Diff: {}", diff.report.summary()),
+                Some(Err(e)) => format!("Diff failed: {}", e),
+                None => format!(
+                    "{} of 2 previews selected for diff",
+                    app.diff_selection.len()
+                ),
+            };
+        }
+
+        Message::DiffStyleChanged(style) => {
+            app.diff_style = style;
+            app.refresh_preview_diff();
+        }
+
+        Message::DiffToleranceChanged(value) => {
+            app.diff_tolerance_input = value;
+            app.refresh_preview_diff();
+        }
+
+        Message::AnalyzeCoverage => {
+            app.coverage_report_requested = true;
+            app.status = format!(
+                "Analyzing character coverage of {} fonts...",
+                app.fonts.len()
+            );
+            let command = app.analyze_coverage_in_background();
+            report_coverage_if_done(app);
+            return command;
+        }
+
+        Message::CoverageAnalyzed(analyses) => {
+            app.store_coverage(analyses);
+            if !app.font_filter.trim().is_empty() {
+                app.status = format!(
+                    "Filtered fonts: showing {} of {}",
+                    app.visible_font_indices().len(),
+                    app.fonts.len()
+                );
+            }
+            report_coverage_if_done(app);
+        }
+
+        Message::CoverageQueryChanged(query) => {
+            app.coverage_query = query;
+            return app.analyze_coverage_in_background();
+        }
+
+        Message::MatrixRowsChanged(rows) => {
+            app.matrix_rows = rows;
+        }
+
+        Message::MatrixCustomCharsetChanged(value) => {
+            app.matrix_custom_charset = value;
+        }
+
+        Message::BuildCoverageMatrix => {
+            let fonts = app.fonts.clone();
+            let mut coverage = app.coverage.clone();
+            let missing: Vec<TestypfFontInfo> = fonts
+                .iter()
+                .filter(|font| !coverage.contains_key(font.path()))
+                .cloned()
+                .collect();
+            app.coverage_pending
+                .extend(missing.iter().map(|font| font.path().clone()));
+            let rows = app.matrix_rows;
+            let custom_charset = app.matrix_custom_charset.clone();
+            app.status = "Building coverage matrix...".to_string();
+            return Command::perform(
+                async move {
+                    let analyses: Vec<CoverageAnalysis> =
+                        missing.iter().map(CoverageAnalysis::of).collect();
+                    for analysis in &analyses {
+                        coverage
+                            .entry(analysis.path.clone())
+                            .or_insert_with(|| analysis.coverage.clone());
+                    }
+                    let matrix =
+                        helpers::build_coverage_matrix(rows, &custom_charset, &fonts, &coverage);
+                    (analyses, matrix)
+                },
+                |(analyses, matrix)| Message::CoverageMatrixBuilt(analyses, matrix),
+            );
+        }
+
+        Message::CoverageMatrixBuilt(analyses, matrix) => {
+            app.store_coverage(analyses);
+            report_coverage_if_done(app);
+            app.status = match &matrix {
+                Ok(matrix) => format!(
+                    "Coverage matrix: {} row(s) across {} font(s)",
+                    matrix.rows.len(),
+                    matrix.fonts.len()
+                ),
+                Err(e) => format!("Coverage matrix failed: {}", e),
+            };
+            app.coverage_matrix = Some(matrix);
+            app.matrix_drill = None;
+        }
+
+        Message::MatrixCellSelected(row, column) => {
+            app.matrix_drill = if app.matrix_drill == Some((row, column)) {
+                None
+            } else {
+                Some((row, column))
+            };
+        }
+
+        Message::ExportCoverageMatrix => {
+            if !matches!(app.coverage_matrix, Some(Ok(_))) {
+                app.status = "Build a coverage matrix before exporting".to_string();
+                return Command::none();
+            }
+            app.status =
+                "Choose a folder for coverage-matrix.json and coverage-matrix.csv...".to_string();
+            return Command::perform(
+                async { rfd::FileDialog::new().pick_folder() },
+                Message::CoverageMatrixDestinationChosen,
+            );
+        }
+
+        Message::CoverageMatrixDestinationChosen(destination) => {
+            match (destination, &app.coverage_matrix) {
+                (Some(folder), Some(Ok(matrix))) => match save_coverage_matrix(matrix, &folder) {
+                    Ok(()) => {
+                        app.status = format!("Saved coverage matrix to {}", folder.display());
+                    }
+                    Err(e) => {
+                        app.status = format!("Saving coverage matrix failed: {}", e);
+                    }
+                },
+                _ => {
+                    app.status = "Coverage matrix export cancelled".to_string();
+                }
+            }
+        }
+
+        Message::FontTablesToggled(enabled) => {
+            app.show_font_tables = enabled;
+            app.refresh_font_tables();
+        }
+
+        Message::GuideLineToggled(guide, enabled) => {
+            app.guide_lines.retain(|&g| g != guide);
+            if enabled {
+                app.guide_lines.push(guide);
+            }
+            app.refresh_preview_overlays();
+        }
+
+        Message::OverlayLayerToggled(layer, enabled) => {
+            app.overlay_options.set(layer, enabled);
+            // Only ask typf for positions while an overlay needs them
+            app.render_settings.glyph_positions = app.overlay_options.any();
+            app.refresh_preview_overlays();
+            if app.overlay_options.any() && app.render_previews.iter().any(|p| p.glyphs.is_empty())
+            {
+                app.status =
+                    "Render again to fetch glyph positions for the overlay (not every backend reports them)"
+                        .to_string();
+            }
+        }
+
+        Message::ComparisonBackendToggled(backend, enabled) => {
+            app.comparison_backends = helpers::toggle_comparison_backend(
+                &app.available_backends,
+                &app.comparison_backends,
+                &backend,
+                enabled,
+            );
+            app.status = format!("Comparing {} backend(s)", app.comparison_backends.len());
+        }
+
+        Message::RunComparison => return run_comparison(app),
+
+        Message::ComparisonFinished(outcome, elapsed_ms) => {
+            app.comparison_running = false;
+            match outcome {
+                Ok(rows) => {
+                    let failed = rows
+                        .iter()
+                        .flat_map(|row| &row.cells)
+                        .filter(|cell| cell.outcome.is_err())
+                        .count();
+                    app.status = format!(
+                        "Compared {} font(s) across {} backend(s) in {} ms{}",
+                        rows.len(),
+                        rows.first().map_or(0, |row| row.cells.len()),
+                        elapsed_ms,
+                        if failed > 0 {
+                            format!(", {} render(s) failed", failed)
+                        } else {
+                            String::new()
+                        }
+                    );
+                    app.comparison_rows = rows;
+                }
+                Err(e) => app.status = format!("Comparison failed: {}", e),
+            }
+        }
+
+        Message::ExportComparison => {
+            if app.comparison_rows.is_empty() {
+                app.status = "Compare backends before exporting the matrix".to_string();
+                return Command::none();
+            }
+
+            app.status = "Choose where to save the comparison matrix...".to_string();
+            let file_name = format!("backend_matrix.{}", app.export_format.extension());
+            return Command::perform(
+                async move { rfd::FileDialog::new().set_file_name(file_name).save_file() },
+                Message::ComparisonDestinationChosen,
+            );
+        }
+
+        Message::ComparisonDestinationChosen(destination) => match destination {
+            Some(path) => match export_comparison(app, &path) {
+                Ok((width, height)) => {
+                    app.status = format!(
+                        "Exported {}x{} backend matrix to {}",
+                        width,
+                        height,
+                        helpers::export_target(&path, app.export_format).0.display()
+                    );
+                }
+                Err(e) => {
+                    app.status = format!("Matrix export failed: {}", e);
+                }
+            },
+            None => {
+                app.status = "Matrix export cancelled".to_string();
+            }
+        },
+
+        Message::BenchmarkWarmupChanged(value) => {
+            app.benchmark_warmup_input = value;
+        }
+
+        Message::BenchmarkIterationsChanged(value) => {
+            app.benchmark_iterations_input = value;
+        }
+
+        Message::RunBenchmark => return run_benchmark(app),
+
+        Message::BenchmarkFinished(outcome) => {
+            app.benchmark_running = false;
+            match outcome {
+                Ok(results) => {
+                    let failed = results.iter().filter(|r| r.error.is_some()).count();
+                    app.status = format!(
+                        "Benchmarked {} case(s){}",
+                        results.len(),
+                        if failed > 0 {
+                            format!(", {} failed", failed)
+                        } else {
+                            String::new()
+                        }
+                    );
+                    app.benchmark_results = results;
+                }
+                Err(e) => app.status = format!("Benchmark failed: {}", e),
+            }
+        }
+
+        Message::SaveBenchmark => {
+            if app.benchmark_results.is_empty() {
+                app.status = "Run a benchmark before saving results".to_string();
+                return Command::none();
+            }
+
+            app.status = "Choose a folder for benchmark.json and benchmark.csv...".to_string();
+            return Command::perform(
+                async { rfd::FileDialog::new().pick_folder() },
+                Message::BenchmarkDestinationChosen,
+            );
+        }
+
+        Message::BenchmarkDestinationChosen(destination) => match destination {
+            Some(folder) => match save_benchmark(&app.benchmark_results, &folder) {
+                Ok(()) => {
+                    app.status = format!(
+                        "Saved {} benchmark result(s) to {}",
+                        app.benchmark_results.len(),
+                        folder.display()
+                    );
+                }
+                Err(e) => {
+                    app.status = format!("Saving benchmark failed: {}", e);
+                }
+            },
+            None => {
+                app.status = "Benchmark save cancelled".to_string();
+            }
+        },
+
+        Message::ExportScaleToggled(scale, enabled) => {
+            app.export_scales.retain(|&s| s != scale);
+            if enabled {
+                app.export_scales.push(scale);
+                app.export_scales.sort_unstable();
+            }
+            app.status = if app.export_scales.is_empty() {
+                "Select at least one export scale".to_string()
+            } else {
+                format!(
+                    "Exports will be written at {}",
+                    app.export_scales
+                        .iter()
+                        .map(|s| format!("{s}×"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+        }
+
+        Message::ExportSidecarToggled(enabled) => {
+            app.export_sidecar = enabled;
+            app.status = if enabled {
+                "Exports will include JSON sidecars".to_string()
+            } else {
+                "Exports will embed settings in PNG metadata only".to_string()
+            };
+        }
+
+        Message::ExportTemplateChanged(template) => {
+            app.status = match testypf_core::export::validate_name_template(&template) {
+                Ok(()) => format!("Export file names: {}", template),
+                Err(e) => e.to_string(),
+            };
+            app.export_template = template;
+            app.save_config();
+        }
+
+        Message::ExportFormatChanged(format) => {
+            app.export_format = format;
+            app.status = if format.supports_alpha() {
+                format!("Exports will be written as {}", format)
+            } else {
+                format!(
+                    "Exports will be written as {} (flattened, no alpha)",
+                    format
+                )
+            };
+            app.save_config();
+        }
+
+        Message::ExportFlattenToggled(enabled) => {
+            app.export_flatten_enabled = enabled;
+            app.status = if enabled {
+                "Exports will be flattened onto the background color".to_string()
+            } else {
+                "Exports keep transparency where the format allows".to_string()
+            };
+        }
+
+        Message::ExportFlattenColorChanged(value) => {
+            app.export_flatten_input = value.clone();
+            app.status = match helpers::parse_rgba_hex(&value) {
+                Some(_) => "Export flatten color updated".to_string(),
+                None => "Flatten color must be #RRGGBB or #RRGGBBAA".to_string(),
+            };
+        }
+
+        Message::ReproduceFromImage => {
+            app.status = "Choose an exported PNG to reproduce...".to_string();
+            return Command::perform(
+                async {
+                    rfd::FileDialog::new()
+                        .add_filter("Exported renders", &["png", "json"])
+                        .pick_file()
+                },
+                Message::ReproduceImageChosen,
+            );
+        }
+
+        Message::ReproduceImageChosen(path) => match path {
+            Some(path) => return reproduce_from_image(app, &path),
+            None => {
+                app.status = "Reproduce cancelled".to_string();
+            }
+        },
+
         Message::WindowClosed(id) => {
             if Some(id) == app.render_window_id {
                 app.render_window_id = None;
@@ -386,6 +1021,193 @@ pub fn handle_message(app: &mut TestypfApp, message: Message) -> Command<Message
     Command::none()
 }
 
+/// Status text with a warning appended when the backend ignores some raster options.
+fn raster_status(app: &TestypfApp, message: String) -> String {
+    match helpers::raster_support_note(&app.render_settings.backend, &app.render_settings.raster) {
+        Some(note) => format!("{} ({})", message, note),
+        None => message,
+    }
+}
+
+/// Render every target font once per checked backend in a background command.
+fn run_comparison(app: &mut TestypfApp) -> Command<Message> {
+    if app.comparison_running {
+        return Command::none();
+    }
+    if app.comparison_backends.is_empty() {
+        app.status = "Select at least one backend to compare".to_string();
+        return Command::none();
+    }
+
+    let visible_indices = app.visible_font_indices();
+    let fonts: Vec<(usize, PathBuf)> = helpers::derive_render_targets(
+        app.selected_font,
+        &visible_indices,
+        app.render_selected_only,
+    )
+    .into_iter()
+    .filter_map(|i| app.fonts.get(i).map(|font| (i, font.path().clone())))
+    .collect();
+    if fonts.is_empty() {
+        app.status = "No fonts to compare".to_string();
+        return Command::none();
+    }
+
+    let backends = app.comparison_backends.clone();
+    let settings = app.render_settings.clone();
+    app.comparison_running = true;
+    app.status = format!(
+        "Comparing {} font(s) across {} backend(s)...",
+        fonts.len(),
+        backends.len()
+    );
+    Command::perform(
+        async move {
+            let started = Instant::now();
+            let rows = TestypfEngine::detached_text_renderer()
+                .map(|mut renderer| {
+                    helpers::render_comparison(&mut *renderer, &fonts, &backends, &settings)
+                })
+                .map_err(|e| e.to_string());
+            (rows, started.elapsed().as_millis())
+        },
+        |(rows, elapsed_ms)| Message::ComparisonFinished(rows, elapsed_ms),
+    )
+}
+
+/// Report the requested coverage analysis once no font is still being analyzed.
+fn report_coverage_if_done(app: &mut TestypfApp) {
+    if !app.coverage_report_requested || !app.coverage_pending.is_empty() {
+        return;
+    }
+    app.coverage_report_requested = false;
+    let failed = app
+        .fonts
+        .iter()
+        .filter(|font| matches!(app.coverage.get(font.path()), Some(Err(_))))
+        .count();
+    app.status = if failed == 0 {
+        format!("Analyzed character coverage of {} fonts", app.fonts.len())
+    } else {
+        format!(
+            "Analyzed character coverage of {} fonts ({} failed)",
+            app.fonts.len(),
+            failed
+        )
+    };
+}
+
+/// Save the comparison matrix as one image in the chosen export format.
+fn export_comparison(app: &TestypfApp, path: &std::path::Path) -> Result<(u32, u32), String> {
+    let flatten = if app.export_flatten_enabled {
+        let (r, g, b, _) = helpers::parse_rgba_hex(&app.export_flatten_input)
+            .ok_or_else(|| "Flatten color must be #RRGGBB or #RRGGBBAA".to_string())?;
+        Some((r, g, b))
+    } else {
+        None
+    };
+
+    let matrix = helpers::compose_comparison_matrix(
+        &app.comparison_rows,
+        &app.fonts,
+        &ContactSheetOptions {
+            background: flatten
+                .map(|(r, g, b)| (r, g, b, 255))
+                .unwrap_or(ContactSheetOptions::default().background),
+            ..ContactSheetOptions::default()
+        },
+    )?;
+
+    let (path, format) = helpers::export_target(path, app.export_format);
+    testypf_core::export::write_image(
+        &path,
+        format,
+        matrix.width,
+        matrix.height,
+        &matrix.data,
+        flatten,
+        None,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok((matrix.width, matrix.height))
+}
+
+/// Benchmark every target font on every available raster backend in a background command.
+fn run_benchmark(app: &mut TestypfApp) -> Command<Message> {
+    if app.benchmark_running {
+        return Command::none();
+    }
+
+    let config = match helpers::parse_benchmark_config(
+        &app.benchmark_warmup_input,
+        &app.benchmark_iterations_input,
+    ) {
+        Ok(config) => config,
+        Err(e) => {
+            app.status = e;
+            return Command::none();
+        }
+    };
+
+    let visible_indices = app.visible_font_indices();
+    let target_indices = helpers::derive_render_targets(
+        app.selected_font,
+        &visible_indices,
+        app.render_selected_only,
+    );
+    let raster_backends: Vec<_> = app
+        .available_backends
+        .iter()
+        .filter(|backend| backend.is_raster())
+        .cloned()
+        .collect();
+    let cases = helpers::benchmark_cases(
+        &app.fonts,
+        &target_indices,
+        &raster_backends,
+        &app.render_settings,
+    );
+    if cases.is_empty() {
+        app.status = "No fonts to benchmark".to_string();
+        return Command::none();
+    }
+
+    app.benchmark_running = true;
+    app.status = format!(
+        "Benchmarking {} case(s) ({} warm-up + {} timed renders each)...",
+        cases.len(),
+        config.warmup,
+        config.iterations
+    );
+    Command::perform(
+        async move {
+            TestypfEngine::detached_text_renderer()
+                .map(|mut renderer| bench::run_benchmark(&mut *renderer, &cases, config, |_, _| {}))
+                .map_err(|e| e.to_string())
+        },
+        Message::BenchmarkFinished,
+    )
+}
+
+/// Write benchmark results as JSON and CSV into `folder`.
+fn save_benchmark(results: &[BenchmarkStats], folder: &std::path::Path) -> Result<(), String> {
+    let json = bench::to_json(results).map_err(|e| e.to_string())?;
+    std::fs::write(folder.join("benchmark.json"), json).map_err(|e| e.to_string())?;
+    std::fs::write(folder.join("benchmark.csv"), bench::to_csv(results))
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Write the coverage matrix as JSON and long-form CSV into `folder`.
+fn save_coverage_matrix(matrix: &CoverageMatrix, folder: &std::path::Path) -> Result<(), String> {
+    let json = matrix.to_json().map_err(|e| e.to_string())?;
+    std::fs::write(folder.join("coverage-matrix.json"), json).map_err(|e| e.to_string())?;
+    std::fs::write(folder.join("coverage-matrix.csv"), matrix.to_csv())
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 /// Handle the RenderPreviews message.
 fn handle_render_previews(app: &mut TestypfApp) -> Command<Message> {
     if app.fonts.is_empty() {
@@ -436,7 +1258,14 @@ fn handle_render_previews(app: &mut TestypfApp) -> Command<Message> {
     );
 
     // Clear previous results
-    app.render_previews.clear();
+    app.clear_render_previews();
+    app.refresh_decoded_sample();
+
+    // Check the sample text against each font's cmap first
+    let mut missing: HashMap<usize, Vec<char>> = target_indices
+        .iter()
+        .map(|&i| (i, app.missing_characters(i)))
+        .collect();
 
     // Render each selected font
     let render_start = Instant::now();
@@ -451,8 +1280,16 @@ fn handle_render_previews(app: &mut TestypfApp) -> Command<Message> {
             {
                 Ok(render_result) => {
                     let duration_ms = per_start.elapsed().as_millis();
-                    match helpers::build_render_preview(font_index, render_result, duration_ms) {
-                        Ok(preview) => previews.push(preview),
+                    match helpers::build_render_preview(
+                        font_index,
+                        render_result,
+                        duration_ms,
+                        app.render_settings.scale_factor,
+                    ) {
+                        Ok(mut preview) => {
+                            preview.missing_chars = missing.remove(&font_index).unwrap_or_default();
+                            previews.push(preview);
+                        }
                         Err(e) => {
                             app.status = format!(
                                 "Failed to create preview for font {}: {}",
@@ -471,12 +1308,24 @@ fn handle_render_previews(app: &mut TestypfApp) -> Command<Message> {
     }
 
     app.render_previews = previews;
+    app.refresh_preview_overlays();
     app.last_render_settings = Some(app.render_settings.clone());
     app.last_render_font_paths = font_paths;
+    let incomplete = app
+        .render_previews
+        .iter()
+        .filter(|p| !p.missing_chars.is_empty())
+        .count();
     app.status = format!(
-        "Rendering complete - {} preview(s) generated in {} ms",
+        "Rendering complete - {} preview(s) generated in {} ms ({}){}",
         app.render_previews.len(),
-        render_start.elapsed().as_millis()
+        render_start.elapsed().as_millis(),
+        helpers::preview_memory_summary(&app.render_previews),
+        if incomplete == 0 {
+            String::new()
+        } else {
+            format!(" - {} font(s) missing sample characters", incomplete)
+        }
     );
 
     let mut cmds = Vec::new();
@@ -583,7 +1432,7 @@ fn process_dropped_paths(app: &mut TestypfApp, paths: Vec<PathBuf>) {
 
     if added_count > 0 {
         app.invalidate_render_cache();
-        app.render_previews.clear();
+        app.clear_render_previews();
     }
 
     if !invalid_paths.is_empty() {
@@ -636,30 +1485,227 @@ fn process_dropped_paths(app: &mut TestypfApp, paths: Vec<PathBuf>) {
     }
 }
 
-/// Export all current render previews into the given folder.
-fn export_previews_to_folder(app: &TestypfApp, folder: &std::path::Path) -> Result<usize, String> {
+/// Export all current render previews into the given folder, once per selected scale.
+///
+/// Variants at the preview's own scale reuse its pixels; other scales are re-rendered with
+/// the backend recorded in the provenance, restoring the renderer's backend afterwards.
+fn export_previews_to_folder(
+    app: &mut TestypfApp,
+    folder: &std::path::Path,
+) -> Result<usize, String> {
+    testypf_core::export::validate_name_template(&app.export_template)
+        .map_err(|e| e.to_string())?;
+    if app.export_scales.is_empty() {
+        return Err("Select at least one export scale".to_string());
+    }
+    let flatten = if app.export_flatten_enabled {
+        let (r, g, b, _) = helpers::parse_rgba_hex(&app.export_flatten_input)
+            .ok_or_else(|| "Flatten color must be #RRGGBB or #RRGGBBAA".to_string())?;
+        Some((r, g, b))
+    } else {
+        None
+    };
+
     std::fs::create_dir_all(folder)
         .map_err(|e| format!("Failed to create export folder {:?}: {}", folder, e))?;
 
+    // Previews were produced with the settings of the last render, not the live controls
+    let settings = app
+        .last_render_settings
+        .clone()
+        .unwrap_or_else(|| app.render_settings.clone());
+    let ext = app.export_format.extension();
     let mut written = 0usize;
 
     for (i, preview) in app.render_previews.iter().enumerate() {
-        let stem = app
-            .fonts
-            .get(preview.font_index)
-            .map(|f| helpers::sanitized_file_stem(&f.full_name))
-            .unwrap_or_else(|| format!("font{}", i + 1));
+        let font = app.fonts.get(preview.font_index);
+        let stem = match font {
+            Some(f) => {
+                let fields = NameFields::new(f, &settings, i + 1);
+                render_name_template(&app.export_template, &fields).map_err(|e| e.to_string())?
+            }
+            None => format!("{:02}_font{}", i + 1, i + 1),
+        };
 
-        let mut candidate = folder.join(format!("{:02}_{}.png", i + 1, stem));
-        let mut suffix = 1;
-        while candidate.exists() {
-            candidate = folder.join(format!("{:02}_{}_{suffix}.png", i + 1, stem));
-            suffix += 1;
+        for &scale in &app.export_scales {
+            let scaled_settings = settings.at_scale(scale as f32);
+            let rerendered;
+            let variant = if preview.scale_factor == scale as f32 {
+                preview
+            } else {
+                let font =
+                    font.ok_or_else(|| format!("Cannot re-render preview {} at {scale}×", i + 1))?;
+                // Re-render with the backend the preview was made with, then put the live one back
+                let renderer = app.engine.text_renderer();
+                let previous = renderer.backend();
+                let result = renderer
+                    .set_backend(scaled_settings.backend.clone())
+                    .and_then(|_| renderer.render_text(font.path(), &scaled_settings));
+                let restored = renderer.set_backend(previous);
+                let result = result
+                    .and_then(|result| restored.map(|_| result))
+                    .map_err(|e| e.to_string())?;
+                rerendered =
+                    helpers::build_render_preview(preview.font_index, result, 0, scale as f32)?;
+                &rerendered
+            };
+            let provenance = font.map(|f| {
+                RenderProvenance::capture(f.path(), &scaled_settings, app.engine.shaper())
+            });
+
+            let stem = format!("{stem}{}", helpers::export_scale_suffix(scale));
+            let candidate = helpers::unused_export_path(folder, &stem, ext);
+
+            helpers::export_preview_as(
+                variant,
+                &candidate,
+                app.export_format,
+                flatten,
+                provenance.as_ref(),
+            )?;
+            if variant.svg.is_some() {
+                helpers::export_preview_svg_to_path(variant, &candidate.with_extension("svg"))?;
+            }
+            if app.export_sidecar {
+                if let Some(provenance) = &provenance {
+                    testypf_core::export::write_sidecar(&candidate, provenance)
+                        .map_err(|e| e.to_string())?;
+                }
+            }
+            written += 1;
         }
-
-        helpers::export_preview_to_path(preview, &candidate)?;
-        written += 1;
     }
 
     Ok(written)
 }
+
+/// Tile all current previews into one image laid out like the on-screen grid.
+fn export_contact_sheet(app: &TestypfApp, path: &std::path::Path) -> Result<(u32, u32), String> {
+    let settings = app
+        .last_render_settings
+        .as_ref()
+        .unwrap_or(&app.render_settings);
+    let flatten = if app.export_flatten_enabled {
+        let (r, g, b, _) = helpers::parse_rgba_hex(&app.export_flatten_input)
+            .ok_or_else(|| "Flatten color must be #RRGGBB or #RRGGBBAA".to_string())?;
+        Some((r, g, b))
+    } else {
+        None
+    };
+
+    let sheet = helpers::compose_contact_sheet(
+        &app.render_previews,
+        &app.fonts,
+        settings,
+        &ContactSheetOptions {
+            columns: app.layout_mode.columns(),
+            background: flatten
+                .map(|(r, g, b)| (r, g, b, 255))
+                .unwrap_or(ContactSheetOptions::default().background),
+            ..ContactSheetOptions::default()
+        },
+    )?;
+
+    let (path, format) = helpers::export_target(path, app.export_format);
+    testypf_core::export::write_image(
+        &path,
+        format,
+        sheet.width,
+        sheet.height,
+        &sheet.data,
+        flatten,
+        None,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok((sheet.width, sheet.height))
+}
+
+/// Restore render settings from an exported PNG (or its sidecar) and re-render that font.
+fn reproduce_from_image(app: &mut TestypfApp, path: &std::path::Path) -> Command<Message> {
+    let provenance = match testypf_core::export::read_provenance(path) {
+        Ok(provenance) => provenance,
+        Err(e) => {
+            app.status = format!("Cannot reproduce render: {}", e);
+            return Command::none();
+        }
+    };
+
+    let font_index = match app
+        .fonts
+        .iter()
+        .position(|f| f.path() == &provenance.font_path)
+    {
+        Some(index) => index,
+        None => {
+            let source = FontliftFontSource::new(provenance.font_path.clone());
+            match app.engine.font_manager().add_font(&source) {
+                Ok(font_info) => {
+                    app.fonts.push(font_info);
+                    app.fonts.len() - 1
+                }
+                Err(e) => {
+                    app.status = format!(
+                        "Cannot reproduce render: font {:?} unavailable ({})",
+                        provenance.font_path, e
+                    );
+                    return Command::none();
+                }
+            }
+        }
+    };
+
+    let settings = provenance.settings.clone();
+    if !app.available_backends.contains(&settings.backend) {
+        app.status = format!(
+            "Cannot reproduce render: backend {} is not available in this build",
+            settings.backend
+        );
+        return Command::none();
+    }
+
+    let _ = app
+        .engine
+        .text_renderer()
+        .set_backend(settings.backend.clone());
+    app.foreground_input = helpers::format_rgba_hex(settings.foreground_color);
+    app.background_enabled = settings.background_color.is_some();
+    if let Some(color) = settings.background_color {
+        app.background_input = helpers::format_rgba_hex(color);
+    }
+    app.gamma_input = settings.raster.gamma.to_string();
+    let exported_scale = settings.scale_factor;
+    let (settings, scale_mode) = helpers::reproduced_settings(settings, app.overlay_options.any());
+    app.render_settings = settings;
+    app.scale_mode = scale_mode;
+    app.selected_font = Some(font_index);
+    app.refresh_font_tables();
+    app.refresh_decoded_sample();
+    app.invalidate_render_cache();
+
+    // Render just the reproduced font, without changing the user's render scope
+    let render_selected_only = std::mem::replace(&mut app.render_selected_only, true);
+    let command = handle_render_previews(app);
+    app.render_selected_only = render_selected_only;
+
+    let hash_note = match provenance.font_matches() {
+        Some(true) => "font hash verified",
+        Some(false) => "WARNING: font file changed since export",
+        None => "font hash not checked",
+    };
+    app.status = format!(
+        "{} | Reproduced from {} (testypf {}, {})",
+        app.status,
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        provenance.testypf_version,
+        hash_note
+    );
+    if app.render_settings.scale_factor != exported_scale {
+        app.status.push_str(&format!(
+            " | exported at {}×, rendered at the nearest fixed scale {}",
+            exported_scale, app.scale_mode
+        ));
+    }
+
+    command
+}
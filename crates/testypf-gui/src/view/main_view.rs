@@ -3,14 +3,22 @@
 use crate::app::TestypfApp;
 use crate::helpers;
 use crate::message::Message;
-use crate::styles::DragActiveStyle;
-use crate::types::{InstallScope, LayoutMode, RenderAvailability};
+use crate::styles::{BarStyle, CoverageCellStyle, DragActiveStyle};
+use crate::types::{
+    InstallScope, LayoutMode, MatrixRows, RenderAvailability, ScaleMode, EXPORT_SCALES,
+};
 
 use iced::widget::{
     button, checkbox, column, container, image as iced_image, pick_list, row, scrollable, slider,
     text, text_input,
 };
 use iced::{Element, Length};
+use testypf_core::escapes;
+use testypf_core::export::{ExportFormat, DEFAULT_NAME_TEMPLATE, NAME_PLACEHOLDERS};
+use testypf_core::normalization::Normalization;
+use testypf_core::overlay::{GuideLine, OverlayLayer};
+use testypf_core::proofs::ProofSection;
+use testypf_core::{AntialiasMode, HintingMode};
 
 /// Render the main window view.
 pub fn render(app: &TestypfApp) -> Element<'_, Message> {
@@ -20,7 +28,15 @@ pub fn render(app: &TestypfApp) -> Element<'_, Message> {
             0.2, 0.2, 0.8,
         )));
 
-    let status = text(&app.status).size(14);
+    let status = column![
+        text(&app.status).size(14),
+        text(helpers::preview_memory_summary(&app.render_previews))
+            .size(10)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.45, 0.45, 0.45
+            ))),
+    ]
+    .spacing(2);
     let visible_indices = app.visible_font_indices();
     let render_state = RenderAvailability::derive(
         app.fonts.len(),
@@ -57,9 +73,12 @@ pub fn render(app: &TestypfApp) -> Element<'_, Message> {
     // Font list section
     let font_list_header = text("Font List").size(18);
 
-    let font_filter = text_input("Filter fonts (name, family, style, path)", &app.font_filter)
-        .on_input(Message::FontFilterChanged)
-        .size(12);
+    let font_filter = text_input(
+        "Filter fonts (name, family, style, path, supported language)",
+        &app.font_filter,
+    )
+    .on_input(Message::FontFilterChanged)
+    .size(12);
     let filter_hint = text(format!(
         "Showing {} of {}",
         visible_indices.len(),
@@ -103,6 +122,8 @@ pub fn render(app: &TestypfApp) -> Element<'_, Message> {
 
     let metadata_panel: Element<Message> = metadata_panel_view(app);
 
+    let coverage_panel = coverage_view(app);
+
     let drop_area = drop_area_view(app);
 
     // Render controls section
@@ -111,6 +132,10 @@ pub fn render(app: &TestypfApp) -> Element<'_, Message> {
     // Preview section
     let preview_area: Element<Message> = preview_area_view(app, &visible_indices, render_state);
 
+    let comparison = comparison_view(app, render_state);
+
+    let benchmark_panel = benchmark_panel_view(app, render_state);
+
     // Layout everything
     let content = column![
         title,
@@ -125,10 +150,13 @@ pub fn render(app: &TestypfApp) -> Element<'_, Message> {
         font_list,
         font_ops_notice.unwrap_or_else(|| text("").into()),
         metadata_panel,
+        coverage_panel,
         drop_area,
         // Render controls and preview
         render_controls,
         preview_area,
+        comparison,
+        benchmark_panel,
     ]
     .spacing(20)
     .padding(20)
@@ -141,6 +169,202 @@ pub fn render(app: &TestypfApp) -> Element<'_, Message> {
         .into()
 }
 
+/// Width of the font-name column in the backend comparison grid.
+const COMPARISON_LABEL_WIDTH: f32 = 160.0;
+
+/// Build the backend comparison matrix: fonts as rows, compared backends as columns.
+fn comparison_view(app: &TestypfApp, render_state: RenderAvailability) -> Element<'_, Message> {
+    let header = text("Backend Comparison").size(18);
+
+    let backend_toggles = row(app
+        .available_backends
+        .iter()
+        .map(|backend| {
+            let toggled = backend.clone();
+            checkbox(
+                backend.to_string(),
+                app.comparison_backends.contains(backend),
+            )
+            .on_toggle(move |enabled| Message::ComparisonBackendToggled(toggled.clone(), enabled))
+            .size(14)
+            .text_size(12)
+            .into()
+        })
+        .collect::<Vec<Element<Message>>>())
+    .spacing(12);
+
+    let controls = row![
+        button("Compare Backends").on_press_maybe(
+            (render_state.can_render()
+                && !app.comparison_backends.is_empty()
+                && !app.comparison_running)
+                .then_some(Message::RunComparison)
+        ),
+        button("Export Matrix")
+            .on_press_maybe((!app.comparison_rows.is_empty()).then_some(Message::ExportComparison)),
+    ]
+    .spacing(10);
+
+    let hint = text("Renders each target font once per checked backend with the current settings.")
+        .size(10)
+        .style(iced::theme::Text::Color(iced::Color::from_rgb(
+            0.45, 0.45, 0.45,
+        )));
+
+    let grid: Element<Message> = match app.comparison_rows.first() {
+        None => text("").into(),
+        Some(first) => {
+            let mut header_row =
+                row![text("").width(Length::Fixed(COMPARISON_LABEL_WIDTH))].spacing(10);
+            for cell in &first.cells {
+                header_row = header_row.push(
+                    text(cell.backend.to_string())
+                        .size(14)
+                        .width(Length::FillPortion(1)),
+                );
+            }
+
+            let mut rows = column![header_row].spacing(10);
+            for comparison_row in &app.comparison_rows {
+                let font_name = app
+                    .fonts
+                    .get(comparison_row.font_index)
+                    .map(|font| font.full_name.as_str())
+                    .unwrap_or("Unknown font");
+                let mut grid_row = row![text(font_name)
+                    .size(12)
+                    .width(Length::Fixed(COMPARISON_LABEL_WIDTH))]
+                .spacing(10);
+                for cell in &comparison_row.cells {
+                    grid_row = grid_row.push(comparison_cell_view(cell));
+                }
+                rows = rows.push(grid_row);
+            }
+            scrollable(rows).into()
+        }
+    };
+
+    column![header, backend_toggles, controls, hint, grid]
+        .spacing(10)
+        .into()
+}
+
+/// One cell of the comparison grid: the render at logical size plus its timing.
+fn comparison_cell_view(cell: &crate::types::ComparisonCell) -> Element<'_, Message> {
+    let body: Element<Message> = match &cell.outcome {
+        Ok(preview) => {
+            let (logical_width, logical_height) = helpers::preview_logical_size(preview);
+            iced_image::Image::new(preview.handle.clone())
+                .width(Length::Fixed(logical_width))
+                .height(Length::Fixed(logical_height))
+                .into()
+        }
+        Err(_) => text("No render").size(12).into(),
+    };
+
+    let color = if cell.outcome.is_ok() {
+        iced::Color::from_rgb(0.45, 0.45, 0.45)
+    } else {
+        iced::Color::from_rgb(0.7, 0.25, 0.25)
+    };
+
+    container(
+        column![
+            body,
+            text(helpers::comparison_cell_text(cell))
+                .size(10)
+                .style(iced::theme::Text::Color(color)),
+        ]
+        .spacing(4),
+    )
+    .padding(8)
+    .width(Length::FillPortion(1))
+    .style(iced::theme::Container::Box)
+    .into()
+}
+
+/// Width of the longest bar in the benchmark chart.
+const BENCHMARK_BAR_WIDTH: f32 = 320.0;
+
+/// Build the benchmark panel: run controls plus a per-font chart of backend medians.
+fn benchmark_panel_view(
+    app: &TestypfApp,
+    render_state: RenderAvailability,
+) -> Element<'_, Message> {
+    let header = text("Benchmark").size(18);
+
+    let controls = row![
+        text("Warm-up").size(12),
+        text_input("2", &app.benchmark_warmup_input)
+            .on_input(Message::BenchmarkWarmupChanged)
+            .width(Length::Fixed(60.0))
+            .size(12),
+        text("Iterations").size(12),
+        text_input("10", &app.benchmark_iterations_input)
+            .on_input(Message::BenchmarkIterationsChanged)
+            .width(Length::Fixed(60.0))
+            .size(12),
+        button("Run Benchmark").on_press_maybe(
+            (render_state.can_render() && !app.benchmark_running).then_some(Message::RunBenchmark)
+        ),
+        button("Save Results...")
+            .on_press_maybe((!app.benchmark_results.is_empty()).then_some(Message::SaveBenchmark)),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+
+    let hint =
+        text("Renders each target font on every available backend and charts the median time.")
+            .size(10)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.45, 0.45, 0.45,
+            )));
+
+    let slowest = app
+        .benchmark_results
+        .iter()
+        .filter(|r| r.error.is_none())
+        .map(|r| r.median_ms)
+        .fold(0.0_f64, f64::max);
+
+    let mut chart = column![].spacing(4);
+    let mut current_font: Option<&str> = None;
+    for stats in &app.benchmark_results {
+        if current_font != Some(stats.font.as_str()) {
+            current_font = Some(stats.font.as_str());
+            chart = chart.push(text(&stats.font).size(14));
+        }
+
+        let bar: Element<Message> = if stats.error.is_some() {
+            text("—").size(12).into()
+        } else {
+            container(text(""))
+                .width(Length::Fixed(helpers::benchmark_bar_width(
+                    stats.median_ms,
+                    slowest,
+                    BENCHMARK_BAR_WIDTH,
+                )))
+                .height(Length::Fixed(12.0))
+                .style(iced::theme::Container::Custom(Box::new(BarStyle(
+                    iced::Color::from_rgb(0.3, 0.5, 0.85),
+                ))))
+                .into()
+        };
+
+        chart = chart.push(
+            row![
+                text(&stats.backend).size(12).width(Length::Fixed(110.0)),
+                container(bar).width(Length::Fixed(BENCHMARK_BAR_WIDTH)),
+                text(helpers::benchmark_summary(stats)).size(11),
+            ]
+            .spacing(10)
+            .align_items(iced::Alignment::Center),
+        );
+    }
+
+    column![header, controls, hint, chart].spacing(10).into()
+}
+
 /// Build the font list view.
 fn font_list_view<'a>(app: &'a TestypfApp, visible_indices: &[usize]) -> Element<'a, Message> {
     if app.fonts.is_empty() {
@@ -229,13 +453,29 @@ fn metadata_panel_view(app: &TestypfApp) -> Element<'_, Message> {
             .map(|line| text(line).size(12).into())
             .collect::<Vec<_>>();
 
+        let languages = app
+            .font_languages
+            .get(selected.path())
+            .map(|languages| helpers::language_support_lines(languages))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|line| text(line).size(12).into())
+            .collect::<Vec<_>>();
+
         let variation_panel = variation_controls(app, selected);
+        let tables_toggle = checkbox("Show font tables", app.show_font_tables)
+            .on_toggle(Message::FontTablesToggled)
+            .size(14)
+            .text_size(12);
 
         container(
             column![
                 text("Font Metadata").size(16),
                 column(rows).spacing(4),
-                variation_panel
+                column(languages).spacing(4),
+                variation_panel,
+                tables_toggle,
+                font_tables_view(app, selected),
             ]
             .spacing(8),
         )
@@ -260,6 +500,292 @@ fn metadata_panel_view(app: &TestypfApp) -> Element<'_, Message> {
     }
 }
 
+/// Table directory and decoded table summaries for the selected font, when enabled.
+fn font_tables_view<'a>(
+    app: &'a TestypfApp,
+    font: &'a testypf_core::TestypfFontInfo,
+) -> Element<'a, Message> {
+    if !app.show_font_tables {
+        return column![].into();
+    }
+    let muted = iced::theme::Text::Color(iced::Color::from_rgb(0.45, 0.45, 0.45));
+    match &app.font_tables {
+        Some((path, Ok(tables))) if path == font.path() => column(
+            helpers::font_table_sections(tables)
+                .into_iter()
+                .map(|(title, lines)| {
+                    column![
+                        text(title).size(13),
+                        column(
+                            lines
+                                .into_iter()
+                                .map(|line| text(line).size(11).style(muted).into())
+                                .collect::<Vec<_>>()
+                        )
+                        .spacing(2),
+                    ]
+                    .spacing(4)
+                    .into()
+                })
+                .collect::<Vec<_>>(),
+        )
+        .spacing(8)
+        .into(),
+        Some((path, Err(e))) if path == font.path() => {
+            text(format!("Could not read font tables: {}", e))
+                .size(12)
+                .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                    0.8, 0.3, 0.3,
+                )))
+                .into()
+        }
+        _ => text("Font tables not loaded").size(12).style(muted).into(),
+    }
+}
+
+/// Height of the scrollable block and script lists in the coverage panel.
+const COVERAGE_LIST_HEIGHT: f32 = 220.0;
+
+/// Character coverage of the selected font plus a "which fonts cover this" lookup.
+fn coverage_view(app: &TestypfApp) -> Element<'_, Message> {
+    let muted = iced::theme::Text::Color(iced::Color::from_rgb(0.45, 0.45, 0.45));
+    let header = text("Character Coverage").size(18);
+    let controls = row![
+        button("Analyze Coverage")
+            .on_press_maybe((!app.fonts.is_empty()).then_some(Message::AnalyzeCoverage)),
+        text("Reads each font's cmap and summarizes it by Unicode block and script.")
+            .size(10)
+            .style(muted),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+
+    let summary_list = |title: &str, lines: Vec<String>| -> Element<'_, Message> {
+        column![
+            text(title.to_string()).size(13),
+            column(
+                lines
+                    .into_iter()
+                    .map(|line| text(line).size(11).into())
+                    .collect::<Vec<_>>()
+            )
+            .spacing(2),
+        ]
+        .spacing(4)
+        .width(Length::FillPortion(1))
+        .into()
+    };
+
+    let selected = app.selected_font.and_then(|i| app.fonts.get(i));
+    let details: Element<Message> = match selected.map(|font| (font, app.coverage.get(font.path())))
+    {
+        None => text("Select a font to see its coverage.")
+            .size(12)
+            .style(muted)
+            .into(),
+        Some((_, None)) => text("Coverage not analyzed yet.")
+            .size(12)
+            .style(muted)
+            .into(),
+        Some((_, Some(Err(e)))) => text(format!("Could not read cmap: {}", e))
+            .size(12)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.8, 0.3, 0.3,
+            )))
+            .into(),
+        Some((font, Some(Ok(coverage)))) => column![
+            text(format!(
+                "{}: {} codepoints mapped",
+                font.full_name,
+                coverage.len()
+            ))
+            .size(12),
+            scrollable(
+                row![
+                    summary_list(
+                        "Blocks",
+                        coverage
+                            .blocks()
+                            .iter()
+                            .map(helpers::coverage_summary_line)
+                            .collect()
+                    ),
+                    summary_list(
+                        "Scripts",
+                        coverage
+                            .scripts()
+                            .iter()
+                            .map(helpers::coverage_summary_line)
+                            .collect()
+                    ),
+                ]
+                .spacing(20)
+            )
+            .height(Length::Fixed(COVERAGE_LIST_HEIGHT)),
+        ]
+        .spacing(6)
+        .into(),
+    };
+
+    let lookup_input = text_input(
+        "Which loaded fonts cover… (character or U+XXXX)",
+        &app.coverage_query,
+    )
+    .on_input(Message::CoverageQueryChanged)
+    .size(12);
+    let lookup_result: Element<Message> = if app.coverage_query.trim().is_empty() {
+        text("").into()
+    } else {
+        match helpers::parse_coverage_query(&app.coverage_query) {
+            None => text("Enter one character or a codepoint such as U+0416")
+                .size(11)
+                .style(muted)
+                .into(),
+            Some(ch) => {
+                let covering = helpers::fonts_covering(&app.fonts, &app.coverage, ch);
+                let mut lines = vec![text(format!(
+                    "U+{:04X} {} is covered by {} of {} fonts",
+                    u32::from(ch),
+                    ch,
+                    covering.len(),
+                    app.fonts.len()
+                ))
+                .size(12)
+                .into()];
+                lines.extend(covering.into_iter().map(|i| {
+                    text(format!("• {}", app.fonts[i].full_name))
+                        .size(11)
+                        .into()
+                }));
+                column(lines).spacing(2).into()
+            }
+        }
+    };
+
+    container(
+        column![
+            header,
+            controls,
+            details,
+            lookup_input,
+            lookup_result,
+            coverage_matrix_view(app),
+        ]
+        .spacing(8),
+    )
+    .padding(12)
+    .width(Length::Fill)
+    .style(iced::theme::Container::Box)
+    .into()
+}
+
+/// Width of the row-label column in the coverage matrix.
+const MATRIX_LABEL_WIDTH: f32 = 240.0;
+
+/// Height of the scrollable coverage matrix grid.
+const MATRIX_HEIGHT: f32 = 280.0;
+
+/// Rows (blocks or charset sections) by fonts, cells colored by coverage; click a cell for its missing codepoints.
+fn coverage_matrix_view(app: &TestypfApp) -> Element<'_, Message> {
+    let muted = iced::theme::Text::Color(iced::Color::from_rgb(0.45, 0.45, 0.45));
+    let mut controls = row![
+        text("Coverage matrix rows").size(12),
+        pick_list(
+            MatrixRows::options(),
+            Some(app.matrix_rows),
+            Message::MatrixRowsChanged
+        ),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+    if app.matrix_rows == MatrixRows::Custom {
+        controls = controls.push(
+            text_input(
+                "Characters or U+0100-017F ranges",
+                &app.matrix_custom_charset,
+            )
+            .on_input(Message::MatrixCustomCharsetChanged)
+            .size(12)
+            .width(Length::Fixed(260.0)),
+        );
+    }
+    controls = controls
+        .push(
+            button("Build Matrix")
+                .on_press_maybe((!app.fonts.is_empty()).then_some(Message::BuildCoverageMatrix)),
+        )
+        .push(button("Export Matrix").on_press_maybe(
+            matches!(app.coverage_matrix, Some(Ok(_))).then_some(Message::ExportCoverageMatrix),
+        ));
+
+    let body: Element<Message> = match &app.coverage_matrix {
+        None => text("Build the matrix to compare every loaded font.")
+            .size(10)
+            .style(muted)
+            .into(),
+        Some(Err(e)) => text(e)
+            .size(12)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.8, 0.3, 0.3,
+            )))
+            .into(),
+        Some(Ok(matrix)) => {
+            let mut header = row![text(&matrix.source)
+                .size(12)
+                .width(Length::Fixed(MATRIX_LABEL_WIDTH))]
+            .spacing(4);
+            for font in &matrix.fonts {
+                header = header.push(text(font).size(11).width(Length::FillPortion(1)));
+            }
+            let mut grid = column![].spacing(2);
+            for (row_index, matrix_row) in matrix.rows.iter().enumerate() {
+                let mut cells = row![text(&matrix_row.label)
+                    .size(11)
+                    .width(Length::Fixed(MATRIX_LABEL_WIDTH))]
+                .spacing(4);
+                for (column, cell) in matrix_row.cells.iter().enumerate() {
+                    cells = cells.push(
+                        button(text(format!("{}/{}", cell.covered, cell.total)).size(11))
+                            .on_press(Message::MatrixCellSelected(row_index, column))
+                            .style(iced::theme::Button::Custom(Box::new(CoverageCellStyle(
+                                helpers::coverage_cell_color(cell.percent()),
+                            ))))
+                            .padding([2, 6])
+                            .width(Length::FillPortion(1)),
+                    );
+                }
+                grid = grid.push(cells);
+            }
+            let drill: Element<Message> = match app.matrix_drill.and_then(|(r, c)| {
+                let matrix_row = matrix.rows.get(r)?;
+                Some((matrix_row, matrix.fonts.get(c)?, matrix_row.cells.get(c)?))
+            }) {
+                Some((matrix_row, font, cell)) => column(
+                    helpers::matrix_drill_lines(font, &matrix_row.label, cell)
+                        .into_iter()
+                        .map(|line| text(line).size(11).into())
+                        .collect::<Vec<_>>(),
+                )
+                .spacing(2)
+                .into(),
+                None => text("Click a cell to list its missing codepoints.")
+                    .size(10)
+                    .style(muted)
+                    .into(),
+            };
+            column![
+                header,
+                scrollable(grid).height(Length::Fixed(MATRIX_HEIGHT)),
+                drill
+            ]
+            .spacing(6)
+            .into()
+        }
+    };
+
+    column![controls, body].spacing(8).into()
+}
+
 /// Variable font axis controls for the selected font.
 fn variation_controls<'a>(
     app: &'a TestypfApp,
@@ -477,14 +1003,123 @@ fn render_controls_view(
 ) -> Element<'_, Message> {
     let render_header = text("Render Controls").size(18);
 
-    let sample_text_input = text_input("Enter sample text...", &app.render_settings.sample_text)
-        .on_input(Message::SampleTextChanged)
-        .size(14);
+    let sample_text_input = text_input(
+        "Enter sample text... (\\u{200D}, \\u0301 and /glyphname escapes)",
+        &app.render_settings.sample_text,
+    )
+    .on_input(Message::SampleTextChanged)
+    .size(14);
+    let sample_choices = helpers::sample_choices(
+        &app.sample_library,
+        app.selected_font_coverage(),
+        app.sample_suggestions_only,
+    );
+    let current_sample = sample_choices
+        .iter()
+        .find(|sample| sample.text == app.render_settings.sample_text)
+        .cloned();
+    let sample_row = row![
+        sample_text_input,
+        pick_list(sample_choices, current_sample, Message::SamplePicked)
+            .placeholder("Sample library")
+            .text_size(12)
+            .width(Length::Fixed(260.0)),
+    ]
+    .spacing(8)
+    .align_items(iced::Alignment::Center);
+    let user_sample =
+        helpers::current_user_sample(&app.sample_library, &app.render_settings.sample_text);
+    let mut remove_sample_btn = button(text("Remove").size(12));
+    if user_sample.is_some() {
+        remove_sample_btn = remove_sample_btn.on_press(Message::RemoveSampleEntry);
+    }
+    let suggestions_label = if app.selected_font_coverage().is_some() {
+        "Only samples the selected font renders"
+    } else {
+        "Only samples the selected font renders (select a font)"
+    };
+    let decoded_lines =
+        helpers::decoded_sample_lines(&app.render_settings.sample_text, &app.decoded_sample);
+    let decoded_sample =
+        decoded_lines
+            .into_iter()
+            .enumerate()
+            .fold(column![].spacing(2), |col, (index, line)| {
+                let color = if index == 0 {
+                    iced::Color::from_rgb(0.3, 0.3, 0.3)
+                } else {
+                    iced::Color::from_rgb(0.8, 0.2, 0.2)
+                };
+                col.push(text(line).size(11).style(iced::theme::Text::Color(color)))
+            });
+    let sample_library_row = row![
+        text_input("Name for current text", &app.sample_entry_name)
+            .on_input(Message::SampleEntryNameChanged)
+            .on_submit(Message::SaveSampleEntry)
+            .size(12)
+            .width(Length::Fixed(180.0)),
+        button(text("Save to Library").size(12)).on_press(Message::SaveSampleEntry),
+        remove_sample_btn,
+        checkbox(suggestions_label, app.sample_suggestions_only)
+            .on_toggle(Message::SampleSuggestionsToggled)
+            .size(14)
+            .text_size(12),
+    ]
+    .spacing(8)
+    .align_items(iced::Alignment::Center);
+    let spacing_proof_row = row![
+        text("Spacing proof").size(12),
+        pick_list(
+            &ProofSection::ALL[..],
+            Some(app.proof_section),
+            Message::ProofSectionChanged
+        )
+        .text_size(12),
+        button(text("Load Spacing Proof").size(12))
+            .on_press_maybe(app.selected_font.map(|_| Message::LoadSpacingProof))
+            .style(iced::theme::Button::Secondary),
+        text("Controls for every letter, or GPOS kern pairs largest first")
+            .size(10)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.45, 0.45, 0.45
+            ))),
+    ]
+    .spacing(8)
+    .align_items(iced::Alignment::Center);
 
     let font_size_input = text_input("Font size", &app.render_settings.font_size.to_string())
         .on_input(Message::FontSizeChanged)
         .size(14);
 
+    let scale_selector = pick_list(
+        &ScaleMode::OPTIONS[..],
+        Some(app.scale_mode),
+        Message::ScaleModeChanged,
+    );
+    let scale_row = row![
+        text("Render scale").size(12),
+        scale_selector,
+        button("Detect")
+            .on_press(Message::DetectScaleFactor)
+            .style(iced::theme::Button::Secondary),
+        text(match app.detected_scale_factor {
+            Some(scale) => format!(
+                "Window: {}× · rendering at {}×",
+                scale, app.render_settings.scale_factor
+            ),
+            None => format!(
+                "Window scale unknown · rendering at {}×",
+                app.render_settings.scale_factor
+            ),
+        })
+        .size(10)
+        .style(iced::theme::Text::Color(iced::Color::from_rgb(
+            0.45, 0.45, 0.45
+        ))),
+    ]
+    .spacing(8)
+    .align_items(iced::Alignment::Center);
+
     // Backend selector
     let backend_options = app.get_available_backends();
     let backend_descriptions = backend_options
@@ -509,6 +1144,36 @@ fn render_controls_view(
             0.35, 0.35, 0.35,
         )));
 
+    let raster = app.render_settings.raster;
+    let raster_row = row![
+        text("Antialias").size(12),
+        pick_list(
+            &AntialiasMode::ALL[..],
+            Some(raster.antialias),
+            Message::AntialiasChanged
+        ),
+        pick_list(
+            &HintingMode::ALL[..],
+            Some(raster.hinting),
+            Message::HintingChanged
+        ),
+        text("Gamma").size(12),
+        text_input("1.0", &app.gamma_input)
+            .on_input(Message::GammaChanged)
+            .size(14)
+            .width(Length::Fixed(70.0)),
+    ]
+    .spacing(8)
+    .align_items(iced::Alignment::Center);
+    let raster_note = text(
+        helpers::raster_support_note(&app.render_settings.backend, &raster)
+            .unwrap_or_else(|| "Rasterization options are supported by this backend".to_string()),
+    )
+    .size(10)
+    .style(iced::theme::Text::Color(iced::Color::from_rgb(
+        0.45, 0.45, 0.45,
+    )));
+
     let foreground_input = text_input("#RRGGBB or #RRGGBBAA", &app.foreground_input)
         .on_input(Message::ForegroundChanged)
         .size(14);
@@ -541,6 +1206,8 @@ fn render_controls_view(
         .align_items(iced::Alignment::Center),
         backend_info,
         backend_caps,
+        raster_row,
+        raster_note,
     ]
     .spacing(4);
 
@@ -558,12 +1225,42 @@ fn render_controls_view(
         0.45, 0.45, 0.45,
     )));
 
+    let overlay_toggles = OverlayLayer::ALL.iter().fold(
+        row![text("Overlay").size(12)]
+            .spacing(8)
+            .align_items(iced::Alignment::Center),
+        |row, &layer| {
+            row.push(
+                checkbox(layer.to_string(), app.overlay_options.get(layer))
+                    .on_toggle(move |enabled| Message::OverlayLayerToggled(layer, enabled))
+                    .size(14)
+                    .text_size(12),
+            )
+        },
+    );
+
+    let guide_toggles = GuideLine::ALL.iter().fold(
+        row![text("Guides").size(12)]
+            .spacing(8)
+            .align_items(iced::Alignment::Center),
+        |row, &guide| {
+            row.push(
+                checkbox(guide.to_string(), app.guide_lines.contains(&guide))
+                    .on_toggle(move |enabled| Message::GuideLineToggled(guide, enabled))
+                    .size(14)
+                    .text_size(12),
+            )
+        },
+    );
+
     let layout_controls = column![
         text("Preview Layout").size(16),
         row![layout_selector]
             .spacing(8)
             .align_items(iced::Alignment::Center),
         layout_hint,
+        overlay_toggles,
+        guide_toggles,
     ]
     .spacing(6);
 
@@ -594,9 +1291,98 @@ fn render_controls_view(
     let open_render_window_btn = button("Open Render Window")
         .on_press(Message::OpenRenderWindow)
         .style(iced::theme::Button::Secondary);
-    let export_btn = button("Export PNGs")
+    let export_btn = button("Export Images")
         .on_press(Message::ExportPreviews)
         .style(iced::theme::Button::Secondary);
+    let contact_sheet_btn = button("Export Contact Sheet")
+        .on_press(Message::ExportContactSheet)
+        .style(iced::theme::Button::Secondary);
+    let normalization_row = row![
+        text("Normalization").size(12),
+        pick_list(
+            &Normalization::ALL[..],
+            Some(app.render_settings.normalization),
+            Message::NormalizationChanged
+        )
+        .text_size(12),
+        button(text("Compare NFC vs NFD").size(12))
+            .on_press_maybe(app.selected_font.map(|_| Message::CompareNormalization))
+            .style(iced::theme::Button::Secondary),
+        text("Applied after escapes; the comparison uses the diff style and tolerance")
+            .size(10)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.45, 0.45, 0.45
+            ))),
+    ]
+    .spacing(8)
+    .align_items(iced::Alignment::Center);
+    let shaping_btn = button("Inspect Shaping")
+        .on_press_maybe(app.selected_font.map(|_| Message::InspectShaping))
+        .style(iced::theme::Button::Secondary);
+    let reproduce_btn = button("Reproduce from PNG...")
+        .on_press(Message::ReproduceFromImage)
+        .style(iced::theme::Button::Secondary);
+    let sidecar_toggle = checkbox("Write JSON sidecar with exports", app.export_sidecar)
+        .on_toggle(Message::ExportSidecarToggled);
+    let export_template_input = text_input(DEFAULT_NAME_TEMPLATE, &app.export_template)
+        .on_input(Message::ExportTemplateChanged)
+        .padding(6)
+        .size(12);
+    let export_format_selector = pick_list(
+        &ExportFormat::ALL[..],
+        Some(app.export_format),
+        Message::ExportFormatChanged,
+    );
+    let flatten_toggle = checkbox("Flatten onto", app.export_flatten_enabled)
+        .on_toggle(Message::ExportFlattenToggled);
+    let flatten_input = text_input("#FFFFFF", &app.export_flatten_input)
+        .on_input(Message::ExportFlattenColorChanged)
+        .padding(6)
+        .size(12)
+        .width(Length::Fixed(110.0));
+    let scale_toggles = EXPORT_SCALES.iter().fold(
+        row![text("Scales").size(12)]
+            .spacing(8)
+            .align_items(iced::Alignment::Center),
+        |row, &scale| {
+            row.push(
+                checkbox(format!("{scale}×"), app.export_scales.contains(&scale))
+                    .on_toggle(move |enabled| Message::ExportScaleToggled(scale, enabled)),
+            )
+        },
+    );
+    let template_hint = text(format!(
+        "Placeholders: {}. Scales above 1× get an @2x/@3x suffix. JPEG is always flattened (white unless set).",
+        NAME_PLACEHOLDERS
+            .iter()
+            .map(|p| format!("{{{p}}}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    ))
+    .size(10)
+    .style(iced::theme::Text::Color(iced::Color::from_rgb(
+        0.45, 0.45, 0.45,
+    )));
+    let export_controls = column![
+        text("Export").size(16),
+        row![text("File names").size(12), export_template_input]
+            .spacing(8)
+            .align_items(iced::Alignment::Center),
+        row![
+            text("Format").size(12),
+            export_format_selector,
+            flatten_toggle,
+            flatten_input
+        ]
+        .spacing(8)
+        .align_items(iced::Alignment::Center),
+        scale_toggles,
+        template_hint,
+    ]
+    .spacing(6);
+    let export_hint = text("Exported PNGs embed font path/hash, backend, shaper, size, colors and variations; reproduce re-renders from them.")
+        .size(10)
+        .style(iced::theme::Text::Color(iced::Color::from_rgb(0.45, 0.45, 0.45)));
     let shortcut_hint = text("Shortcuts: ⌘/Ctrl+O add fonts, ⌘/Ctrl+R render, ⌘/Ctrl+E export, ⌘/Ctrl+W open render window")
         .size(10)
         .style(iced::theme::Text::Color(iced::Color::from_rgb(0.45, 0.45, 0.45)));
@@ -608,16 +1394,31 @@ fn render_controls_view(
 
     column![
         render_header,
-        sample_text_input,
+        sample_row,
+        decoded_sample,
+        sample_library_row,
+        spacing_proof_row,
+        normalization_row,
         font_size_input,
+        scale_row,
         backend_row,
         layout_controls,
         render_scope_toggle,
         render_scope_hint,
         color_controls,
-        row![render_btn, open_render_window_btn, export_btn]
-            .spacing(10)
-            .align_items(iced::Alignment::Center),
+        export_controls,
+        row![
+            render_btn,
+            open_render_window_btn,
+            export_btn,
+            contact_sheet_btn,
+            shaping_btn,
+            reproduce_btn
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center),
+        sidecar_toggle,
+        export_hint,
         render_hint,
         shortcut_hint,
     ]
@@ -667,7 +1468,258 @@ fn preview_area_view<'a>(
         scrollable(preview_rows(app, false)).into()
     };
 
-    column![preview_header, preview_content].spacing(10).into()
+    let diff_section: Element<Message> = match &app.preview_diff {
+        _ if app.render_previews.len() < 2 => text("").into(),
+        None => text("Tick \"Diff\" on two previews to compare them pixel by pixel.")
+            .size(10)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.45, 0.45, 0.45,
+            )))
+            .into(),
+        Some(diff) => diff_card(app, diff),
+    };
+
+    let normalization_section: Element<Message> = match &app.normalization_compare {
+        Some(compare) => normalization_card(app, compare),
+        None => text("").into(),
+    };
+
+    let shaping_section: Element<Message> = match &app.shaping_inspection {
+        Some(inspection) => shaping_inspector(app, inspection),
+        None => text("").into(),
+    };
+
+    column![
+        preview_header,
+        diff_section,
+        normalization_section,
+        shaping_section,
+        preview_content
+    ]
+    .spacing(10)
+    .into()
+}
+
+/// Width of each column in the shaping table, matching `helpers::SHAPING_COLUMNS`.
+const SHAPING_COLUMN_WIDTHS: [f32; 8] = [32.0, 70.0, 120.0, 60.0, 60.0, 60.0, 60.0, 60.0];
+
+/// Shaping table for the inspected font, next to its bitmap preview when one is rendered.
+fn shaping_inspector<'a>(
+    app: &'a TestypfApp,
+    inspection: &'a crate::types::ShapingInspection,
+) -> Element<'a, Message> {
+    let font_name = app
+        .fonts
+        .get(inspection.font_index)
+        .map(|font| font.full_name.as_str())
+        .unwrap_or("Unknown font");
+
+    let preview: Element<Message> = match app
+        .render_previews
+        .iter()
+        .find(|preview| preview.font_index == inspection.font_index)
+    {
+        Some(preview) => {
+            let (logical_width, logical_height) = helpers::preview_logical_size(preview);
+            iced_image::Image::new(preview.handle.clone())
+                .width(Length::Fixed(logical_width))
+                .height(Length::Fixed(logical_height))
+                .into()
+        }
+        None => text("Render previews to see the bitmap here")
+            .size(10)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.45, 0.45, 0.45,
+            )))
+            .into(),
+    };
+
+    let table_row = |cells: Vec<String>, size: u16| {
+        cells
+            .into_iter()
+            .zip(SHAPING_COLUMN_WIDTHS)
+            .fold(row![].spacing(4), |row, (cell, width)| {
+                row.push(text(cell).size(size).width(Length::Fixed(width)))
+            })
+    };
+
+    let table: Element<Message> = match &inspection.result {
+        Ok(result) if result.glyph_count() == 0 => text("No glyphs").size(12).into(),
+        Ok(result) => {
+            let mut table = column![table_row(
+                helpers::SHAPING_COLUMNS
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect(),
+                12
+            )]
+            .spacing(2);
+            for (index, run) in result.runs.iter().enumerate() {
+                table = table.push(text(helpers::shaping_run_label(index, run)).size(12));
+                for cells in helpers::shaping_table_rows(run) {
+                    table = table.push(table_row(cells.to_vec(), 11));
+                }
+            }
+            scrollable(table).height(Length::Fixed(240.0)).into()
+        }
+        Err(e) => text(format!("Shaping failed: {}", e))
+            .size(12)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.7, 0.25, 0.25,
+            )))
+            .into(),
+    };
+
+    container(
+        column![
+            text(format!("Shaping - {}", font_name)).size(16),
+            text(format!("Text: \"{}\"", inspection.text)).size(12),
+            row![preview, table].spacing(20),
+        ]
+        .spacing(8),
+    )
+    .padding(10)
+    .width(Length::Fill)
+    .style(iced::theme::Container::Box)
+    .into()
+}
+
+/// Diff between the two ticked previews, with style and tolerance controls.
+fn diff_card<'a>(
+    app: &'a TestypfApp,
+    diff: &'a Result<crate::types::PreviewDiff, String>,
+) -> Element<'a, Message> {
+    let name = |position: usize| {
+        app.render_previews
+            .get(position)
+            .and_then(|preview| app.fonts.get(preview.font_index))
+            .map(|font| font.full_name.clone())
+            .unwrap_or_else(|| format!("Preview {}", position + 1))
+    };
+
+    let controls = row![
+        text("Style").size(12),
+        pick_list(
+            testypf_core::diff::DiffStyle::ALL.to_vec(),
+            Some(app.diff_style),
+            Message::DiffStyleChanged
+        ),
+        text("Tolerance").size(12),
+        text_input("0", &app.diff_tolerance_input)
+            .on_input(Message::DiffToleranceChanged)
+            .width(Length::Fixed(60.0))
+            .size(12),
+    ]
+    .spacing(10)
+    .align_items(iced::Alignment::Center);
+
+    let body: Element<Message> = match diff {
+        Ok(diff) => {
+            let scale = diff.scale_factor.max(0.01);
+            let report = &diff.report;
+            let verdict = if report.changed_pixels == 0 {
+                "Renders match".to_string()
+            } else {
+                format!(
+                    "{} of {} pixels differ",
+                    report.changed_pixels,
+                    report.total_pixels()
+                )
+            };
+            column![
+                text(format!("{} vs {}", name(diff.first), name(diff.second))).size(14),
+                iced_image::Image::new(diff.handle.clone())
+                    .width(Length::Fixed(diff.width as f32 / scale))
+                    .height(Length::Fixed(diff.height as f32 / scale)),
+                text(verdict).size(12),
+                text(report.summary())
+                    .size(10)
+                    .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                        0.45, 0.45, 0.45
+                    ))),
+            ]
+            .spacing(6)
+            .into()
+        }
+        Err(e) => text(format!("Diff failed: {}", e))
+            .size(12)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.7, 0.25, 0.25,
+            )))
+            .into(),
+    };
+
+    container(column![text("Diff").size(16), controls, body].spacing(8))
+        .padding(10)
+        .width(Length::Fill)
+        .style(iced::theme::Container::Box)
+        .into()
+}
+
+/// NFC and NFD renders of the sample text side by side with their diff.
+fn normalization_card<'a>(
+    app: &'a TestypfApp,
+    compare: &'a Result<crate::types::NormalizationCompare, String>,
+) -> Element<'a, Message> {
+    let body: Element<Message> = match compare {
+        Ok(compare) => {
+            let scale = compare.scale_factor.max(0.01);
+            let image = |label: String, image: &crate::types::RenderedImage| {
+                column![
+                    text(label).size(11),
+                    iced_image::Image::new(image.handle.clone())
+                        .width(Length::Fixed(image.width as f32 / scale))
+                        .height(Length::Fixed(image.height as f32 / scale)),
+                ]
+                .spacing(4)
+            };
+            let font_name = app
+                .fonts
+                .get(compare.font_index)
+                .map(|font| font.full_name.as_str())
+                .unwrap_or("Unknown font");
+            column![
+                text(font_name).size(14),
+                image(
+                    format!("NFC: {}", escapes::visible_form(&compare.nfc_text)),
+                    &compare.nfc
+                ),
+                image(
+                    format!("NFD: {}", escapes::visible_form(&compare.nfd_text)),
+                    &compare.nfd
+                ),
+                image("Diff".to_string(), &compare.diff),
+                text(helpers::normalization_verdict(compare)).size(12),
+                text(compare.report.summary())
+                    .size(10)
+                    .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                        0.45, 0.45, 0.45
+                    ))),
+            ]
+            .spacing(6)
+            .into()
+        }
+        Err(e) => text(format!("Comparison failed: {}", e))
+            .size(12)
+            .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                0.7, 0.25, 0.25,
+            )))
+            .into(),
+    };
+
+    let header = row![
+        text("NFC vs NFD").size(16).width(Length::Fill),
+        button(text("Close").size(12))
+            .on_press(Message::CloseNormalizationCompare)
+            .style(iced::theme::Button::Secondary),
+    ]
+    .align_items(iced::Alignment::Center);
+
+    container(column![header, body].spacing(8))
+        .padding(10)
+        .width(Length::Fill)
+        .style(iced::theme::Container::Box)
+        .into()
 }
 
 /// Build preview rows for the main window.
@@ -675,7 +1727,8 @@ fn preview_rows(app: &TestypfApp, condensed: bool) -> Element<'_, Message> {
     let cards: Vec<Element<Message>> = app
         .render_previews
         .iter()
-        .filter_map(|preview| {
+        .enumerate()
+        .filter_map(|(position, preview)| {
             let font = app.fonts.get(preview.font_index)?;
             let passes_filter = app.font_matches_filter(font)
                 || (app.render_selected_only && app.selected_font == Some(preview.font_index));
@@ -685,7 +1738,7 @@ fn preview_rows(app: &TestypfApp, condensed: bool) -> Element<'_, Message> {
             Some(if condensed {
                 overlay_preview_card(app, preview)
             } else {
-                preview_card(app, preview)
+                preview_card(app, position, preview)
             })
         })
         .collect();
@@ -696,12 +1749,15 @@ fn preview_rows(app: &TestypfApp, condensed: bool) -> Element<'_, Message> {
 /// Card used in the main window with full metadata.
 fn preview_card<'a>(
     app: &'a TestypfApp,
+    position: usize,
     preview: &'a crate::types::RenderPreview,
 ) -> Element<'a, Message> {
     if let Some(font) = app.fonts.get(preview.font_index) {
-        let image_widget = iced_image::Image::new(preview.handle.clone())
-            .width(Length::Shrink)
-            .height(Length::Shrink);
+        // Previews are rendered at device resolution; show them at logical size
+        let (logical_width, logical_height) = helpers::preview_logical_size(preview);
+        let image_widget = iced_image::Image::new(helpers::preview_display_handle(preview))
+            .width(Length::Fixed(logical_width))
+            .height(Length::Fixed(logical_height));
         let metadata_text = helpers::preview_metadata_text(preview, font, &app.render_settings);
 
         container(
@@ -712,10 +1768,21 @@ fn preview_card<'a>(
                         0.2, 0.2, 0.8
                     ))),
                 text(format!("Sample: \"{}\"", app.render_settings.sample_text)).size(12),
+                text(
+                    helpers::missing_characters_summary(&preview.missing_chars).unwrap_or_default()
+                )
+                .size(12)
+                .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                    0.8, 0.3, 0.3
+                ))),
                 image_widget,
                 text(metadata_text).size(10).style(iced::theme::Text::Color(
                     iced::Color::from_rgb(0.6, 0.6, 0.6)
                 )),
+                checkbox("Diff", app.diff_selection.contains(&position))
+                    .on_toggle(move |enabled| Message::DiffPreviewToggled(position, enabled))
+                    .size(14)
+                    .text_size(12),
             ]
             .spacing(5),
         )
@@ -744,9 +1811,11 @@ fn overlay_preview_card<'a>(
     preview: &'a crate::types::RenderPreview,
 ) -> Element<'a, Message> {
     if let Some(font) = app.fonts.get(preview.font_index) {
-        let image_widget = iced_image::Image::new(preview.handle.clone())
-            .width(Length::Shrink)
-            .height(Length::Shrink);
+        // Previews are rendered at device resolution; show them at logical size
+        let (logical_width, logical_height) = helpers::preview_logical_size(preview);
+        let image_widget = iced_image::Image::new(helpers::preview_display_handle(preview))
+            .width(Length::Fixed(logical_width))
+            .height(Length::Fixed(logical_height));
         let metadata_text = helpers::preview_metadata_text(preview, font, &app.render_settings);
 
         container(
@@ -75,9 +75,11 @@ fn overlay_preview_card<'a>(
     preview: &'a crate::types::RenderPreview,
 ) -> Element<'a, Message> {
     if let Some(font) = app.fonts.get(preview.font_index) {
-        let image_widget = iced_image::Image::new(preview.handle.clone())
-            .width(Length::Shrink)
-            .height(Length::Shrink);
+        // Previews are rendered at device resolution; show them at logical size
+        let (logical_width, logical_height) = helpers::preview_logical_size(preview);
+        let image_widget = iced_image::Image::new(helpers::preview_display_handle(preview))
+            .width(Length::Fixed(logical_width))
+            .height(Length::Fixed(logical_height));
         let metadata_text = helpers::preview_metadata_text(preview, font, &app.render_settings);
 
         container(
@@ -110,9 +112,11 @@ fn preview_card<'a>(
     preview: &'a crate::types::RenderPreview,
 ) -> Element<'a, Message> {
     if let Some(font) = app.fonts.get(preview.font_index) {
-        let image_widget = iced_image::Image::new(preview.handle.clone())
-            .width(Length::Shrink)
-            .height(Length::Shrink);
+        // Previews are rendered at device resolution; show them at logical size
+        let (logical_width, logical_height) = helpers::preview_logical_size(preview);
+        let image_widget = iced_image::Image::new(helpers::preview_display_handle(preview))
+            .width(Length::Fixed(logical_width))
+            .height(Length::Fixed(logical_height));
         let metadata_text = helpers::preview_metadata_text(preview, font, &app.render_settings);
 
         container(
@@ -123,6 +127,13 @@ fn preview_card<'a>(
                         0.2, 0.2, 0.8
                     ))),
                 text(format!("Sample: \"{}\"", app.render_settings.sample_text)).size(12),
+                text(
+                    helpers::missing_characters_summary(&preview.missing_chars).unwrap_or_default()
+                )
+                .size(12)
+                .style(iced::theme::Text::Color(iced::Color::from_rgb(
+                    0.8, 0.3, 0.3
+                ))),
                 image_widget,
                 text(metadata_text).size(10).style(iced::theme::Text::Color(
                     iced::Color::from_rgb(0.6, 0.6, 0.6)